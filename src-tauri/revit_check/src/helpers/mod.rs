@@ -0,0 +1,137 @@
+//! Revit process detection and control.
+//!
+//! The real implementation is built on the Win32 API and `tasklist`. On every other platform Revit can't be
+//! running, so the functions report that there is nothing to do. This keeps crates that depend on this one
+//! compiling (and testable) on non-Windows machines.
+
+#[cfg(windows)]
+mod windows;
+#[cfg(windows)]
+pub use windows::*;
+
+#[cfg(not(windows))]
+mod unsupported;
+#[cfg(not(windows))]
+pub use unsupported::*;
+
+#[derive(Debug, Clone)]
+pub struct RevitProcessInfo {
+    /// The raw handle of the process's main window. Will be 0 if no window was found
+    pub hwnd: isize,
+    pub process_id: u32,
+    pub window_title: String,
+    pub executable_path: Option<String>, // Store the executable path for reopening, None if it could not be read
+    /// The documents Revit was started with, e.g. by opening a .rvt file. They are opened again when reopening
+    pub documents: Vec<String>,
+}
+
+#[derive(Debug)]
+pub struct CloseRevitResult {
+    pub closed_count: usize,
+    pub total_count: usize,
+    pub message: String,
+}
+
+#[derive(Debug)]
+pub struct ReopenRevitResult {
+    pub reopened_count: usize,
+    pub total_count: usize,
+    pub message: String,
+}
+
+/// Finds the first Revit version (a year such as "2026") in a path, e.g. an executable path like
+/// `C:\Program Files\Autodesk\Revit 2025\Revit.exe`, a process name like `Revit2025.exe` or a registry folder
+/// like "2026/SomeAddin"
+pub fn revit_version_from_path(path: &str) -> Option<String> {
+    path.split(|c: char| !c.is_ascii_digit())
+        .filter(|part| part.len() == 4)
+        .find(|part| {
+            part.parse::<u16>()
+                .is_ok_and(|year| (2000..2100).contains(&year))
+        })
+        .map(|part| part.to_string())
+}
+
+/// The Revit documents in a process's command line,
+/// e.g. `"C:\Program Files\Autodesk\Revit 2025\Revit.exe" "D:\Projects\Tower.rvt"`
+#[cfg_attr(not(windows), allow(dead_code))]
+pub fn documents_from_command_line(command_line: &str) -> Vec<String> {
+    let mut arguments = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    for c in command_line.chars() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            c if c.is_whitespace() && !in_quotes => {
+                if !current.is_empty() {
+                    arguments.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        arguments.push(current);
+    }
+
+    let document_extensions = ["rvt", "rfa", "rte", "rft"];
+    arguments
+        .into_iter()
+        // The first argument is Revit itself
+        .skip(1)
+        .filter(|argument| {
+            std::path::Path::new(argument)
+                .extension()
+                .is_some_and(|extension| {
+                    document_extensions
+                        .iter()
+                        .any(|document| extension.eq_ignore_ascii_case(document))
+                })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_revit_version_from_path() {
+        assert_eq!(
+            revit_version_from_path(r"C:\Program Files\Autodesk\Revit 2025\Revit.exe").as_deref(),
+            Some("2025")
+        );
+        assert_eq!(
+            revit_version_from_path("Revit2024.exe").as_deref(),
+            Some("2024")
+        );
+        assert_eq!(revit_version_from_path("Revit.exe"), None);
+        assert_eq!(
+            revit_version_from_path(r"D:\Apps\Revit Preview\Revit.exe"),
+            None
+        );
+        assert_eq!(
+            revit_version_from_path("2026/SomeAddin"),
+            Some("2026".to_string())
+        );
+        assert_eq!(
+            revit_version_from_path("Revit 2019 Only/SomeAddin"),
+            Some("2019".to_string())
+        );
+        assert_eq!(revit_version_from_path("All Versions/SomeAddin"), None);
+        assert_eq!(revit_version_from_path("Addin12345/v1"), None);
+    }
+
+    #[test]
+    fn test_documents_from_command_line() {
+        assert_eq!(
+            documents_from_command_line(
+                r#""C:\Program Files\Autodesk\Revit 2025\Revit.exe" "D:\My Projects\Tower.RVT" /language ENU"#
+            ),
+            vec![r"D:\My Projects\Tower.RVT".to_string()]
+        );
+        assert!(
+            documents_from_command_line(r"C:\Autodesk\Revit 2025\Revit.exe /nosplash").is_empty()
+        );
+    }
+}
@@ -0,0 +1,46 @@
+//! Stand-ins for platforms that Revit doesn't run on
+
+use super::{CloseRevitResult, ReopenRevitResult, RevitProcessInfo};
+
+/// Revit only runs on Windows, so it is never running here
+pub fn is_revit_running() -> Result<bool, String> {
+    Ok(false)
+}
+
+pub fn get_running_revit_processes() -> Result<Vec<RevitProcessInfo>, String> {
+    Ok(Vec::new())
+}
+
+pub fn get_running_revit_process_ids() -> Result<Vec<u32>, String> {
+    Ok(Vec::new())
+}
+
+pub fn get_running_revit_versions() -> Result<Vec<Option<String>>, String> {
+    Ok(Vec::new())
+}
+
+pub fn close_revit_windows() -> Result<CloseRevitResult, String> {
+    Ok(CloseRevitResult {
+        closed_count: 0,
+        total_count: 0,
+        message: "No Revit processes found".to_string(),
+    })
+}
+
+pub fn close_revit_windows_with_reopen_info()
+-> Result<(CloseRevitResult, Vec<RevitProcessInfo>), String> {
+    Ok((close_revit_windows()?, Vec::new()))
+}
+
+pub fn reopen_revit_windows(
+    processes_to_reopen: &[RevitProcessInfo],
+) -> Result<ReopenRevitResult, String> {
+    if processes_to_reopen.is_empty() {
+        return Ok(ReopenRevitResult {
+            reopened_count: 0,
+            total_count: 0,
+            message: "No Revit processes to reopen".to_string(),
+        });
+    }
+    Err("Reopening Revit is only supported on Windows".to_string())
+}
@@ -0,0 +1,508 @@
+use std::{os::windows::process::CommandExt, process::Command};
+
+use super::{
+    CloseRevitResult, ReopenRevitResult, RevitProcessInfo, documents_from_command_line,
+    revit_version_from_path,
+};
+use log::{info, warn};
+use windows::Win32::{
+    Foundation::{CloseHandle, HANDLE, HWND, LPARAM, UNICODE_STRING, WPARAM},
+    System::Diagnostics::ToolHelp::{
+        CreateToolhelp32Snapshot, PROCESSENTRY32W, Process32FirstW, Process32NextW,
+        TH32CS_SNAPPROCESS,
+    },
+    System::Threading::{
+        CREATE_NO_WINDOW, OpenProcess, PROCESS_NAME_WIN32, PROCESS_QUERY_LIMITED_INFORMATION,
+        QueryFullProcessImageNameW,
+    },
+    UI::WindowsAndMessaging::{
+        EnumWindows, GetWindowTextLengthW, GetWindowTextW, GetWindowThreadProcessId, PostMessageW,
+        WM_CLOSE,
+    },
+};
+use windows::{
+    Wdk::System::Threading::{NtQueryInformationProcess, ProcessCommandLineInformation},
+    core::PWSTR,
+};
+
+/// The image names of the Revit processes, compared case-insensitively
+const REVIT_PROCESS_NAMES: [&str; 5] = [
+    "Revit.exe",
+    "Revit2024.exe",
+    "Revit2025.exe",
+    "Revit2026.exe",
+    "Revit2027.exe",
+];
+
+/// Check if any version of Autodesk Revit is currently running
+pub fn is_revit_running() -> Result<bool, String> {
+    Ok(!find_revit_processes()?.is_empty())
+}
+
+/// The process IDs of all running Revit processes
+pub fn get_running_revit_process_ids() -> Result<Vec<u32>, String> {
+    Ok(find_revit_processes()?
+        .into_iter()
+        .map(|(process_id, _)| process_id)
+        .collect())
+}
+
+/// Get a list of all running Revit processes with their window titles
+pub fn get_running_revit_processes() -> Result<Vec<RevitProcessInfo>, String> {
+    Ok(find_revit_processes()?
+        .into_iter()
+        // For each Revit process, try to find its main window
+        .filter_map(|(process_id, _)| get_process_info_from_pid(process_id).ok())
+        .collect())
+}
+
+/// The version of each running Revit process, or None if it couldn't be determined
+///
+/// Versioned process names tell the version directly, otherwise it is read from the executable path
+pub fn get_running_revit_versions() -> Result<Vec<Option<String>>, String> {
+    Ok(find_revit_processes()?
+        .into_iter()
+        .map(|(process_id, image_name)| {
+            revit_version_from_path(&image_name).or_else(|| {
+                query_process_executable_path(process_id)
+                    .ok()
+                    .and_then(|path| revit_version_from_path(&path))
+            })
+        })
+        .collect())
+}
+
+/// Attempt to gracefully close all open Revit windows
+/// This sends WM_CLOSE messages to Revit windows, allowing Revit to prompt users to save work
+pub fn close_revit_windows() -> Result<CloseRevitResult, String> {
+    let running_processes = get_running_revit_processes()?;
+
+    if running_processes.is_empty() {
+        return Ok(CloseRevitResult {
+            closed_count: 0,
+            total_count: 0,
+            message: "No Revit processes found".to_string(),
+        });
+    }
+
+    let mut closed_count = 0;
+    let total_count = running_processes.len();
+
+    info!("Found {} Revit processes", total_count);
+    for process_info in &running_processes {
+        if process_info.hwnd != 0 {
+            match send_close_message_to_window(HWND(process_info.hwnd)) {
+                Ok(()) => {
+                    closed_count += 1;
+                    info!(
+                        "Asked Revit to close (PID: {}, title: '{}')",
+                        process_info.process_id, process_info.window_title
+                    );
+                }
+                Err(e) => warn!(
+                    "Failed to ask Revit to close (PID: {}): {}",
+                    process_info.process_id, e
+                ),
+            }
+        } else {
+            // Without a window Revit can't be asked to close, and killing it would lose unsaved work
+            warn!(
+                "Revit has no window, leaving it running (PID: {})",
+                process_info.process_id
+            );
+        }
+    }
+
+    let message = if closed_count == total_count {
+        format!(
+            "Successfully sent close requests to all {} Revit windows",
+            closed_count
+        )
+    } else {
+        format!(
+            "Sent close requests to {}/{} Revit windows",
+            closed_count, total_count
+        )
+    };
+
+    Ok(CloseRevitResult {
+        closed_count,
+        total_count,
+        message,
+    })
+}
+
+// Helper functions
+
+/// A snapshot of the running processes, closed when dropped
+struct ProcessSnapshot(HANDLE);
+
+impl Drop for ProcessSnapshot {
+    fn drop(&mut self) {
+        let _ = unsafe { CloseHandle(self.0) };
+    }
+}
+
+/// The ID and image name of every running Revit process, read from a single snapshot of the running processes
+fn find_revit_processes() -> Result<Vec<(u32, String)>, String> {
+    let snapshot = unsafe { CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0) }
+        .map(ProcessSnapshot)
+        .map_err(|e| format!("Failed to list the running processes: {:?}", e))?;
+    let mut entry = PROCESSENTRY32W {
+        dwSize: std::mem::size_of::<PROCESSENTRY32W>() as u32,
+        ..Default::default()
+    };
+    let mut revit_processes = Vec::new();
+    let mut has_entry = unsafe { Process32FirstW(snapshot.0, &mut entry) }.is_ok();
+    while has_entry {
+        let name_length = entry
+            .szExeFile
+            .iter()
+            .position(|&character| character == 0)
+            .unwrap_or(entry.szExeFile.len());
+        let image_name = String::from_utf16_lossy(&entry.szExeFile[..name_length]);
+        if REVIT_PROCESS_NAMES
+            .iter()
+            .any(|name| name.eq_ignore_ascii_case(&image_name))
+        {
+            revit_processes.push((entry.th32ProcessID, image_name));
+        }
+        has_entry = unsafe { Process32NextW(snapshot.0, &mut entry) }.is_ok();
+    }
+    Ok(revit_processes)
+}
+
+/// A handle that can only query the process, closed when dropped
+struct ProcessQueryHandle(HANDLE);
+
+impl ProcessQueryHandle {
+    fn open(process_id: u32) -> Result<Self, String> {
+        unsafe { OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, process_id) }
+            .map(Self)
+            .map_err(|e| format!("Failed to open process {}: {:?}", process_id, e))
+    }
+}
+
+impl Drop for ProcessQueryHandle {
+    fn drop(&mut self) {
+        let _ = unsafe { CloseHandle(self.0) };
+    }
+}
+
+/// The full path of the executable a process runs
+fn query_process_executable_path(process_id: u32) -> Result<String, String> {
+    let process = ProcessQueryHandle::open(process_id)?;
+    // Long enough for extended-length paths
+    let mut buffer = vec![0u16; 32 * 1024];
+    let mut length = buffer.len() as u32;
+    unsafe {
+        QueryFullProcessImageNameW(
+            process.0,
+            PROCESS_NAME_WIN32,
+            PWSTR(buffer.as_mut_ptr()),
+            &mut length,
+        )
+    }
+    .map_err(|e| {
+        format!(
+            "Failed to get the executable path of process {}: {:?}",
+            process_id, e
+        )
+    })?;
+    Ok(String::from_utf16_lossy(&buffer[..length as usize]))
+}
+
+/// The command line a process was started with
+fn query_process_command_line(process_id: u32) -> Option<String> {
+    let process = ProcessQueryHandle::open(process_id).ok()?;
+    // The first call only reports how much room the command line needs
+    let mut length = 0u32;
+    let _ = unsafe {
+        NtQueryInformationProcess(
+            process.0,
+            ProcessCommandLineInformation,
+            std::ptr::null_mut(),
+            0,
+            &mut length,
+        )
+    };
+    if length == 0 {
+        return None;
+    }
+    // u64s, so that the UNICODE_STRING at the start is aligned
+    let mut buffer = vec![0u64; (length as usize).div_ceil(8)];
+    let status = unsafe {
+        NtQueryInformationProcess(
+            process.0,
+            ProcessCommandLineInformation,
+            buffer.as_mut_ptr().cast(),
+            length,
+            &mut length,
+        )
+    };
+    if !status.is_ok() {
+        return None;
+    }
+    // The UNICODE_STRING points into the rest of the buffer
+    let command_line = unsafe { &*(buffer.as_ptr() as *const UNICODE_STRING) };
+    if command_line.Buffer.is_null() {
+        return None;
+    }
+    let characters = unsafe {
+        std::slice::from_raw_parts(
+            command_line.Buffer.0,
+            command_line.Length as usize / std::mem::size_of::<u16>(),
+        )
+    };
+    Some(String::from_utf16_lossy(characters))
+}
+
+/// The executable a process runs, None if it can't be read. It's never guessed, since starting another installed
+/// Revit version than the one that was closed would load the wrong addins
+fn get_process_executable_path(process_id: u32) -> Option<String> {
+    query_process_executable_path(process_id)
+        .inspect_err(|e| warn!("{}", e))
+        .ok()
+}
+
+fn get_process_info_from_pid(process_id: u32) -> Result<RevitProcessInfo, String> {
+    // Use EnumWindows to find windows belonging to this specific process
+    let mut found_hwnd = HWND::default();
+    let target_pid = process_id;
+
+    let enum_result = unsafe {
+        EnumWindows(
+            Some(enum_windows_proc_for_pid),
+            LPARAM(&mut (found_hwnd, target_pid) as *mut (HWND, u32) as isize),
+        )
+    };
+
+    // Try to get the executable path for this process
+    let executable_path = get_process_executable_path(process_id);
+    let documents = query_process_command_line(process_id)
+        .map(|command_line| documents_from_command_line(&command_line))
+        .unwrap_or_default();
+
+    if enum_result.is_ok() && found_hwnd.0 != 0 {
+        let window_title = get_window_title(found_hwnd)?;
+        return Ok(RevitProcessInfo {
+            hwnd: found_hwnd.0,
+            process_id,
+            window_title,
+            executable_path,
+            documents,
+        });
+    }
+
+    // If no window found, create a dummy entry
+    Ok(RevitProcessInfo {
+        hwnd: 0,
+        process_id,
+        window_title: format!("Revit Process (PID: {})", process_id),
+        executable_path: None,
+        documents,
+    })
+}
+
+unsafe extern "system" fn enum_windows_proc_for_pid(
+    hwnd: HWND,
+    lparam: LPARAM,
+) -> windows::Win32::Foundation::BOOL {
+    let mut process_id: u32 = 0;
+    let _thread_id = unsafe { GetWindowThreadProcessId(hwnd, Some(&mut process_id)) };
+
+    // Get the target PID and found_hwnd from lparam
+    let data_ptr = lparam.0 as *mut (HWND, u32);
+    let (ref mut found_hwnd, target_pid) = unsafe { *data_ptr };
+
+    // Check if this window belongs to the specific process we're looking for
+    if process_id == target_pid {
+        // Found a window for our target process, store it
+        *found_hwnd = hwnd;
+        return windows::Win32::Foundation::BOOL(0); // Stop enumeration
+    }
+
+    windows::Win32::Foundation::BOOL(1) // Continue enumeration
+}
+
+fn get_window_title(hwnd: HWND) -> Result<String, String> {
+    let title_length = unsafe { GetWindowTextLengthW(hwnd) };
+
+    if title_length == 0 {
+        return Ok("".to_string());
+    }
+
+    let mut title_buffer = vec![0u16; (title_length + 1) as usize];
+    let chars_written = unsafe { GetWindowTextW(hwnd, &mut title_buffer) };
+
+    if chars_written == 0 {
+        return Ok("".to_string());
+    }
+
+    // Convert UTF-16 to String
+    let title = String::from_utf16(&title_buffer[..chars_written as usize])
+        .map_err(|e| format!("Failed to convert window title to UTF-8: {}", e))?;
+
+    Ok(title)
+}
+
+fn send_close_message_to_window(hwnd: HWND) -> Result<(), String> {
+    // WM_CLOSE closes Revit as if the user did, so it still prompts to save unsaved work
+    let result1 = unsafe { PostMessageW(hwnd, WM_CLOSE, WPARAM(0), LPARAM(0)) };
+
+    if result1.is_ok() {
+        return Ok(());
+    }
+
+    Err("WM_CLOSE message failed".to_string())
+}
+
+/// Store information about Revit processes before closing them
+/// This allows us to reopen them later
+pub fn close_revit_windows_with_reopen_info()
+-> Result<(CloseRevitResult, Vec<RevitProcessInfo>), String> {
+    let running_processes = get_running_revit_processes()?;
+
+    if running_processes.is_empty() {
+        return Ok((
+            CloseRevitResult {
+                closed_count: 0,
+                total_count: 0,
+                message: "No Revit processes found".to_string(),
+            },
+            Vec::new(),
+        ));
+    }
+
+    // Only the processes that were asked to close are reopened
+    let mut processes_to_reopen = Vec::new();
+
+    let mut closed_count = 0;
+    let total_count = running_processes.len();
+
+    info!("Found {} Revit processes", total_count);
+    for process_info in &running_processes {
+        if process_info.hwnd != 0 {
+            match send_close_message_to_window(HWND(process_info.hwnd)) {
+                Ok(()) => {
+                    closed_count += 1;
+                    processes_to_reopen.push(process_info.clone());
+                    info!(
+                        "Asked Revit to close (PID: {}, title: '{}')",
+                        process_info.process_id, process_info.window_title
+                    );
+                }
+                Err(e) => warn!(
+                    "Failed to ask Revit to close (PID: {}): {}",
+                    process_info.process_id, e
+                ),
+            }
+        } else {
+            // Without a window Revit can't be asked to close, and killing it would lose unsaved work
+            warn!(
+                "Revit has no window, leaving it running (PID: {})",
+                process_info.process_id
+            );
+        }
+    }
+
+    let message = if closed_count == total_count {
+        format!(
+            "Successfully sent close requests to all {} Revit processes",
+            closed_count
+        )
+    } else {
+        format!(
+            "Sent close requests to {}/{} Revit processes, the others could not be closed",
+            closed_count, total_count
+        )
+    };
+
+    Ok((
+        CloseRevitResult {
+            closed_count,
+            total_count,
+            message,
+        },
+        processes_to_reopen,
+    ))
+}
+
+/// Reopen Revit windows using stored process information
+pub fn reopen_revit_windows(
+    processes_to_reopen: &[RevitProcessInfo],
+) -> Result<ReopenRevitResult, String> {
+    if processes_to_reopen.is_empty() {
+        return Ok(ReopenRevitResult {
+            reopened_count: 0,
+            total_count: 0,
+            message: "No Revit processes to reopen".to_string(),
+        });
+    }
+
+    let mut reopened_count = 0;
+    let mut unknown_path_count = 0;
+    let total_count = processes_to_reopen.len();
+
+    info!("Reopening {} Revit processes", total_count);
+    for process_info in processes_to_reopen {
+        if let Some(exec_path) = &process_info.executable_path {
+            // Try to reopen using the stored executable path
+            match launch_revit_executable(exec_path, &process_info.documents) {
+                Ok(()) => {
+                    reopened_count += 1;
+                    info!("Reopened Revit from {}", exec_path);
+                }
+                Err(e) => warn!("Failed to reopen Revit: {}", e),
+            }
+        } else {
+            unknown_path_count += 1;
+            warn!(
+                "Not reopening Revit, its executable could not be read (PID: {}, title: '{}')",
+                process_info.process_id, process_info.window_title
+            );
+        }
+    }
+
+    let message = if reopened_count == total_count {
+        format!(
+            "Successfully reopened all {} Revit processes",
+            reopened_count
+        )
+    } else if unknown_path_count > 0 {
+        format!(
+            "Reopened {}/{} Revit processes, {} could not be reopened because their executable could not be read",
+            reopened_count, total_count, unknown_path_count
+        )
+    } else {
+        format!(
+            "Reopened {}/{} Revit processes",
+            reopened_count, total_count
+        )
+    };
+
+    Ok(ReopenRevitResult {
+        reopened_count,
+        total_count,
+        message,
+    })
+}
+
+/// Launch Revit using a specific executable path, opening the documents that still exist
+fn launch_revit_executable(exec_path: &str, documents: &[String]) -> Result<(), String> {
+    let result = Command::new(exec_path)
+        .args(
+            documents
+                .iter()
+                .filter(|document| std::path::Path::new(document).exists()),
+        )
+        .creation_flags(CREATE_NO_WINDOW.0)
+        .spawn()
+        .map_err(|e| format!("Failed to launch Revit at {}: {}", exec_path, e))?;
+
+    if result.id() > 0 {
+        Ok(())
+    } else {
+        Err("Failed to launch Revit process".to_string())
+    }
+}
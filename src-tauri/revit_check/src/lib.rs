@@ -2,6 +2,10 @@ use crate::models::RevitProcessModel;
 
 mod helpers;
 mod models;
+mod watcher;
+pub use helpers::{ReopenRevitResult, revit_version_from_path};
+pub use models::RunningRevitVersions;
+pub use watcher::*;
 
 pub fn get_revit_processes() -> Result<Vec<RevitProcessModel>, String> {
     let result = helpers::get_running_revit_processes()?;
@@ -14,3 +18,90 @@ pub async fn is_revit_running() -> Result<bool, String> {
         .map_err(|e| e.to_string())?
         .map_err(|e| e.to_string())
 }
+
+/// Which Revit versions are running, so that only the addins of those versions have to wait for Revit to close
+pub async fn get_running_revit_versions() -> Result<RunningRevitVersions, String> {
+    let process_versions = tokio::task::spawn_blocking(helpers::get_running_revit_versions)
+        .await
+        .map_err(|e| e.to_string())??;
+    Ok(RunningRevitVersions::from_process_versions(
+        process_versions,
+    ))
+}
+
+/// The Revit processes that were asked to close, so that they can be started again afterwards
+pub struct RevitCloseRequest {
+    processes: Vec<helpers::RevitProcessInfo>,
+    /// How many of the running processes accepted the request. The others, e.g. without a window, keep running
+    pub closed_count: usize,
+    pub total_count: usize,
+    pub message: String,
+}
+
+impl RevitCloseRequest {
+    pub fn is_empty(&self) -> bool {
+        self.processes.is_empty()
+    }
+}
+
+/// Asks every Revit window to close, as if the user closed it, so Revit still prompts to save unsaved work.
+/// Processes without a window can't be asked and are left running. They count as not closed and are not reopened,
+/// so the updates of their versions stay deferred
+pub async fn request_revit_close() -> Result<RevitCloseRequest, String> {
+    let (close_result, processes) =
+        tokio::task::spawn_blocking(helpers::close_revit_windows_with_reopen_info)
+            .await
+            .map_err(|e| e.to_string())??;
+    Ok(RevitCloseRequest {
+        processes,
+        closed_count: close_result.closed_count,
+        total_count: close_result.total_count,
+        message: close_result.message,
+    })
+}
+
+/// Waits for the processes that were asked to close to exit. Returns false if some are still running after the
+/// timeout, e.g. because the user canceled a save prompt
+pub async fn wait_for_revit_exit(
+    request: &RevitCloseRequest,
+    timeout: std::time::Duration,
+) -> Result<bool, String> {
+    let started = std::time::Instant::now();
+    loop {
+        if request_still_running(request).await?.is_empty() {
+            return Ok(true);
+        }
+        if started.elapsed() >= timeout {
+            return Ok(false);
+        }
+        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+    }
+}
+
+/// Starts the closed processes again, with the documents they were started with where those are known.
+/// Processes that didn't exit or whose executable could not be read are skipped
+pub async fn reopen_revit(request: &RevitCloseRequest) -> Result<ReopenRevitResult, String> {
+    let still_running = request_still_running(request).await?;
+    let processes: Vec<helpers::RevitProcessInfo> = request
+        .processes
+        .iter()
+        .filter(|process| !still_running.contains(&process.process_id))
+        .cloned()
+        .collect();
+    tokio::task::spawn_blocking(move || helpers::reopen_revit_windows(&processes))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// The process IDs of the request that are still running
+async fn request_still_running(request: &RevitCloseRequest) -> Result<Vec<u32>, String> {
+    let running = tokio::task::spawn_blocking(helpers::get_running_revit_process_ids)
+        .await
+        .map_err(|e| e.to_string())??;
+    Ok(request
+        .processes
+        .iter()
+        .map(|process| process.process_id)
+        .filter(|process_id| running.contains(process_id))
+        .collect())
+}
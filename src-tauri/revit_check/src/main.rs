@@ -1,18 +1,16 @@
-use crate::{helpers::CloseRevitResult, models::RevitProcessModel};
+use std::time::Duration;
 
-mod helpers;
-mod models;
-
-fn main() -> Result<(), String> {
+#[tokio::main]
+async fn main() -> Result<(), String> {
     // Close Revit and store info
-    let (close_result, processes_to_reopen) = helpers::close_revit_windows_with_reopen_info()?;
-    println!("Closed: {}", close_result.message);
+    let close_request = revit_check::request_revit_close().await?;
+    println!("Closed: {}", close_request.message);
 
-    // Wait a moment to ensure processes are fully closed
-    std::thread::sleep(std::time::Duration::from_secs(2));
+    // Wait for the processes to be fully closed
+    revit_check::wait_for_revit_exit(&close_request, Duration::from_secs(30)).await?;
 
     // Reopen Revit
-    let reopen_result = helpers::reopen_revit_windows(&processes_to_reopen)?;
+    let reopen_result = revit_check::reopen_revit(&close_request).await?;
     println!("Reopened: {}", reopen_result.message);
 
     Ok(())
@@ -13,9 +13,51 @@ pub struct RevitProcessModel {
 impl From<RevitProcessInfo> for RevitProcessModel {
     fn from(process: RevitProcessInfo) -> Self {
         Self {
-            hwnd: process.hwnd.0 as u32,
+            hwnd: process.hwnd as u32,
             process_id: process.process_id,
             window_title: process.window_title,
         }
     }
 }
+
+/// Which Revit versions are running, e.g. "2025"
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunningRevitVersions {
+    pub versions: Vec<String>,
+    /// Set if the version of a running Revit couldn't be determined. Every version counts as running then
+    pub has_unknown_version: bool,
+}
+
+impl RunningRevitVersions {
+    /// Takes the version of each running Revit process, None if it is unknown
+    pub fn from_process_versions(process_versions: Vec<Option<String>>) -> Self {
+        let mut running = Self::default();
+        for version in process_versions {
+            match version {
+                Some(version) if !running.versions.contains(&version) => {
+                    running.versions.push(version)
+                }
+                Some(_) => {}
+                None => running.has_unknown_version = true,
+            }
+        }
+        running
+    }
+
+    /// For when the running processes couldn't be listed. Every version counts as running
+    pub fn unknown() -> Self {
+        Self {
+            versions: Vec::new(),
+            has_unknown_version: true,
+        }
+    }
+
+    pub fn is_any_running(&self) -> bool {
+        self.has_unknown_version || !self.versions.is_empty()
+    }
+
+    pub fn is_running(&self, version: &str) -> bool {
+        self.has_unknown_version || self.versions.iter().any(|running| running == version)
+    }
+}
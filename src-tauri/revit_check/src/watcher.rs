@@ -0,0 +1,207 @@
+//! Notices when Revit starts or exits, so that work waiting for Revit to close can start right away
+
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread,
+    time::Duration,
+};
+
+use log::warn;
+use serde::Serialize;
+use tokio::sync::broadcast;
+
+use crate::{helpers, models::RunningRevitVersions};
+
+/// Where the watcher learns which Revit versions are running. Tests use a scripted source instead of the system
+pub trait RevitProcessSource: Send + 'static {
+    fn running_versions(&mut self) -> Result<RunningRevitVersions, String>;
+}
+
+/// Reads the running Revit processes from the system
+pub struct SystemProcessSource;
+
+impl RevitProcessSource for SystemProcessSource {
+    fn running_versions(&mut self) -> Result<RunningRevitVersions, String> {
+        Ok(RunningRevitVersions::from_process_versions(
+            helpers::get_running_revit_versions()?,
+        ))
+    }
+}
+
+/// What changed between two looks at the running Revit processes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RevitProcessChange {
+    pub started: Vec<String>,
+    pub exited: Vec<String>,
+    /// What is running now
+    pub running: RunningRevitVersions,
+}
+
+impl RevitProcessChange {
+    /// None if nothing changed
+    pub fn between(
+        previous: &RunningRevitVersions,
+        current: &RunningRevitVersions,
+    ) -> Option<Self> {
+        let started: Vec<String> = current
+            .versions
+            .iter()
+            .filter(|version| !previous.versions.contains(version))
+            .cloned()
+            .collect();
+        let exited: Vec<String> = previous
+            .versions
+            .iter()
+            .filter(|version| !current.versions.contains(version))
+            .cloned()
+            .collect();
+        let unknown_changed = previous.has_unknown_version != current.has_unknown_version;
+        if started.is_empty() && exited.is_empty() && !unknown_changed {
+            return None;
+        }
+        Some(Self {
+            started,
+            exited,
+            running: current.clone(),
+        })
+    }
+
+    /// Whether a Revit closed, which may let work that waited for it go ahead
+    pub fn has_exits(&self) -> bool {
+        !self.exited.is_empty() || !self.running.is_any_running()
+    }
+}
+
+/// Polls the process source on a background thread and broadcasts every change. Stops when dropped
+///
+/// `spawn` hands back a receiver that exists before the thread starts, so no change is missed
+pub struct RevitProcessWatcher {
+    sender: broadcast::Sender<RevitProcessChange>,
+    stopped: Arc<AtomicBool>,
+}
+
+impl RevitProcessWatcher {
+    pub fn spawn(
+        mut source: impl RevitProcessSource,
+        poll_interval: Duration,
+    ) -> (Self, broadcast::Receiver<RevitProcessChange>) {
+        let (sender, receiver) = broadcast::channel(16);
+        let stopped = Arc::new(AtomicBool::new(false));
+        let thread_sender = sender.clone();
+        let thread_stopped = stopped.clone();
+        // Reading the processes blocks, so it gets its own thread instead of an async task
+        thread::spawn(move || {
+            let mut previous: Option<RunningRevitVersions> = None;
+            // Only logged when it changes, instead of on every poll
+            let mut last_error: Option<String> = None;
+            while !thread_stopped.load(Ordering::SeqCst) {
+                match source.running_versions() {
+                    Ok(current) => {
+                        // The first look is only the starting point
+                        if let Some(change) = previous
+                            .as_ref()
+                            .and_then(|previous| RevitProcessChange::between(previous, &current))
+                        {
+                            // Nobody listening is fine
+                            let _ = thread_sender.send(change);
+                        }
+                        previous = Some(current);
+                        last_error = None;
+                    }
+                    Err(e) => {
+                        if last_error.as_ref() != Some(&e) {
+                            warn!("Could not read the running Revit processes: {}", e);
+                        }
+                        last_error = Some(e);
+                    }
+                }
+                thread::sleep(poll_interval);
+            }
+        });
+        (Self { sender, stopped }, receiver)
+    }
+
+    /// Listens to the changes from now on. Changes broadcast before this call are not received
+    pub fn subscribe(&self) -> broadcast::Receiver<RevitProcessChange> {
+        self.sender.subscribe()
+    }
+}
+
+impl Drop for RevitProcessWatcher {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use super::*;
+
+    /// Plays back a fixed list of states, then keeps reporting the last one
+    struct FakeProcessSource {
+        states: VecDeque<Vec<&'static str>>,
+        last: Vec<&'static str>,
+    }
+
+    impl FakeProcessSource {
+        fn new(states: &[&[&'static str]]) -> Self {
+            Self {
+                states: states.iter().map(|state| state.to_vec()).collect(),
+                last: Vec::new(),
+            }
+        }
+    }
+
+    impl RevitProcessSource for FakeProcessSource {
+        fn running_versions(&mut self) -> Result<RunningRevitVersions, String> {
+            if let Some(state) = self.states.pop_front() {
+                self.last = state;
+            }
+            Ok(RunningRevitVersions::from_process_versions(
+                self.last
+                    .iter()
+                    .map(|version| Some(version.to_string()))
+                    .collect(),
+            ))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reports_starts_and_exits() {
+        let source = FakeProcessSource::new(&[&[], &["2025"], &["2025", "2024"], &["2024"], &[]]);
+        let (_watcher, mut changes) = RevitProcessWatcher::spawn(source, Duration::from_millis(5));
+
+        let started = changes.recv().await.unwrap();
+        assert_eq!(started.started, vec!["2025"]);
+        assert!(!started.has_exits());
+
+        let started = changes.recv().await.unwrap();
+        assert_eq!(started.started, vec!["2024"]);
+
+        let exited = changes.recv().await.unwrap();
+        assert_eq!(exited.exited, vec!["2025"]);
+        assert!(exited.has_exits());
+        assert!(exited.running.is_running("2024"));
+
+        let exited = changes.recv().await.unwrap();
+        assert_eq!(exited.exited, vec!["2024"]);
+        assert!(!exited.running.is_any_running());
+    }
+
+    #[test]
+    fn test_no_change_between_equal_states() {
+        let running = RunningRevitVersions::from_process_versions(vec![Some("2025".to_string())]);
+        assert_eq!(RevitProcessChange::between(&running, &running), None);
+
+        let unknown = RunningRevitVersions::from_process_versions(vec![None]);
+        let change =
+            RevitProcessChange::between(&unknown, &RunningRevitVersions::default()).unwrap();
+        assert!(change.has_exits());
+    }
+}
@@ -1,4 +1,8 @@
-use std::{path::Path, sync::Arc};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
 
 use tauri::{AppHandle, Manager};
 
@@ -9,83 +13,246 @@ use crate::services::{
         addin_packages::service::AddinPackagesService,
         addin_permissions::service::AddinPermissionsService, service::AdminService,
     },
+    app_readiness::service::AppReadinessService,
     app_save::service::{AppSavePath, AppSaveService},
+    app_updater::service::AppUpdaterService,
+    config::{app_paths::AppPaths, keys},
     dev_resources::DevResourcesService,
     local_addins::service::LocalAddinsService,
     local_db::service::LocalDbService,
+    logging::service::LoggingService,
+    registry_mirror::service::RegistryMirrorService,
+    revit_installations::service::RevitInstallationsService,
+    support_bundle::service::SupportBundleService,
     user_stats::LocalUserStatsService,
 };
 
+/// How long checking whether a network share can be reached may take before it counts as unreachable
+const SHARE_CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
 pub fn initialize_app(handle: &AppHandle) {
+    // Managed right away, so that the frontend can always ask whether the other services are ready
+    let app_readiness_service = Arc::new(AppReadinessService::new(handle.clone()));
+    handle.manage(Arc::clone(&app_readiness_service));
+
     let handle = handle.clone();
     tauri::async_runtime::spawn(async move {
-        let app_save_service = initialize_app_save_service(AppSavePath::AppData);
-        let local_db_service = initialize_local_db_service(&app_save_service, handle.clone()).await;
-
-        let stats_db_dir = Path::new("S:\\BasesRevitAddinsRegistry");
-
-        let local_addins_service = initialize_local_addins_service(handle.clone());
-
-        let addins_registry_service = initialize_addins_registry_service_local(
-            Arc::clone(&local_db_service),
-            Arc::clone(&local_addins_service),
-        );
-        let user_stats_service = initialize_user_stats_service_local(
-            Arc::clone(&local_db_service),
-            Arc::clone(&addins_registry_service),
-            stats_db_dir,
-        )
-        .await;
-        let addin_permissions_service =
-            initialize_addins_permissions_service(Arc::clone(&user_stats_service)).await;
-        let admin_service = initialize_admin_service(Arc::clone(&local_db_service)).await;
-        let packages_service = initialize_addin_packages_service(
-            Arc::clone(&local_db_service),
-            Arc::clone(&app_save_service),
-        );
-        let dev_resources_service = initialize_dev_resources_service(Arc::clone(&local_db_service));
-
-        let addin_updater_service = initialize_addin_updater_service(
-            Arc::clone(&addins_registry_service),
-            handle.clone(),
-            Arc::clone(&user_stats_service),
-            Arc::clone(&local_db_service),
-            Arc::clone(&admin_service),
-        );
-
-        handle.manage(Arc::clone(&local_db_service));
-        handle.manage(Arc::clone(&app_save_service));
-        handle.manage(Arc::clone(&addins_registry_service));
-        handle.manage(Arc::clone(&user_stats_service));
-        handle.manage(Arc::clone(&addin_updater_service));
-        handle.manage(Arc::clone(&addin_permissions_service));
-        handle.manage(Arc::clone(&admin_service));
-        handle.manage(Arc::clone(&packages_service));
-        handle.manage(Arc::clone(&dev_resources_service));
+        // The services are built in their own task, so that a panic is reported instead of lost
+        let initialization = tauri::async_runtime::spawn(initialize_services(handle)).await;
+        let shares = match initialization {
+            Ok(Ok(shares)) => shares,
+            Ok(Err(e)) => return app_readiness_service.mark_failed(e),
+            Err(e) => {
+                return app_readiness_service
+                    .mark_failed(format!("A service panicked while initializing: {}", e))
+            }
+        };
+        // Commands can run as soon as the services are managed. Checking the shares can take long while offline,
+        // so the app only turns degraded afterwards
+        app_readiness_service.mark_ready(Vec::new());
+        let degraded_reasons = shares.find_degraded_reasons().await;
+        if !degraded_reasons.is_empty() {
+            app_readiness_service.mark_ready(degraded_reasons);
+        }
     });
 }
 
+/// The optional dependencies that live on network shares, checked once the services are ready
+struct ShareDependencies {
+    app_paths: Arc<AppPaths>,
+    local_db: Arc<LocalDbService>,
+    user_stats: Arc<LocalUserStatsService>,
+}
+
+/// Builds and manages every service
+///
+/// Returns what is needed to check the network shares, or an error if a service that everything depends on failed
+async fn initialize_services(handle: AppHandle) -> Result<ShareDependencies, String> {
+    let app_paths = initialize_app_paths()?;
+    let app_save_service =
+        initialize_app_save_service(AppSavePath::Other(app_paths.app_save_root.clone()));
+    let local_db_service = initialize_local_db_service(&app_save_service, handle.clone()).await?;
+    let logging_service =
+        initialize_logging_service(&app_save_service, Arc::clone(&local_db_service)).await;
+
+    let local_addins_service =
+        initialize_local_addins_service(handle.clone(), Arc::clone(&app_paths));
+    let revit_installations_service =
+        initialize_revit_installations_service(Arc::clone(&app_paths));
+    let registry_mirror_service =
+        initialize_registry_mirror_service(handle.clone(), &app_save_service);
+
+    let addins_registry_service = initialize_addins_registry_service_local(
+        Arc::clone(&local_db_service),
+        Arc::clone(&local_addins_service),
+        Arc::clone(&app_paths),
+        Arc::clone(&registry_mirror_service),
+    );
+    let user_stats_service = initialize_user_stats_service_local(
+        Arc::clone(&local_db_service),
+        Arc::clone(&addins_registry_service),
+        Arc::clone(&local_addins_service),
+        app_paths.stats_db_dir.clone(),
+    );
+    let addin_permissions_service =
+        initialize_addins_permissions_service(Arc::clone(&user_stats_service)).await;
+    let admin_service = initialize_admin_service(Arc::clone(&local_db_service)).await;
+    let packages_service = initialize_addin_packages_service(
+        Arc::clone(&local_db_service),
+        Arc::clone(&app_save_service),
+        Arc::clone(&app_paths),
+    );
+    let dev_resources_service =
+        initialize_dev_resources_service(Arc::clone(&local_db_service), Arc::clone(&app_paths));
+
+    let addin_updater_service = initialize_addin_updater_service(
+        Arc::clone(&addins_registry_service),
+        handle.clone(),
+        Arc::clone(&user_stats_service),
+        Arc::clone(&local_db_service),
+        Arc::clone(&admin_service),
+        Arc::clone(&packages_service),
+        Arc::clone(&revit_installations_service),
+        Arc::clone(&local_addins_service),
+        Arc::clone(&registry_mirror_service),
+        Arc::clone(&app_paths),
+    );
+
+    let support_bundle_service = initialize_support_bundle_service(
+        Arc::clone(&local_db_service),
+        Arc::clone(&local_addins_service),
+        Arc::clone(&registry_mirror_service),
+        Arc::clone(&logging_service),
+        Arc::clone(&app_paths),
+    );
+
+    let app_updater_service =
+        initialize_app_updater_service(handle.clone(), Arc::clone(&local_db_service));
+
+    handle.manage(Arc::clone(&local_db_service));
+    handle.manage(Arc::clone(&app_save_service));
+    handle.manage(Arc::clone(&addins_registry_service));
+    handle.manage(Arc::clone(&user_stats_service));
+    handle.manage(Arc::clone(&addin_updater_service));
+    handle.manage(Arc::clone(&addin_permissions_service));
+    handle.manage(Arc::clone(&admin_service));
+    handle.manage(Arc::clone(&packages_service));
+    handle.manage(Arc::clone(&dev_resources_service));
+    handle.manage(Arc::clone(&revit_installations_service));
+    handle.manage(Arc::clone(&local_addins_service));
+    handle.manage(Arc::clone(&registry_mirror_service));
+    handle.manage(Arc::clone(&logging_service));
+    handle.manage(Arc::clone(&support_bundle_service));
+    handle.manage(Arc::clone(&app_updater_service));
+
+    app_updater_service.check_in_background();
+
+    Ok(ShareDependencies {
+        app_paths,
+        local_db: local_db_service,
+        user_stats: user_stats_service,
+    })
+}
+
+impl ShareDependencies {
+    /// The reasons the app is degraded. The registry and the stats database are checked at the same time
+    async fn find_degraded_reasons(&self) -> Vec<String> {
+        let (registry_reason, stats_db_reason) =
+            tokio::join!(self.registry_reason(), self.stats_db_reason());
+        registry_reason.into_iter().chain(stats_db_reason).collect()
+    }
+
+    async fn registry_reason(&self) -> Option<String> {
+        let registry_path =
+            match keys::get_addins_registry_path(&self.app_paths, self.local_db.clone()).await {
+                Ok(registry_path) if registry_path.is_empty() => {
+                    return Some("No addin registry is configured".to_string());
+                }
+                Ok(registry_path) => registry_path,
+                Err(e) => return Some(format!("Could not read the addin registry path: {}", e)),
+            };
+        // A share that can't be reached can block the stat for long, so it runs off the async runtime
+        let is_dir = {
+            let registry_path = registry_path.clone();
+            tokio::task::spawn_blocking(move || Path::new(&registry_path).is_dir())
+        };
+        match tokio::time::timeout(SHARE_CHECK_TIMEOUT, is_dir).await {
+            Ok(Ok(true)) => None,
+            _ => Some(format!(
+                "The addin registry {} can't be reached",
+                registry_path
+            )),
+        }
+    }
+
+    async fn stats_db_reason(&self) -> Option<String> {
+        let stats_db_status = self.user_stats.get_stats_db_status().await;
+        (!stats_db_status.is_online).then(|| {
+            format!(
+                "The stats database {} can't be reached: {}",
+                stats_db_status.path,
+                stats_db_status.last_error.unwrap_or_default()
+            )
+        })
+    }
+}
+
+fn initialize_app_paths() -> Result<Arc<AppPaths>, String> {
+    let app_paths =
+        AppPaths::system().map_err(|e| format!("Could not resolve the app folders: {}", e))?;
+    Ok(Arc::new(app_paths))
+}
+
 fn initialize_app_save_service(save_dir: AppSavePath) -> Arc<AppSaveService> {
     Arc::new(AppSaveService::new(save_dir))
 }
 
-fn initialize_local_addins_service(app_handle: AppHandle) -> Arc<LocalAddinsService> {
-    Arc::new(LocalAddinsService::new(app_handle))
+fn initialize_local_addins_service(
+    app_handle: AppHandle,
+    app_paths: Arc<AppPaths>,
+) -> Arc<LocalAddinsService> {
+    Arc::new(LocalAddinsService::new(app_handle, app_paths))
+}
+
+fn initialize_revit_installations_service(
+    app_paths: Arc<AppPaths>,
+) -> Arc<RevitInstallationsService> {
+    Arc::new(RevitInstallationsService::new(app_paths))
+}
+
+fn initialize_registry_mirror_service(
+    app_handle: AppHandle,
+    app_save_service: &AppSaveService,
+) -> Arc<RegistryMirrorService> {
+    Arc::new(RegistryMirrorService::new(app_handle, app_save_service))
 }
 
 fn initialize_addins_registry_service_local(
     db: Arc<LocalDbService>,
     local_addins_serice: Arc<LocalAddinsService>,
+    app_paths: Arc<AppPaths>,
+    registry_mirror: Arc<RegistryMirrorService>,
 ) -> Arc<LocalAddinsRegistryService> {
-    Arc::new(LocalAddinsRegistryService::new(db, local_addins_serice))
+    Arc::new(LocalAddinsRegistryService::new(
+        db,
+        local_addins_serice,
+        app_paths,
+        registry_mirror,
+    ))
 }
 
+#[allow(clippy::too_many_arguments)]
 fn initialize_addin_updater_service(
     addins_registry: Arc<LocalAddinsRegistryService>,
     app_handle: AppHandle,
     user_stats: Arc<LocalUserStatsService>,
     db: Arc<LocalDbService>,
     admin_service: Arc<AdminService>,
+    packages_service: Arc<AddinPackagesService>,
+    revit_installations: Arc<RevitInstallationsService>,
+    local_addins: Arc<LocalAddinsService>,
+    registry_mirror: Arc<RegistryMirrorService>,
+    paths: Arc<AppPaths>,
 ) -> Arc<AddinUpdaterService> {
     Arc::new(AddinUpdaterService::new(
         addins_registry,
@@ -93,22 +260,45 @@ fn initialize_addin_updater_service(
         user_stats,
         db,
         admin_service,
+        packages_service,
+        revit_installations,
+        local_addins,
+        registry_mirror,
+        paths,
     ))
 }
 
 async fn initialize_local_db_service(
     app_save_service: &Arc<AppSaveService>,
     handle: AppHandle,
-) -> Arc<LocalDbService> {
-    Arc::new(LocalDbService::new_async(app_save_service, handle).await)
+) -> Result<Arc<LocalDbService>, String> {
+    let local_db = LocalDbService::new_async(app_save_service, handle).await?;
+    Ok(Arc::new(local_db))
+}
+
+async fn initialize_logging_service(
+    app_save_service: &AppSaveService,
+    local_db: Arc<LocalDbService>,
+) -> Arc<LoggingService> {
+    let logging_service = LoggingService::new(app_save_service, local_db);
+    if let Err(e) = logging_service.apply_stored_level().await {
+        log::warn!("Could not apply the stored log level: {}", e);
+    }
+    Arc::new(logging_service)
 }
 
-async fn initialize_user_stats_service_local(
+fn initialize_user_stats_service_local(
     db: Arc<LocalDbService>,
     addins_registry: Arc<LocalAddinsRegistryService>,
-    path_to_stats_db: &Path,
+    local_addins: Arc<LocalAddinsService>,
+    default_stats_db_dir: PathBuf,
 ) -> Arc<LocalUserStatsService> {
-    Arc::new(LocalUserStatsService::new_async(db, addins_registry, path_to_stats_db).await)
+    Arc::new(LocalUserStatsService::new(
+        db,
+        addins_registry,
+        local_addins,
+        default_stats_db_dir,
+    ))
 }
 
 async fn initialize_addins_permissions_service(
@@ -124,10 +314,41 @@ async fn initialize_admin_service(local_db: Arc<LocalDbService>) -> Arc<AdminSer
 fn initialize_addin_packages_service(
     local_db: Arc<LocalDbService>,
     app_save_service: Arc<AppSaveService>,
+    app_paths: Arc<AppPaths>,
 ) -> Arc<AddinPackagesService> {
-    Arc::new(AddinPackagesService::new(local_db, app_save_service))
+    Arc::new(AddinPackagesService::new(
+        local_db,
+        app_save_service,
+        app_paths,
+    ))
+}
+
+fn initialize_dev_resources_service(
+    local_db: Arc<LocalDbService>,
+    app_paths: Arc<AppPaths>,
+) -> Arc<DevResourcesService> {
+    Arc::new(DevResourcesService::new(local_db, app_paths))
 }
 
-fn initialize_dev_resources_service(local_db: Arc<LocalDbService>) -> Arc<DevResourcesService> {
-    Arc::new(DevResourcesService::new(local_db))
+fn initialize_support_bundle_service(
+    local_db: Arc<LocalDbService>,
+    local_addins: Arc<LocalAddinsService>,
+    registry_mirror: Arc<RegistryMirrorService>,
+    logging: Arc<LoggingService>,
+    app_paths: Arc<AppPaths>,
+) -> Arc<SupportBundleService> {
+    Arc::new(SupportBundleService::new(
+        local_db,
+        local_addins,
+        registry_mirror,
+        logging,
+        app_paths,
+    ))
+}
+
+fn initialize_app_updater_service(
+    app_handle: AppHandle,
+    local_db: Arc<LocalDbService>,
+) -> Arc<AppUpdaterService> {
+    Arc::new(AppUpdaterService::new(app_handle, local_db))
 }
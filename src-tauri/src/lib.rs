@@ -4,16 +4,23 @@ use services::admin::addin_exporter::tauri_exports::*;
 use services::admin::addin_packages::tauri_exports::*;
 use services::admin::addin_permissions::tauri_exports::*;
 use services::admin::tauri_exports::*;
+use services::app_readiness::tauri_exports::*;
+use services::app_updater::tauri_exports::*;
 use services::dev_resources::tauri_exports::*;
 use services::local_addins::tauri_exports::*;
 use services::local_db::tables::app_kv_store::tauri_exports::*;
+use services::logging::tauri_exports::*;
+use services::registry_mirror::tauri_exports::*;
+use services::revit_installations::tauri_exports::*;
+use services::support_bundle::tauri_exports::*;
 use services::user_stats::tauri_exports::*;
+#[cfg(any(target_os = "macos", target_os = "windows"))]
 use tauri::Manager;
 
 mod app_service_container;
-mod app_updater;
 mod constants;
 mod models;
+mod platform;
 mod services;
 mod utils;
 
@@ -24,6 +31,9 @@ pub fn run() {
         .plugin(tauri_plugin_updater::Builder::new().build())
         .plugin(tauri_plugin_dialog::init())
         .invoke_handler(tauri::generate_handler![
+            // App readiness
+            get_app_readiness,
+            wait_for_app_ready,
             // Local DB
             kv_store_set,
             kv_store_get,
@@ -34,10 +44,27 @@ pub fn run() {
             get_categories,
             delist_addin,
             add_category_to_registry,
+            // Registry Mirror
+            get_registry_mirror_status,
             // Local Addins
             get_local_addins,
             get_revit_versions,
             uninstall_addins,
+            get_install_receipts,
+            get_rollback_receipts,
+            roll_back_addin,
+            adopt_local_addins,
+            disable_local_addins,
+            enable_local_addins,
+            get_bisect_sessions,
+            start_bisect_session,
+            answer_bisect_step,
+            end_bisect_session,
+            verify_local_addins,
+            repair_local_addins,
+            // Revit Installations
+            get_revit_installations,
+            get_addin_dll_conflicts,
             // Addin Exporter
             export_addin,
             get_addin_file_info,
@@ -50,10 +77,28 @@ pub fn run() {
             change_user_stats_name,
             update_user_stats,
             get_all_user_stats,
+            get_stats_db_status,
+            reconnect_stats_db,
             // Addin Updater
             check_for_updates_manual,
             is_revit_running,
             get_pending_updates_info,
+            get_pending_operations,
+            close_revit_and_update,
+            get_last_update_cycle_report,
+            get_update_history,
+            export_update_history,
+            // Logging
+            get_log_level,
+            set_log_level,
+            export_recent_logs,
+            // Support
+            create_support_bundle,
+            // App updates
+            get_app_update_state,
+            check_for_app_update,
+            download_app_update,
+            apply_app_update,
             // Addin Permissions
             register_user,
             get_user,
@@ -76,24 +121,23 @@ pub fn run() {
             install_dev_visual_studio_templates
         ])
         .setup(|app| {
-            if cfg!(debug_assertions) {
-                app.handle().plugin(
-                    tauri_plugin_log::Builder::default()
-                        .level(log::LevelFilter::Info)
-                        .build(),
-                )?;
-            }
-            // Prefer checking for updates on startup in the frontend
-            // let handle = app.handle().clone();
-            // tauri::async_runtime::spawn(async move {
-            //     app_updater::update(handle).await.unwrap();
-            // });
+            // Logged in every build, so that users can send the log files when they report a problem
+            app.handle()
+                .plugin(services::logging::plugin(services::logging::system_log_dir()))?;
+            // Until the level stored in the config is applied
+            log::set_max_level(log::LevelFilter::Info);
             // ! Initialize the app service container regardless of if debug mode:
             app_service_container::initialize_app(app.handle());
 
+            #[cfg(any(target_os = "macos", target_os = "windows"))]
             let window = app.get_webview_window("main").unwrap();
             #[cfg(target_os = "macos")]
-            window_vibrancy::apply_vibrancy(&window, NSVisualEffectMaterial::HudWindow, None, None)
+            window_vibrancy::apply_vibrancy(
+                &window,
+                window_vibrancy::NSVisualEffectMaterial::HudWindow,
+                None,
+                None,
+            )
                 .expect("Unsupported platform! 'apply_vibrancy' is only supported on macOS");
 
             #[cfg(target_os = "windows")]
@@ -0,0 +1,18 @@
+//! Everything that only works one way on Windows lives here, so that the services can be compiled and tested
+//! on any platform. Revit process detection lives in the `revit_check` crate, which follows the same layout.
+
+#[cfg(windows)]
+mod windows;
+#[cfg(windows)]
+pub use windows::*;
+
+#[cfg(not(windows))]
+mod unsupported;
+#[cfg(not(windows))]
+pub use unsupported::*;
+
+/// `revitcli` shells out to Windows-only tooling and doesn't compile anywhere else
+#[cfg(windows)]
+pub use ::revitcli;
+#[cfg(not(windows))]
+pub mod revitcli;
@@ -0,0 +1,66 @@
+//! Mirrors the parts of the `revitcli` API that the app uses. Every operation fails, since building and
+//! exporting addins requires the Windows .NET tooling
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+const UNSUPPORTED: &str = "Building and exporting addins is only supported on Windows";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorList {
+    errors: Vec<String>,
+    warnings: Vec<String>,
+}
+
+impl ErrorList {
+    pub fn new_with_error(error: &str) -> Self {
+        Self {
+            errors: vec![error.to_string()],
+            warnings: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddinFileInfo {
+    pub name: String,
+    pub assembly: String,
+    pub addin_id: String,
+    pub full_class_name: String,
+    pub vendor_id: String,
+    pub vendor_description: String,
+    pub vendor_email: String,
+}
+
+pub async fn build_project(_starting_dir: &str) -> Result<String, String> {
+    Err(UNSUPPORTED.to_string())
+}
+
+pub async fn export_addin(
+    _starting_dir: &str,
+    _extra_dlls: &[&str],
+    _destination_dir: &Path,
+) -> ErrorList {
+    ErrorList::new_with_error(UNSUPPORTED)
+}
+
+pub fn get_addin_file_info(_starting_dir: &str) -> Result<AddinFileInfo, String> {
+    Err(UNSUPPORTED.to_string())
+}
+
+pub fn create_addin_file_for_project(
+    _starting_dir: &str,
+    _addin_info: AddinFileInfo,
+) -> Result<String, String> {
+    Err(UNSUPPORTED.to_string())
+}
+
+pub fn get_project_dlls(_starting_dir: &str) -> Result<Vec<String>, String> {
+    Err(UNSUPPORTED.to_string())
+}
+
+pub fn get_project_name(_starting_dir: &str) -> Result<String, String> {
+    Err(UNSUPPORTED.to_string())
+}
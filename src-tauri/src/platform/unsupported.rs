@@ -0,0 +1,31 @@
+//! Stand-ins for platforms that Revit doesn't run on
+
+use std::path::{Path, PathBuf};
+
+/// Opens the file with its default application
+pub fn open_path(path: &Path) -> Result<(), String> {
+    let opener = if cfg!(target_os = "macos") {
+        "open"
+    } else {
+        "xdg-open"
+    };
+    let status = std::process::Command::new(opener)
+        .arg(path)
+        .status()
+        .map_err(|e| format!("Failed to open file: {}", e))?;
+
+    if !status.success() {
+        return Err(format!("Failed to open file: {}", path.display()));
+    }
+    Ok(())
+}
+
+/// Revit can't be installed here
+pub fn program_files_dirs() -> Vec<PathBuf> {
+    Vec::new()
+}
+
+/// There is no machine-wide Revit Addins folder here
+pub fn program_data_dir() -> Option<PathBuf> {
+    None
+}
@@ -0,0 +1,40 @@
+use std::{os::windows::process::CommandExt, path::Path, path::PathBuf};
+
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+/// Opens the file with its default application
+pub fn open_path(path: &Path) -> Result<(), String> {
+    // Use 'cmd /c start' to leverage Windows file associations
+    let status = std::process::Command::new("cmd")
+        .creation_flags(CREATE_NO_WINDOW)
+        .args(["/c", "start", "", &path.to_string_lossy()])
+        .status()
+        .map_err(|e| format!("Failed to open file: {}", e))?;
+
+    if !status.success() {
+        return Err(format!("Failed to open file: {}", path.display()));
+    }
+    Ok(())
+}
+
+/// The Program Files folders. Typically C:\Program Files
+pub fn program_files_dirs() -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = ["ProgramW6432", "ProgramFiles"]
+        .iter()
+        .filter_map(std::env::var_os)
+        .map(PathBuf::from)
+        .collect();
+    if dirs.is_empty() {
+        dirs.push(PathBuf::from(r"C:\Program Files"));
+    }
+    dirs.dedup();
+    dirs
+}
+
+/// The folder for data shared by every user of the machine. Typically C:\ProgramData
+pub fn program_data_dir() -> Option<PathBuf> {
+    let program_data = std::env::var_os("ProgramData")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(r"C:\ProgramData"));
+    Some(program_data)
+}
@@ -1,2 +1,8 @@
+mod pending_operation_model;
+mod revit_restart_model;
+mod update_cycle_report_model;
 mod update_notification_model;
+pub use pending_operation_model::*;
+pub use revit_restart_model::*;
+pub use update_cycle_report_model::*;
 pub use update_notification_model::*;
@@ -0,0 +1,153 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::services::addin_updater::models::UpdateOperationType;
+use crate::utils;
+
+use crate::services::local_addins::models::{
+    install_scope_model::InstallScope, uninstall_result_model::UninstallTargetModel,
+};
+
+/// The first retry waits this long, each one after it twice as long as the one before
+const FIRST_RETRY_DELAY: Duration = Duration::from_secs(60);
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// What to do once Revit is closed, or what to retry after it failed. Addins are referred to by their paths, so that an operation still makes sense
+/// after a restart
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum PendingOperationKind {
+    /// Copy the newer registry addin over the local one
+    #[serde(rename_all = "camelCase")]
+    Update {
+        registry_dll_folder: String,
+        local_dll_folder: String,
+    },
+    /// Install a registry addin the user is allowed to have
+    #[serde(rename_all = "camelCase")]
+    Install {
+        registry_dll_folder: String,
+        for_revit_versions: Vec<String>,
+        install_scope: InstallScope,
+    },
+    /// Finish an uninstall that failed because Revit held the files
+    #[serde(rename_all = "camelCase")]
+    Uninstall { target: UninstallTargetModel },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingOperationModel {
+    /// Storing an operation with the same key replaces the previous one
+    pub key: String,
+    pub addin_name: String,
+    pub operation: PendingOperationKind,
+    pub created_at: String,
+    /// How many times applying it failed
+    pub attempts: u32,
+    pub last_error: Option<String>,
+    /// When a failed operation may be tried again. Unset if it never failed
+    #[serde(default)]
+    pub next_attempt_at: Option<String>,
+}
+
+impl PendingOperationModel {
+    pub fn new(addin_name: &str, operation: PendingOperationKind) -> Self {
+        let key = match &operation {
+            PendingOperationKind::Update {
+                local_dll_folder, ..
+            } => format!("update:{}", local_dll_folder),
+            PendingOperationKind::Install {
+                registry_dll_folder,
+                install_scope,
+                ..
+            } => format!("install:{}:{}", registry_dll_folder, install_scope.as_str()),
+            PendingOperationKind::Uninstall { target } => format!("uninstall:{}", target.xml_file),
+        };
+        Self {
+            key,
+            addin_name: addin_name.to_string(),
+            operation,
+            created_at: chrono::Utc::now().to_rfc3339(),
+            attempts: 0,
+            last_error: None,
+            next_attempt_at: None,
+        }
+    }
+
+    pub fn operation_type(&self) -> UpdateOperationType {
+        match self.operation {
+            PendingOperationKind::Update { .. } => UpdateOperationType::Update,
+            PendingOperationKind::Install { .. } => UpdateOperationType::Install,
+            PendingOperationKind::Uninstall { .. } => UpdateOperationType::Uninstall,
+        }
+    }
+
+    /// Counts the failure and schedules the next attempt
+    pub fn fail(&mut self, error: String, now: DateTime<Utc>) {
+        self.attempts += 1;
+        self.last_error = Some(error);
+        let delay = chrono::Duration::from_std(retry_delay(self.attempts)).unwrap_or_default();
+        self.next_attempt_at = Some((now + delay).to_rfc3339());
+    }
+
+    /// Whether the backoff after the last failure is over. An unreadable time counts as over
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.next_attempt_at
+            .as_deref()
+            .and_then(|next_attempt_at| DateTime::parse_from_rfc3339(next_attempt_at).ok())
+            .map_or(true, |next_attempt_at| next_attempt_at <= now)
+    }
+}
+
+/// How long to wait before trying again after the given number of failed attempts
+pub fn retry_delay(attempts: u32) -> Duration {
+    utils::backoff_delay(FIRST_RETRY_DELAY, MAX_RETRY_DELAY, attempts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn update_operation() -> PendingOperationModel {
+        PendingOperationModel::new(
+            "MyAddin",
+            PendingOperationKind::Update {
+                registry_dll_folder: "registry/MyAddin".to_string(),
+                local_dll_folder: "local/MyAddin".to_string(),
+            },
+        )
+    }
+
+    #[test]
+    fn test_retry_delay_doubles_up_to_the_maximum() {
+        assert_eq!(retry_delay(1), Duration::from_secs(60));
+        assert_eq!(retry_delay(2), Duration::from_secs(120));
+        assert_eq!(retry_delay(3), Duration::from_secs(240));
+        assert_eq!(retry_delay(20), MAX_RETRY_DELAY);
+        assert_eq!(retry_delay(u32::MAX), MAX_RETRY_DELAY);
+    }
+
+    #[test]
+    fn test_failed_operation_is_due_after_the_backoff() {
+        let now = Utc::now();
+        let mut operation = update_operation();
+        assert!(operation.is_due(now));
+
+        operation.fail("file in use".to_string(), now);
+        assert_eq!(operation.attempts, 1);
+        assert_eq!(operation.last_error.as_deref(), Some("file in use"));
+        assert!(!operation.is_due(now));
+        assert!(operation.is_due(now + chrono::Duration::seconds(60)));
+    }
+
+    #[test]
+    fn test_operations_stored_before_backoff_are_due() {
+        let mut json = serde_json::to_value(update_operation()).unwrap();
+        json.as_object_mut().unwrap().remove("nextAttemptAt");
+        let operation: PendingOperationModel = serde_json::from_value(json).unwrap();
+        assert!(operation.is_due(Utc::now()));
+    }
+}
@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RevitRestartStage {
+    Closing,
+    WaitingForExit,
+    Updating,
+    Reopening,
+    Done,
+}
+
+/// Sent with the "revit_restart_progress" event while Revit is closed for an update
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RevitRestartProgressModel {
+    pub stage: RevitRestartStage,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RevitRestartResultModel {
+    /// Whether Revit closed in time. If not, nothing was updated
+    pub revit_closed: bool,
+    /// The Revit processes that could not be asked to close, e.g. because they have no window.
+    /// They keep running and the operations for their versions keep waiting
+    pub not_closed_count: usize,
+    /// The pending operations that still wait, e.g. because they failed
+    pub remaining_operations: usize,
+    pub reopened_count: usize,
+    /// What went wrong while updating or reopening Revit
+    pub error: Option<String>,
+}
@@ -0,0 +1,168 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum UpdateOperationType {
+    Update,
+    Install,
+    Uninstall,
+}
+
+/// What happened to a single update, install or uninstall
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "camelCase")]
+pub enum OperationOutcome {
+    Succeeded,
+    /// Nothing was done, e.g. the addin was already up to date or a retry isn't due yet
+    #[serde(rename_all = "camelCase")]
+    Skipped {
+        reason: String,
+    },
+    /// Waits for a Revit version to close
+    Deferred,
+    /// Kept as a pending operation and retried at `retry_at`
+    #[serde(rename_all = "camelCase")]
+    Failed {
+        cause: String,
+        attempts: u32,
+        retry_at: Option<String>,
+    },
+}
+
+/// Addins all report version "1.0.0" for now, so the modification time of the DLLs tells versions apart
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddinVersionModel {
+    pub version: String,
+    /// RFC 3339
+    pub modified_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OperationReportModel {
+    pub addin_name: String,
+    pub operation_type: UpdateOperationType,
+    pub outcome: OperationOutcome,
+    /// The installed version before the operation. Unset for installs
+    #[serde(default)]
+    pub version_before: Option<AddinVersionModel>,
+    /// The version the operation installs, whether or not it succeeded. Unset for uninstalls
+    #[serde(default)]
+    pub version_after: Option<AddinVersionModel>,
+}
+
+impl OperationReportModel {
+    pub fn with_versions(
+        &mut self,
+        version_before: Option<AddinVersionModel>,
+        version_after: Option<AddinVersionModel>,
+    ) {
+        self.version_before = version_before;
+        self.version_after = version_after;
+    }
+}
+
+/// Everything an update cycle did. Sent with the "update_cycle_report" event and kept in the update history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateCycleReportModel {
+    pub id: String,
+    pub started_at: String,
+    pub finished_at: Option<String>,
+    /// Unset if the cycle only applied pending operations and didn't scan the registry
+    pub registry_reachable: Option<bool>,
+    pub operations: Vec<OperationReportModel>,
+}
+
+impl UpdateCycleReportModel {
+    pub fn start() -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            started_at: chrono::Utc::now().to_rfc3339(),
+            finished_at: None,
+            registry_reachable: None,
+            operations: Vec::new(),
+        }
+    }
+
+    /// Whether the cycle is worth keeping in the history: it did something, or the registry became reachable or
+    /// unreachable since the previous scan. Otherwise a cycle would be kept every minute while the registry is offline
+    pub fn is_worth_keeping(&self, previous_registry_reachable: Option<bool>) -> bool {
+        !self.operations.is_empty()
+            || (self.registry_reachable.is_some()
+                && self.registry_reachable != previous_registry_reachable)
+    }
+
+    pub fn finish(&mut self) {
+        self.finished_at = Some(chrono::Utc::now().to_rfc3339());
+    }
+
+    pub fn push(
+        &mut self,
+        addin_name: &str,
+        operation_type: UpdateOperationType,
+        outcome: OperationOutcome,
+    ) -> &mut OperationReportModel {
+        self.operations.push(OperationReportModel {
+            addin_name: addin_name.to_string(),
+            operation_type,
+            outcome,
+            version_before: None,
+            version_after: None,
+        });
+        self.operations.last_mut().unwrap()
+    }
+
+    pub fn failures(&self) -> impl Iterator<Item = &OperationReportModel> {
+        self.operations
+            .iter()
+            .filter(|operation| matches!(operation.outcome, OperationOutcome::Failed { .. }))
+    }
+}
+
+/// A page of the update history, newest cycle first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateHistoryPageModel {
+    pub cycles: Vec<UpdateCycleReportModel>,
+    /// How many cycles there are in total
+    pub total: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cycle_is_kept_if_reachability_changed_or_it_did_anything() {
+        let mut report = UpdateCycleReportModel::start();
+        assert!(!report.is_worth_keeping(None));
+        assert!(!report.is_worth_keeping(Some(true)));
+
+        report.registry_reachable = Some(false);
+        assert!(report.is_worth_keeping(None));
+        assert!(report.is_worth_keeping(Some(true)));
+        assert!(!report.is_worth_keeping(Some(false)));
+
+        let mut report = UpdateCycleReportModel::start();
+        report
+            .push(
+                "MyAddin",
+                UpdateOperationType::Update,
+                OperationOutcome::Deferred,
+            )
+            .with_versions(
+                None,
+                Some(AddinVersionModel {
+                    version: "1.0.0".to_string(),
+                    modified_at: "2025-01-01T00:00:00+00:00".to_string(),
+                }),
+            );
+        assert!(report.is_worth_keeping(None));
+        assert_eq!(
+            report.operations[0].version_after.as_ref().unwrap().version,
+            "1.0.0"
+        );
+    }
+}
@@ -5,7 +5,9 @@ use serde::{Deserialize, Serialize};
 pub enum UpdateNotificationType {
     Info,
     Install,
-    Warning
+    Warning,
+    /// An update or install failed and will be retried
+    Error,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -7,8 +7,12 @@ use crate::services::{
         *,
     },
     addins_registry::services::AsyncAddinsRegistryServiceType,
-    admin::service::AdminService,
+    admin::{addin_packages::service::AddinPackagesService, service::AdminService},
+    config::app_paths::AppPaths,
+    local_addins::service::LocalAddinsService,
     local_db::service::LocalDbService,
+    registry_mirror::service::RegistryMirrorService,
+    revit_installations::service::RevitInstallationsService,
     user_stats::LocalUserStatsService,
 };
 
@@ -28,12 +32,18 @@ pub struct AddinUpdaterService {
 }
 
 impl AddinUpdaterService {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         addins_registry: AsyncAddinsRegistryServiceType,
         app_handle: AppHandle,
         user_stats: Arc<LocalUserStatsService>,
         db: Arc<LocalDbService>,
         admin_service: Arc<AdminService>,
+        packages_service: Arc<AddinPackagesService>,
+        revit_installations: Arc<RevitInstallationsService>,
+        local_addins: Arc<LocalAddinsService>,
+        registry_mirror: Arc<RegistryMirrorService>,
+        paths: Arc<AppPaths>,
     ) -> Self {
         let update_checker = update_checker::AddinUpdateChecker::new(
             app_handle,
@@ -41,6 +51,11 @@ impl AddinUpdaterService {
             user_stats,
             db,
             admin_service,
+            packages_service,
+            revit_installations,
+            local_addins,
+            registry_mirror,
+            paths,
         );
         update_checker.spawn_update_checker();
         Self { update_checker }
@@ -48,4 +63,10 @@ impl AddinUpdaterService {
     pub async fn manually_check_for_updates(&self) -> Result<UpdateResult, String> {
         self.update_checker.manual_check_for_updates().await
     }
+    pub async fn close_revit_and_update(&self) -> Result<models::RevitRestartResultModel, String> {
+        self.update_checker.close_revit_and_update().await
+    }
+    pub async fn last_update_cycle_report(&self) -> Option<models::UpdateCycleReportModel> {
+        self.update_checker.last_update_cycle_report().await
+    }
 }
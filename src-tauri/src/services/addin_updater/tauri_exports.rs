@@ -1,9 +1,17 @@
+use log::error;
 use std::sync::Arc;
 use tauri::State;
 
-use crate::services::addin_updater::{
-    service::AddinUpdaterService,
-    update_checker::{PendingUpdatesStateType, UpdateResult},
+use crate::services::{
+    addin_updater::{
+        models::{
+            PendingOperationKind, PendingOperationModel, RevitRestartResultModel,
+            UpdateCycleReportModel, UpdateHistoryPageModel,
+        },
+        service::AddinUpdaterService,
+        update_checker::UpdateResult,
+    },
+    local_db::service::LocalDbService,
 };
 
 /// Manually trigger a check for addin updates
@@ -21,7 +29,7 @@ pub async fn check_for_updates_manual(
             }
         }
         Err(e) => {
-            eprintln!("Manual update check failed: {}", e);
+            error!("Manual update check failed: {}", e);
             Err(e)
         }
     }
@@ -38,16 +46,70 @@ pub async fn is_revit_running() -> Result<bool, String> {
 /// Get information about pending addin updates
 #[tauri::command]
 pub async fn get_pending_updates_info(
-    pending_updates_state: State<'_, PendingUpdatesStateType>,
+    local_db_service: State<'_, Arc<LocalDbService>>,
 ) -> Result<Option<String>, String> {
-    let state = pending_updates_state.lock().await;
-    if let Some(pending_updates) = &state.pending_updates {
-        let update_count = pending_updates.len();
-        return Ok(Some(format!(
-            "{} addin update{} pending",
-            update_count,
-            if update_count == 1 { "" } else { "s" }
-        )));
+    let update_count = local_db_service
+        .pending_operations_table()
+        .get_all()
+        .await?
+        .iter()
+        .filter(|operation| matches!(operation.operation, PendingOperationKind::Update { .. }))
+        .count();
+    if update_count == 0 {
+        return Ok(None);
     }
-    Ok(None)
+    Ok(Some(format!(
+        "{} addin update{} pending",
+        update_count,
+        if update_count == 1 { "" } else { "s" }
+    )))
+}
+
+/// The updates, installs and uninstalls that wait for Revit to close, oldest first
+#[tauri::command]
+pub async fn get_pending_operations(
+    local_db_service: State<'_, Arc<LocalDbService>>,
+) -> Result<Vec<PendingOperationModel>, String> {
+    local_db_service.pending_operations_table().get_all().await
+}
+
+/// Closes Revit, applies the pending operations and reopens Revit with the documents it had open.
+/// Progress is sent with the "revit_restart_progress" event
+#[tauri::command]
+pub async fn close_revit_and_update(
+    addin_updater_service: State<'_, Arc<AddinUpdaterService>>,
+) -> Result<RevitRestartResultModel, String> {
+    addin_updater_service.close_revit_and_update().await
+}
+
+/// What the last update cycle that did anything applied, deferred or failed. Also sent with the "update_cycle_report" event
+#[tauri::command]
+pub async fn get_last_update_cycle_report(
+    addin_updater_service: State<'_, Arc<AddinUpdaterService>>,
+) -> Result<Option<UpdateCycleReportModel>, String> {
+    Ok(addin_updater_service.last_update_cycle_report().await)
+}
+
+/// A page of the update history, newest cycle first
+#[tauri::command]
+pub async fn get_update_history(
+    offset: u64,
+    limit: u64,
+    local_db_service: State<'_, Arc<LocalDbService>>,
+) -> Result<UpdateHistoryPageModel, String> {
+    local_db_service
+        .update_history_table()
+        .get_page(offset, limit)
+        .await
+}
+
+/// Writes the whole update history to a JSON file, to attach to support tickets
+#[tauri::command]
+pub async fn export_update_history(
+    path: String,
+    local_db_service: State<'_, Arc<LocalDbService>>,
+) -> Result<(), String> {
+    let cycles = local_db_service.update_history_table().get_all().await?;
+    let json = serde_json::to_string_pretty(&cycles).map_err(|e| e.to_string())?;
+    std::fs::write(&path, json).map_err(|e| format!("Could not write {}: {}", path, e))
 }
@@ -1,8 +1,15 @@
 use tauri::AppHandle;
 
 use crate::services::{
-    addin_updater::update_checker::{allowed_addins_manager::UpdateCheckError, notifications},
+    addin_updater::{
+        models::{PendingOperationKind, PendingOperationModel},
+        update_checker::{allowed_addins_manager::UpdateCheckError, notifications},
+    },
     addins_registry::{models::addin_model::AddinModel, services::AsyncAddinsRegistryServiceType},
+    local_addins::models::{
+        install_receipt_model::InstallActor, install_scope_model::InstallScope,
+    },
+    logging::span,
 };
 
 pub enum Operation {
@@ -13,6 +20,7 @@ pub struct InstallAddinOperation {
     pub operation: Operation,
     pub addin: AddinModel,
     for_revit_versions: Vec<String>,
+    install_scope: InstallScope,
     app_handle: AppHandle,
     addin_registry: AsyncAddinsRegistryServiceType,
 }
@@ -21,6 +29,7 @@ impl InstallAddinOperation {
     pub fn new_install(
         addin: AddinModel,
         for_revit_versions: Vec<String>,
+        install_scope: InstallScope,
         app_handle: AppHandle,
         addin_registry: AsyncAddinsRegistryServiceType,
     ) -> Self {
@@ -28,21 +37,72 @@ impl InstallAddinOperation {
             operation: Operation::Install,
             addin,
             for_revit_versions,
+            install_scope,
             app_handle,
             addin_registry,
         }
     }
+    pub fn for_revit_versions(&self) -> &[String] {
+        &self.for_revit_versions
+    }
+    /// Splits off the Revit versions that have to wait, returning the install to apply now and the one that waits
+    pub fn split_deferred(
+        self,
+        is_deferred: impl Fn(&str) -> bool,
+    ) -> (Option<Self>, Option<Self>) {
+        let (deferred_versions, versions): (Vec<String>, Vec<String>) = self
+            .for_revit_versions
+            .iter()
+            .cloned()
+            .partition(|version| is_deferred(version));
+        if deferred_versions.is_empty() {
+            return (Some(self), None);
+        }
+        if versions.is_empty() {
+            return (None, Some(self));
+        }
+        let deferred = Self::new_install(
+            self.addin.clone(),
+            deferred_versions,
+            self.install_scope,
+            self.app_handle.clone(),
+            self.addin_registry.clone(),
+        );
+        let install = Self {
+            for_revit_versions: versions,
+            ..self
+        };
+        (Some(install), Some(deferred))
+    }
+    /// The install to apply once Revit is closed
+    pub fn to_pending_operation(&self) -> PendingOperationModel {
+        PendingOperationModel::new(
+            &self.addin.name,
+            PendingOperationKind::Install {
+                registry_dll_folder: self.addin.path_to_addin_dll_folder.clone(),
+                for_revit_versions: self.for_revit_versions.clone(),
+                install_scope: self.install_scope,
+            },
+        )
+    }
     /// Automatically emits a notification to the frontend once it is installed
     pub async fn execute(&self) -> Result<(), UpdateCheckError> {
-        let registry_addin = self.addin.clone();
-        let for_revit_versions = self.for_revit_versions.clone();
-        self.addin_registry
-            .install_addin(registry_addin.clone(), for_revit_versions)
-            .await
-            .map_err(|e| UpdateCheckError::AddinsRegistry(e.to_string()))?;
-        // Emit a notification to the frontend that the addin is now available
-        notifications::with(&self.app_handle).allowed_addin_installed(&registry_addin);
-        Ok(())
+        span::in_span("install", &self.addin.name, async {
+            let registry_addin = self.addin.clone();
+            let for_revit_versions = self.for_revit_versions.clone();
+            self.addin_registry
+                .install_addin(
+                    registry_addin.clone(),
+                    for_revit_versions,
+                    self.install_scope,
+                    InstallActor::AutoUpdater,
+                )
+                .await
+                .map_err(|e| UpdateCheckError::AddinsRegistry(e.to_string()))?;
+            // Emit a notification to the frontend that the addin is now available
+            notifications::with(&self.app_handle).allowed_addin_installed(&registry_addin);
+            Ok(())
+        })
+        .await
     }
-
 }
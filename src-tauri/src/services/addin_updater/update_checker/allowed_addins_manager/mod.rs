@@ -1,3 +1,4 @@
+use log::warn;
 use std::{fmt::Display, sync::Arc};
 
 use tauri::AppHandle;
@@ -7,9 +8,11 @@ use crate::{
         addins_registry::{
             models::addin_model::AddinModel, services::AsyncAddinsRegistryServiceType,
         },
+        admin::addin_packages::service::AddinPackagesService,
         config::keys,
-        local_addins::service::LocalAddinsService,
+        local_addins::{revit_versions::RevitVersionRange, service::LocalAddinsService},
         local_db::service::LocalDbService,
+        revit_installations::service::RevitInstallationsService,
         user_stats::{db::user_addins::UserAddinsError, LocalUserStatsService},
     },
     utils,
@@ -17,8 +20,6 @@ use crate::{
 mod install_addin_operation;
 pub use install_addin_operation::*;
 
-pub const ALL_REVIT_VERSIONS: [&str; 7] = ["2019", "2020", "2021", "2022", "2023", "2024", "2025"];
-
 #[derive(Debug)]
 pub enum UpdateCheckError {
     GetUserEmail(String),
@@ -27,6 +28,8 @@ pub enum UpdateCheckError {
     Deserialization(serde_json::Error),
     AddinsRegistry(String),
     LocalAddins(String),
+    /// The stats database, which stores the allowed addins, can't be reached
+    StatsDbOffline(String),
 }
 impl Display for UpdateCheckError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -41,6 +44,9 @@ pub struct AllowedAddinsManager {
     user_stats: Arc<LocalUserStatsService>,
     db: Arc<LocalDbService>,
     addin_registry: AsyncAddinsRegistryServiceType,
+    packages_service: Arc<AddinPackagesService>,
+    revit_installations: Arc<RevitInstallationsService>,
+    local_addins: Arc<LocalAddinsService>,
 }
 
 impl AllowedAddinsManager {
@@ -49,12 +55,18 @@ impl AllowedAddinsManager {
         user_stats: Arc<LocalUserStatsService>,
         db: Arc<LocalDbService>,
         addin_registry: AsyncAddinsRegistryServiceType,
+        packages_service: Arc<AddinPackagesService>,
+        revit_installations: Arc<RevitInstallationsService>,
+        local_addins: Arc<LocalAddinsService>,
     ) -> Self {
         Self {
             app_handle,
             user_stats,
             db,
             addin_registry,
+            packages_service,
+            revit_installations,
+            local_addins,
         }
     }
     /// Runs the check and returns a vector of operations to be executed ideally when Revit is closed
@@ -64,7 +76,12 @@ impl AllowedAddinsManager {
             .await
             .map_err(UpdateCheckError::GetUserEmail)?;
 
-        let user_addins_table = self.user_stats.stats_db.user_addins_table();
+        let user_addins_table = self
+            .user_stats
+            .stats_db
+            .user_addins_table()
+            .await
+            .map_err(UpdateCheckError::StatsDbOffline)?;
 
         let user = user_addins_table
             .get_user(user_email)
@@ -75,29 +92,61 @@ impl AllowedAddinsManager {
                 let allowed_addin_paths: Vec<String> =
                     serde_json::from_value(user.allowed_addin_paths)
                         .map_err(UpdateCheckError::Deserialization)?;
+                let installed_revit_versions = self.revit_installations.installed_versions();
+                let install_scope = keys::get_default_install_scope(self.db.clone())
+                    .await
+                    .unwrap_or_default();
+                // Packages are optional, so an addin without one just doesn't restrict its versions
+                let packages = self
+                    .packages_service
+                    .get_all_addin_packages()
+                    .await
+                    .unwrap_or_else(|e| {
+                        warn!("Could not read addin packages: {}", e);
+                        Vec::new()
+                    });
                 for allowed_addin in allowed_addin_paths.iter() {
                     let registry_addin = self
                         .fetch_corresponding_registry_addin(allowed_addin)
                         .await?;
                     if let Some(registry_addin) = registry_addin {
-                        let is_already_installed =
-                            LocalAddinsService::is_addin_installed_locally_model(&registry_addin)
-                                .map_err(|e| UpdateCheckError::LocalAddins(e.to_string()))?;
+                        let is_already_installed = self
+                            .local_addins
+                            .is_addin_installed_locally_model(&registry_addin)
+                            .map_err(|e| UpdateCheckError::LocalAddins(e.to_string()))?;
 
                         // Only try to install the addin if it is not already installed
                         if !is_already_installed {
-                            let for_revit_versions =
-                                Self::determine_revit_versions_for_addin(allowed_addin);
+                            let supported_versions =
+                                AddinPackagesService::find_package_for_registry_addin(
+                                    &packages,
+                                    &registry_addin,
+                                )
+                                .map(|package| package.supported_revit_versions())
+                                .unwrap_or_default();
+                            let for_revit_versions = Self::determine_revit_versions_for_addin(
+                                allowed_addin,
+                                &installed_revit_versions,
+                                &supported_versions,
+                            );
+                            if for_revit_versions.is_empty() {
+                                warn!(
+                                    "None of the installed Revit versions {:?} are supported by {:?}",
+                                    installed_revit_versions, allowed_addin
+                                );
+                                continue;
+                            }
                             operations.push(InstallAddinOperation::new_install(
                                 registry_addin,
                                 for_revit_versions,
+                                install_scope,
                                 self.app_handle.clone(),
                                 self.addin_registry.clone(),
                             ));
                         }
                     } else {
-                        println!(
-                            "WARNING: could not find corresponding registry addin for {:?}",
+                        warn!(
+                            "Could not find corresponding registry addin for {:?}",
                             allowed_addin
                         )
                     }
@@ -122,28 +171,107 @@ impl AllowedAddinsManager {
     ) -> Result<Option<AddinModel>, UpdateCheckError> {
         let registry_addins = self.fetch_registry_addins().await?;
 
-        Ok(registry_addins
+        Ok(Self::find_registry_addin(&registry_addins, addin_path).cloned())
+    }
+
+    fn find_registry_addin<'a>(
+        registry_addins: &'a [AddinModel],
+        addin_path: &str,
+    ) -> Option<&'a AddinModel> {
+        registry_addins.iter().find(|addin| {
+            let converted_dll_folder =
+                utils::double_backslash_to_single_forward_slash(&addin.path_to_addin_dll_folder);
+            converted_dll_folder.contains(addin_path)
+        })
+    }
+
+    /// The registry addins that the user is allowed to have, whether or not they are installed
+    pub async fn allowed_registry_addins(
+        &self,
+        registry_addins: &[AddinModel],
+    ) -> Result<Vec<AddinModel>, UpdateCheckError> {
+        let user_email = keys::get_user_email(self.db.clone())
+            .await
+            .map_err(UpdateCheckError::GetUserEmail)?;
+        let user_addins_table = self
+            .user_stats
+            .stats_db
+            .user_addins_table()
+            .await
+            .map_err(UpdateCheckError::StatsDbOffline)?;
+        let Some(user) = user_addins_table
+            .get_user(user_email)
+            .await
+            .map_err(UpdateCheckError::UserAddinsTable)?
+        else {
+            return Ok(Vec::new());
+        };
+        let allowed_addin_paths: Vec<String> = serde_json::from_value(user.allowed_addin_paths)
+            .map_err(UpdateCheckError::Deserialization)?;
+        Ok(allowed_addin_paths
             .iter()
-            .find(|addin| {
-                let converted_dll_folder = utils::double_backslash_to_single_forward_slash(
-                    &addin.path_to_addin_dll_folder,
-                );
-                converted_dll_folder.contains(addin_path)
-            })
-            .cloned())
+            .filter_map(|addin_path| Self::find_registry_addin(registry_addins, addin_path))
+            .cloned()
+            .collect())
+    }
+
+    /// Targets the Revit version encoded in the addin's path if there is one, otherwise every installed version.
+    ///
+    /// Either way, only versions that are installed and supported by the addin are returned
+    fn determine_revit_versions_for_addin(
+        addin_path: &str,
+        installed_revit_versions: &[String],
+        supported_versions: &RevitVersionRange,
+    ) -> Vec<String> {
+        let candidates = match revit_check::revit_version_from_path(addin_path) {
+            Some(version) if installed_revit_versions.contains(&version) => vec![version],
+            Some(_) => Vec::new(),
+            None => installed_revit_versions.to_vec(),
+        };
+        supported_versions.intersect(&candidates)
     }
+}
 
-    fn determine_revit_versions_for_addin(addin_path: &str) -> Vec<String> {
-        let path_str = addin_path;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::revit_installations::providers::FakeRevitInstallationsProvider;
+    use std::fs;
 
-        // Look for first year in path that matches a Revit version
-        for year in ALL_REVIT_VERSIONS.iter() {
-            if path_str.contains(year) {
-                return vec![year.to_string()];
-            }
+    #[test]
+    fn test_targets_installed_and_supported_versions() {
+        let root = std::env::temp_dir().join(format!("allowed_addins_{}", uuid::Uuid::new_v4()));
+        let provider = Arc::new(FakeRevitInstallationsProvider::new(root.clone()));
+        for version in ["2024", "2025", "2026"] {
+            provider.add_installation(version);
         }
+        let installed = RevitInstallationsService::new(provider).installed_versions();
+        let supported = RevitVersionRange::new(Some("2025".to_string()), None);
+
+        assert_eq!(
+            AllowedAddinsManager::determine_revit_versions_for_addin(
+                "Tools/MyAddin",
+                &installed,
+                &supported
+            ),
+            vec!["2025", "2026"]
+        );
+        assert_eq!(
+            AllowedAddinsManager::determine_revit_versions_for_addin(
+                "2026/MyAddin",
+                &installed,
+                &supported
+            ),
+            vec!["2026"]
+        );
+        // Not installed on this machine
+        assert!(AllowedAddinsManager::determine_revit_versions_for_addin(
+            "2027/MyAddin",
+            &installed,
+            &supported
+        )
+        .is_empty());
 
-        // If no year found, return all versions
-        ALL_REVIT_VERSIONS.iter().map(|v| v.to_string()).collect()
+        fs::remove_dir_all(&root).unwrap();
     }
 }
@@ -1,3 +1,6 @@
+use log::error;
+use std::path::Path;
+
 use crate::services::{
     addin_updater::{
         models::{UpdateNotificationModel, UpdateNotificationType},
@@ -5,6 +8,10 @@ use crate::services::{
         update_checker::helpers,
     },
     addins_registry::models::addin_model::AddinModel,
+    local_addins::{
+        disabling, install_receipts, models::install_receipt_model::InstallReceiptModel,
+    },
+    local_db::service::LocalDbService,
 };
 
 /// Install or update an addin by copying files from a registry addin to a local addin.
@@ -28,3 +35,81 @@ pub fn install_addin(
         notification_type: UpdateNotificationType::Install,
     })
 }
+
+/// Updates the install receipt of an addin that was just updated. Failures are only logged, since the update itself succeeded
+///
+/// Unmanaged copies don't have a receipt, and stay unmanaged until the user adopts them
+pub async fn record_update_receipt(
+    local_db: &LocalDbService,
+    up_to_date_addin: &AddinModel,
+    current_addin: &AddinModel,
+) {
+    if find_receipt(local_db, current_addin).await.is_none() {
+        return;
+    }
+    let receipt = match install_receipts::build_update_receipt(up_to_date_addin, current_addin) {
+        Ok(receipt) => receipt,
+        Err(e) => {
+            error!(
+                "Failed to build the install receipt for {}: {}",
+                current_addin.name, e
+            );
+            return;
+        }
+    };
+    if let Err(e) = local_db.install_receipts_table().record(receipt).await {
+        error!(
+            "Failed to save the install receipt for {}: {}",
+            current_addin.name, e
+        );
+    }
+}
+
+/// Backs up the files of an addin the app installed before an update replaces them, so that the update can be
+/// rolled back. Failures are only logged, so that they don't hold back the update
+pub async fn back_up_for_rollback(local_db: &LocalDbService, current_addin: &AddinModel) {
+    let Some(receipt) = find_receipt(local_db, current_addin).await else {
+        return;
+    };
+    if let Err(e) = install_receipts::back_up_for_rollback(&receipt) {
+        error!(
+            "Failed to back up {} before updating it: {}",
+            current_addin.name, e
+        );
+    }
+}
+
+/// Whether the user rolled the addin back from the version the registry has. It isn't updated again until the
+/// registry has another version
+pub fn is_rolled_back_from(
+    receipts: &[InstallReceiptModel],
+    up_to_date_addin: &AddinModel,
+    current_addin: &AddinModel,
+) -> bool {
+    let xml_file = disabling::enabled_xml_path(Path::new(&current_addin.path_to_addin_xml_file));
+    receipts.iter().any(|receipt| {
+        Path::new(&receipt.installed_xml_file) == xml_file
+            && receipt.rolled_back_from.as_ref() == Some(&up_to_date_addin.version)
+    })
+}
+
+async fn find_receipt(
+    local_db: &LocalDbService,
+    current_addin: &AddinModel,
+) -> Option<InstallReceiptModel> {
+    let xml_file = disabling::enabled_xml_path(Path::new(&current_addin.path_to_addin_xml_file));
+    match local_db
+        .install_receipts_table()
+        .get(&xml_file.to_string_lossy())
+        .await
+    {
+        Ok(receipt) => receipt,
+        Err(e) => {
+            error!(
+                "Failed to read the install receipt for {}: {}",
+                current_addin.name, e
+            );
+            None
+        }
+    }
+}
@@ -1,9 +1,12 @@
+use log::info;
 use std::{fs, path::Path};
 
 use chrono::{DateTime, Utc};
 
 use crate::services::{
-    addin_updater::service::CheckForUpdatesError, addins_registry::models::addin_model::AddinModel,
+    addin_updater::{models::AddinVersionModel, service::CheckForUpdatesError},
+    addins_registry::models::addin_model::AddinModel,
+    local_addins::install_manifest,
 };
 
 /// Helper function to ensure target directory exists
@@ -96,6 +99,9 @@ fn copy_directory_contents(
 }
 
 /// Copies all files from the up-to-date addin to the current addin, overwriting existing files
+///
+/// Files that we installed before but that are no longer in the up-to-date addin are removed,
+/// so that stale assemblies can't be loaded anymore. Files the addin created itself are kept
 pub fn copy_all_files(
     up_to_date_addin: &AddinModel,
     current_addin: &AddinModel,
@@ -106,6 +112,22 @@ pub fn copy_all_files(
     // Copy all files from DLL folder (no filter - copy everything)
     copy_directory_contents(source_dll_folder, target_dll_folder, None)?;
 
+    let removed_files = install_manifest::converge_to_source(source_dll_folder, target_dll_folder)
+        .map_err(|e| {
+            CheckForUpdatesError::Update(format!(
+                "Failed to remove stale files from {}: {}",
+                target_dll_folder.display(),
+                e
+            ))
+        })?;
+    if !removed_files.is_empty() {
+        info!(
+            "Removed stale files from {}: {:?}",
+            target_dll_folder.display(),
+            removed_files
+        );
+    }
+
     Ok(())
 }
 
@@ -183,6 +205,15 @@ pub fn get_addin_modification_time(
     })
 }
 
+/// The version to show in the update history. None if the addin's files can't be read
+pub fn get_addin_version(addin: &AddinModel) -> Option<AddinVersionModel> {
+    let modified_at = get_addin_modification_time(addin).ok()?;
+    Some(AddinVersionModel {
+        version: addin.version.clone(),
+        modified_at: modified_at.to_rfc3339(),
+    })
+}
+
 /// Recursively finds the most recent modification time of any file in a directory tree
 fn find_most_recent_file_time(
     dir_path: &Path,
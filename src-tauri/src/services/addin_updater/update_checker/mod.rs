@@ -1,77 +1,148 @@
+use log::{debug, error, info};
+use revit_check::{RevitProcessWatcher, SystemProcessSource};
+use std::path::PathBuf;
 use std::sync::Arc;
-use std::time::Duration;
-use tauri::{AppHandle, Manager};
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
 use tokio::time::sleep;
-use tokio::{sync::Mutex, task::JoinHandle};
 
-use crate::services::admin::service::AdminService;
+use crate::services::admin::{
+    addin_packages::service::AddinPackagesService, service::AdminService,
+};
 use crate::services::{
-    addin_updater::update_checker::allowed_addins_manager::AllowedAddinsManager,
-    local_db::service::LocalDbService, user_stats::LocalUserStatsService,
+    addin_updater::update_checker::{
+        allowed_addins_manager::{AllowedAddinsManager, InstallAddinOperation, UpdateCheckError},
+        registry_watcher::RegistryWatcher,
+    },
+    addins_registry::change_token,
+    config::{app_paths::AppPaths, keys},
+    local_addins::install_activity,
+    local_db::service::LocalDbService,
+    logging::span,
+    registry_mirror::service::RegistryMirrorService,
+    revit_installations::service::RevitInstallationsService,
+    user_stats::LocalUserStatsService,
 };
 
 mod allowed_addins_manager;
 mod helpers;
 mod notifications;
-mod pending_updates;
+pub mod pending_updates;
+mod registry_watcher;
+#[cfg(test)]
+mod tests;
 mod types;
 pub use types::*;
 
 use crate::services::{
-    addin_updater::models::UpdateNotificationModel,
-    addins_registry::{models::addin_model::AddinModel, services::AsyncAddinsRegistryServiceType},
-    local_addins::service::LocalAddinsService,
+    addin_updater::models::{
+        OperationOutcome, RevitRestartResultModel, RevitRestartStage, UpdateCycleReportModel,
+        UpdateNotificationModel, UpdateOperationType,
+    },
+    addins_registry::{
+        models::addin_model::AddinModel,
+        services::{AsyncAddinsRegistryServiceType, GetAddinsError},
+    },
+    local_addins::{
+        management, models::addin_management_model::AddinManagement, service::LocalAddinsService,
+    },
 };
 
+/// How often the registry's change token is read
+const POLL_INTERVAL: Duration = Duration::from_secs(60);
+/// How long the registry must be quiet after a change before it is scanned
+const DEBOUNCE: Duration = Duration::from_secs(10);
+/// How often the running Revit processes are checked, to apply pending operations as soon as Revit exits
+const REVIT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// How long the user has to answer Revit's save prompts before the update is given up
+const REVIT_EXIT_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
 #[derive(Debug)]
 pub enum UpdateResult {
     Updated,
     RevitIsOpen,
     NoUpdatesAvailable,
+    /// The registry can't be reached, so there is nothing to compare against
+    RegistryOffline,
 }
 
 pub struct AddinUpdateChecker {
     app_handle: AppHandle,
     addins_registry: AsyncAddinsRegistryServiceType,
-    pending_updates_state: PendingUpdatesStateType,
     allowed_addins_manager: AllowedAddinsManager,
     admin_service: Arc<AdminService>,
+    local_addins: Arc<LocalAddinsService>,
+    registry_mirror: Arc<RegistryMirrorService>,
+    local_db: Arc<LocalDbService>,
+    paths: Arc<AppPaths>,
+    /// The last update cycle that did anything
+    last_report: Arc<RwLock<Option<UpdateCycleReportModel>>>,
+    /// Whether the registry was reachable the last time it was scanned
+    last_registry_reachable: Arc<RwLock<Option<bool>>>,
 }
 
 impl AddinUpdateChecker {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         app_handle: AppHandle,
         addins_registry: AsyncAddinsRegistryServiceType,
         user_stats: Arc<LocalUserStatsService>,
         db: Arc<LocalDbService>,
         admin_service: Arc<AdminService>,
+        packages_service: Arc<AddinPackagesService>,
+        revit_installations: Arc<RevitInstallationsService>,
+        local_addins: Arc<LocalAddinsService>,
+        registry_mirror: Arc<RegistryMirrorService>,
+        paths: Arc<AppPaths>,
     ) -> Self {
-        let allowed_addins_manager =
-            AllowedAddinsManager::new(app_handle.clone(), user_stats, db, addins_registry.clone());
-        let pending_updates_state = Arc::new(Mutex::new(PendingUpdatesState::default()));
-        app_handle.manage(pending_updates_state.clone());
+        let allowed_addins_manager = AllowedAddinsManager::new(
+            app_handle.clone(),
+            user_stats,
+            db.clone(),
+            addins_registry.clone(),
+            packages_service,
+            revit_installations,
+            local_addins.clone(),
+        );
         Self {
             app_handle,
             addins_registry,
-            pending_updates_state,
             allowed_addins_manager,
             admin_service,
+            local_addins,
+            registry_mirror,
+            local_db: db,
+            paths,
+            last_report: Arc::default(),
+            last_registry_reachable: Arc::default(),
         }
     }
-    /// Spawns the background update checker loop and manages the shared state
+    /// Spawns the background update checker loop
     pub fn spawn_update_checker(&self) -> JoinHandle<()> {
         let app_handle = self.app_handle.clone();
         let addins_registry = self.addins_registry.clone();
-        let pending_updates_state = self.pending_updates_state.clone();
         let allowed_addins_manager = self.allowed_addins_manager.clone();
         let admin_service = self.admin_service.clone();
+        let local_addins = self.local_addins.clone();
+        let registry_mirror = self.registry_mirror.clone();
+        let local_db = self.local_db.clone();
+        let paths = self.paths.clone();
+        let last_report = self.last_report.clone();
+        let last_registry_reachable = self.last_registry_reachable.clone();
         tokio::spawn(async move {
             AddinUpdateChecker {
                 app_handle,
                 addins_registry,
-                pending_updates_state,
                 allowed_addins_manager,
                 admin_service,
+                local_addins,
+                registry_mirror,
+                local_db,
+                paths,
+                last_report,
+                last_registry_reachable,
             }
             .update_checker_loop()
             .await;
@@ -80,101 +151,411 @@ impl AddinUpdateChecker {
 
     /// Checks for updates, applies them if possible, and manages pending updates
     ///
-    /// TODO: if one install fails, the whole thing fails. Consider just emitting notifications for errors
-    async fn check_and_apply_updates(&self) -> Result<UpdateResult, String> {
-        let addins = self
-            .addins_registry
-            .get_addins()
-            .await
-            .map_err(|e| format!("Registry error: {}", e))?;
+    /// Each update and install is applied on its own, a failed one is kept as a pending operation and retried later.
+    /// What happened to each of them is added to the report. The registry mirror is synced from the same scan, so
+    /// the share is only read once per cycle
+    async fn check_and_apply_updates(
+        &self,
+        report: &mut UpdateCycleReportModel,
+    ) -> Result<UpdateResult, String> {
+        span::in_span("update_cycle", "update check", async {
+            let Some(addins) = self.scan_registry(report).await? else {
+                return Ok(UpdateResult::RegistryOffline);
+            };
+            let update_result = {
+                let _installing = install_activity::begin().await;
+                self.apply_updates_from(&addins, report).await
+            };
+            if let Err(e) = self.reconcile_registry_mirror(&addins).await {
+                error!("Error updating the registry mirror: {}", e);
+            }
+            update_result
+        })
+        .await
+    }
+
+    /// Reads the addins in the registry. None if it can't be reached
+    async fn scan_registry(
+        &self,
+        report: &mut UpdateCycleReportModel,
+    ) -> Result<Option<Vec<AddinModel>>, String> {
+        match self.addins_registry.get_addins().await {
+            Ok(addins) => {
+                report.registry_reachable = Some(true);
+                Ok(Some(addins))
+            }
+            Err(GetAddinsError::RegistryNotFound(_)) => {
+                report.registry_reachable = Some(false);
+                Ok(None)
+            }
+            Err(e) => Err(format!("Registry error: {}", e)),
+        }
+    }
 
-        let current_local_addins = LocalAddinsService::get_local_addins()
+    async fn apply_updates_from(
+        &self,
+        addins: &[AddinModel],
+        report: &mut UpdateCycleReportModel,
+    ) -> Result<UpdateResult, String> {
+        let current_local_addins = self
+            .local_addins
+            .get_local_addins()
             .map_err(|e| format!("Local addins error: {}", e))?;
+        let receipts = self
+            .local_db
+            .install_receipts_table()
+            .get_all()
+            .await
+            .map_err(|e| format!("Install receipts error: {}", e))?;
+        // Foreign addins only share a DLL folder name with a registry addin at most, they are never ours to update
+        let current_local_addins: Vec<AddinModel> = current_local_addins
+            .into_iter()
+            .filter(|addin| {
+                management::classify(addin, Some(addins), &receipts) != AddinManagement::Foreign
+            })
+            .collect();
 
-        let addins_needing_updates =
-            Self::detect_addins_needing_update(&addins, &current_local_addins)?;
+        let addins_needing_updates: Vec<AddinNeedingUpdate> =
+            Self::detect_addins_needing_update(addins, &current_local_addins)?
+                .into_iter()
+                .filter(|update| {
+                    !helpers::is_rolled_back_from(
+                        &receipts,
+                        &update.registry_addin,
+                        &update.local_addin,
+                    )
+                })
+                .collect();
 
         // Only check for addins needing installs if the user is NOT an admin:
         let mut addins_needing_installs = Vec::new();
         if !self.admin_service.is_admin().await {
-            addins_needing_installs = self
-                .allowed_addins_manager
-                .run_check()
-                .await
-                .map_err(|e| e.to_string())?;
+            addins_needing_installs = match self.allowed_addins_manager.run_check().await {
+                Ok(operations) => operations,
+                // Updates don't need the stats database, so keep going without the installs
+                Err(UpdateCheckError::StatsDbOffline(e)) => {
+                    info!(
+                        "Skipping addin installs, the stats database is offline: {}",
+                        e
+                    );
+                    Vec::new()
+                }
+                Err(e) => return Err(e.to_string()),
+            };
         }
-        // ! Debug print:
-        println!("Addins needing installs: {}", addins_needing_installs.len());
+        debug!("Addins needing installs: {}", addins_needing_installs.len());
 
         if addins_needing_updates.is_empty() && addins_needing_installs.is_empty() {
-            pending_updates::clear_pending_updates(&self.pending_updates_state).await;
             return Ok(UpdateResult::NoUpdatesAvailable);
         }
-        let revit_is_running = revit_check::is_revit_running()
-            .await
-            .map_err(|e| e.to_string())?;
-        if revit_is_running {
-            // Dont apply updates yet, store them for later
-            // This is to avoid applying updates while Revit is running, which can cause issues
-            // with the addin not being able to load.
-            pending_updates::store_pending_updates(
-                &self.pending_updates_state,
-                &addins_needing_updates,
-            )
-            .await;
-            notifications::with(&self.app_handle).update_addin_pending(&addins_needing_updates);
-            notifications::with(&self.app_handle).install_addin_pending(&addins_needing_installs);
-            // notifications::with(&self.app_handle).pending_update(&addins_needing_updates);
-            return Ok(UpdateResult::RevitIsOpen);
-        }
-        self.apply_updates(addins_needing_updates).await;
-        // Apply all of the installs:
-        for install in addins_needing_installs.iter() {
-            install.execute().await.map_err(|e| e.to_string())?;
+        let running_revit = revit_check::get_running_revit_versions().await?;
+        // Only the addins of running Revit versions wait, as applying them could stop them from loading.
+        // Everything else is applied right away, an install for several versions is split between the two
+        let (deferred_updates, updates): (Vec<_>, Vec<_>) =
+            addins_needing_updates.into_iter().partition(|update| {
+                pending_updates::is_deferred(
+                    &running_revit,
+                    update.local_addin.revit_version.as_deref(),
+                )
+            });
+        let mut installs = Vec::new();
+        let mut deferred_installs = Vec::new();
+        for install in addins_needing_installs {
+            let (install, deferred_install) = install.split_deferred(|version| {
+                pending_updates::is_deferred(&running_revit, Some(version))
+            });
+            installs.extend(install);
+            deferred_installs.extend(deferred_install);
         }
 
-        pending_updates::clear_pending_updates(&self.pending_updates_state).await;
-        Ok(UpdateResult::Updated)
+        self.apply_updates(updates, report).await?;
+        self.apply_installs(installs, report).await?;
+
+        for deferred_update in &deferred_updates {
+            report
+                .push(
+                    &deferred_update.local_addin.name,
+                    UpdateOperationType::Update,
+                    OperationOutcome::Deferred,
+                )
+                .with_versions(
+                    helpers::get_addin_version(&deferred_update.local_addin),
+                    helpers::get_addin_version(&deferred_update.registry_addin),
+                );
+        }
+        for deferred_install in &deferred_installs {
+            report
+                .push(
+                    &deferred_install.addin.name,
+                    UpdateOperationType::Install,
+                    OperationOutcome::Deferred,
+                )
+                .with_versions(None, helpers::get_addin_version(&deferred_install.addin));
+        }
+        if deferred_updates.is_empty() && deferred_installs.is_empty() {
+            return Ok(UpdateResult::Updated);
+        }
+        pending_updates::store_pending_updates(
+            &self.local_db,
+            &deferred_updates,
+            &deferred_installs,
+        )
+        .await?;
+        notifications::with(&self.app_handle).update_addin_pending(&deferred_updates);
+        notifications::with(&self.app_handle).install_addin_pending(&deferred_installs);
+        Ok(UpdateResult::RevitIsOpen)
     }
 
     /// The main background update checker loop
+    ///
+    /// The registry is only scanned when it changed, which the watcher and the change token at the registry root
+    /// tell. If neither is available, or the configured interval passed, it is scanned anyway
     pub async fn update_checker_loop(self) {
+        let mut registry_was_offline = false;
+        let mut watcher = RegistryWatcher::default();
+        let (_revit_watcher, mut revit_changes) =
+            RevitProcessWatcher::spawn(SystemProcessSource, REVIT_POLL_INTERVAL);
+        let mut last_change_token = None;
+        let mut last_scan: Option<Instant> = None;
         loop {
-            match self.check_and_apply_updates().await {
-                Ok(update_result) => {
-                    println!(
-                        "Update result from update checker loop: {:?}",
-                        update_result
-                    );
-                }
-                Err(e) => {
-                    eprintln!("Error checking for updates: {}", e);
+            let mut report = UpdateCycleReportModel::start();
+            let registry_root = self.registry_root().await;
+            if let Some(registry_root) = &registry_root {
+                watcher.watch(registry_root);
+            }
+            let change_token = registry_root.as_deref().and_then(change_token::read);
+            let registry_changed =
+                watcher.take_changed(DEBOUNCE) || change_token != last_change_token;
+            let can_tell_changes = watcher.is_active() || change_token.is_some();
+            let interval = keys::get_update_check_interval_minutes(self.local_db.clone())
+                .await
+                .unwrap_or(keys::DEFAULT_UPDATE_CHECK_INTERVAL_MINUTES);
+            let interval = Duration::from_secs(interval.checked_mul(60).unwrap_or(u64::MAX));
+            let is_due = last_scan.map_or(true, |last_scan| last_scan.elapsed() >= interval);
+
+            if registry_changed || !can_tell_changes || is_due {
+                last_change_token = change_token;
+                last_scan = Some(Instant::now());
+                match self.check_and_apply_updates(&mut report).await {
+                    // Only logged once, instead of every minute until the registry is back
+                    Ok(UpdateResult::RegistryOffline) => {
+                        if !registry_was_offline {
+                            info!("The registry can't be reached, waiting for it to come back");
+                        }
+                        registry_was_offline = true;
+                    }
+                    Ok(update_result) => {
+                        registry_was_offline = false;
+                        info!(
+                            "Update result from update checker loop: {:?}",
+                            update_result
+                        );
+                    }
+                    Err(e) => {
+                        error!("Error checking for updates: {}", e);
+                    }
                 }
             }
-            // Try to apply pending updates if Revit is now closed
-            if let Err(e) = pending_updates::try_apply_pending_updates(
+            // Try to apply pending operations if Revit is now closed. This also runs right after Revit exits
+            if let Err(e) = pending_updates::try_apply_pending_operations(
                 &self.addins_registry,
-                &self.pending_updates_state,
+                &self.local_addins,
+                &self.local_db,
                 &self.app_handle,
+                &mut report,
             )
             .await
             {
-                eprintln!("Error applying pending updates: {}", e);
+                error!("Error applying pending operations: {}", e);
+            }
+            self.publish_report(report).await;
+            tokio::select! {
+                _ = watcher.changed(DEBOUNCE) => {}
+                _ = sleep(POLL_INTERVAL) => {}
+                Ok(change) = revit_changes.recv() => {
+                    notifications::with(&self.app_handle).revit_processes_changed(&change);
+                }
             }
-            sleep(Duration::from_secs(60)).await;
         }
     }
 
+    async fn registry_root(&self) -> Option<PathBuf> {
+        keys::get_addins_registry_path(&self.paths, self.local_db.clone())
+            .await
+            .ok()
+            .filter(|registry_root| !registry_root.is_empty())
+            .map(PathBuf::from)
+    }
+
     /// Manually trigger a check for updates (used by Tauri command)
     pub async fn manual_check_for_updates(&self) -> Result<UpdateResult, String> {
-        println!("Manual update check triggered");
-        let update_result = self.check_and_apply_updates().await?;
-        if let UpdateResult::NoUpdatesAvailable = update_result {
-            notifications::with(&self.app_handle).emit_no_updates();
+        info!("Manual update check triggered");
+        let mut report = UpdateCycleReportModel::start();
+        let update_result = self.check_and_apply_updates(&mut report).await;
+        self.publish_report(report).await;
+        let update_result = update_result?;
+        match update_result {
+            UpdateResult::NoUpdatesAvailable => {
+                notifications::with(&self.app_handle).emit_no_updates();
+            }
+            UpdateResult::RegistryOffline => {
+                return Err("The addin registry can't be reached".to_string());
+            }
+            _ => {}
         }
         Ok(update_result)
     }
 
+    /// Asks Revit to close, applies the pending operations once it has, then starts Revit again.
+    /// Revit is reopened even if the update fails
+    pub async fn close_revit_and_update(&self) -> Result<RevitRestartResultModel, String> {
+        let notifier = notifications::with(&self.app_handle);
+        notifier.revit_restart_progress(RevitRestartStage::Closing, "Asking Revit to close");
+        let close_request = revit_check::request_revit_close().await?;
+        info!("{}", close_request.message);
+
+        let mut result = RevitRestartResultModel {
+            revit_closed: false,
+            not_closed_count: close_request.total_count - close_request.closed_count,
+            remaining_operations: 0,
+            reopened_count: 0,
+            error: None,
+        };
+        match self.update_once_revit_closed(&close_request).await {
+            Ok(revit_closed) => result.revit_closed = revit_closed,
+            Err(e) => result.error = Some(e),
+        }
+
+        if !close_request.is_empty() {
+            notifier.revit_restart_progress(RevitRestartStage::Reopening, "Starting Revit again");
+            match revit_check::reopen_revit(&close_request).await {
+                Ok(reopen_result) => result.reopened_count = reopen_result.reopened_count,
+                Err(e) => {
+                    error!("Failed to reopen Revit: {}", e);
+                    result.error.get_or_insert(e);
+                }
+            }
+        }
+        result.remaining_operations = self
+            .local_db
+            .pending_operations_table()
+            .get_all()
+            .await?
+            .len();
+        notifier.revit_restart_progress(RevitRestartStage::Done, "Done");
+        Ok(result)
+    }
+
+    /// Returns false if Revit didn't close in time, in which case nothing is applied.
+    /// Operations waiting for their retry are applied right away, since Revit was closed for them
+    async fn update_once_revit_closed(
+        &self,
+        close_request: &revit_check::RevitCloseRequest,
+    ) -> Result<bool, String> {
+        let notifier = notifications::with(&self.app_handle);
+        notifier.revit_restart_progress(
+            RevitRestartStage::WaitingForExit,
+            "Waiting for Revit to close. Save your work if Revit asks",
+        );
+        if !revit_check::wait_for_revit_exit(close_request, REVIT_EXIT_TIMEOUT).await? {
+            return Ok(false);
+        }
+        notifier.revit_restart_progress(RevitRestartStage::Updating, "Updating addins");
+        let mut report = UpdateCycleReportModel::start();
+        let applied = pending_updates::apply_all_pending_operations(
+            &self.addins_registry,
+            &self.local_addins,
+            &self.local_db,
+            &self.app_handle,
+            &mut report,
+        )
+        .await;
+        self.publish_report(report).await;
+        applied?;
+        Ok(true)
+    }
+
+    pub async fn last_update_cycle_report(&self) -> Option<UpdateCycleReportModel> {
+        self.last_report.read().await.clone()
+    }
+
+    /// Keeps the report in the update history and for the UI, and notifies about the failures.
+    /// Cycles that didn't apply anything are dropped, unless the registry became reachable or unreachable
+    async fn publish_report(&self, mut report: UpdateCycleReportModel) {
+        let previous_registry_reachable = {
+            let mut last_registry_reachable = self.last_registry_reachable.write().await;
+            let previous = *last_registry_reachable;
+            if report.registry_reachable.is_some() {
+                *last_registry_reachable = report.registry_reachable;
+            }
+            previous
+        };
+        if !report.is_worth_keeping(previous_registry_reachable) {
+            return;
+        }
+        report.finish();
+        if let Err(e) = self.local_db.update_history_table().record(&report).await {
+            error!("Failed to record the update cycle in the history: {}", e);
+        }
+        let notifier = notifications::with(&self.app_handle);
+        notifier.operations_failed(&report);
+        notifier.update_cycle_report(&report);
+        *self.last_report.write().await = Some(report);
+    }
+
+    /// Mirrors the files of the addins the user is entitled to, so they can be reinstalled while the registry is offline
+    ///
+    /// Entitled addins are the ones that are installed locally and the ones the user is allowed to have
+    async fn reconcile_registry_mirror(&self, addins: &[AddinModel]) -> Result<(), String> {
+        let current_local_addins = self
+            .local_addins
+            .get_local_addins()
+            .map_err(|e| format!("Local addins error: {}", e))?;
+
+        let mut entitled = Self::find_installed_registry_addins(addins, &current_local_addins);
+        let is_complete = match self
+            .allowed_addins_manager
+            .allowed_registry_addins(addins)
+            .await
+        {
+            Ok(allowed) => {
+                entitled.extend(allowed);
+                true
+            }
+            Err(e) => {
+                info!(
+                    "Mirroring only the installed addins, could not read the allowed addins: {}",
+                    e
+                );
+                false
+            }
+        };
+
+        let registry_mirror = self.registry_mirror.clone();
+        tokio::task::spawn_blocking(move || registry_mirror.sync_payloads(&entitled, is_complete))
+            .await
+            .map_err(|e| e.to_string())?
+    }
+
+    /// The registry addins that have a local install, matched the same way as in `detect_addins_needing_update`
+    fn find_installed_registry_addins(
+        addins: &[AddinModel],
+        current_local_addins: &[AddinModel],
+    ) -> Vec<AddinModel> {
+        let local_dll_names: Vec<String> = current_local_addins
+            .iter()
+            .filter_map(|addin| helpers::get_addin_dll_folder_name(addin).ok())
+            .collect();
+        addins
+            .iter()
+            .filter(|addin| {
+                helpers::get_addin_dll_folder_name(addin)
+                    .is_ok_and(|dll_name| local_dll_names.contains(&dll_name))
+            })
+            .cloned()
+            .collect()
+    }
+
     /// Returns a list of (registry_addin, local_addin) pairs that need updating
     fn detect_addins_needing_update(
         addins: &[AddinModel],
@@ -210,20 +591,95 @@ impl AddinUpdateChecker {
         Ok(needing_update)
     }
 
-    /// Applies updates for all addins that need them
-    async fn apply_updates(&self, addins_needing_updates: Vec<AddinNeedingUpdate>) {
+    /// Applies updates for all addins that need them, except the ones waiting for a retry
+    async fn apply_updates(
+        &self,
+        addins_needing_updates: Vec<AddinNeedingUpdate>,
+        report: &mut UpdateCycleReportModel,
+    ) -> Result<(), String> {
         let mut notifications = Vec::new();
         for addin_needing_update in addins_needing_updates {
+            let operation = pending_updates::update_operation(&addin_needing_update);
             let registry_addin = addin_needing_update.registry_addin;
             let local_addin = addin_needing_update.local_addin;
-            println!("Updating addin: {}", local_addin.name);
-            match helpers::install_addin(&registry_addin, &local_addin) {
-                Ok(update_notification) => notifications.push(update_notification),
-                Err(e) => {
-                    eprintln!("Failed to update addin {}: {:?}", local_addin.name, e);
+            // Read before the files are replaced
+            let version_before = helpers::get_addin_version(&local_addin);
+            let version_after = helpers::get_addin_version(&registry_addin);
+            let outcome = if let Some(reason) =
+                pending_updates::waiting_for_retry(&self.local_db, &operation).await?
+            {
+                OperationOutcome::Skipped { reason }
+            } else {
+                info!("Updating addin: {}", local_addin.name);
+                helpers::back_up_for_rollback(&self.local_db, &local_addin).await;
+                match helpers::install_addin(&registry_addin, &local_addin) {
+                    Ok(update_notification) => {
+                        helpers::record_update_receipt(
+                            &self.local_db,
+                            &registry_addin,
+                            &local_addin,
+                        )
+                        .await;
+                        notifications.push(update_notification);
+                        // It may have failed before
+                        self.local_db
+                            .pending_operations_table()
+                            .remove(&operation.key)
+                            .await?;
+                        OperationOutcome::Succeeded
+                    }
+                    Err(e) => {
+                        pending_updates::record_failure(
+                            &self.local_db,
+                            operation,
+                            format!("{:?}", e),
+                        )
+                        .await?
+                    }
                 }
-            }
+            };
+            report
+                .push(&local_addin.name, UpdateOperationType::Update, outcome)
+                .with_versions(version_before, version_after);
+        }
+        if !notifications.is_empty() {
+            notifications::with(&self.app_handle).emit_update(&notifications);
+        }
+        Ok(())
+    }
+
+    /// Installs the addins the user is allowed to have, except the ones waiting for a retry
+    async fn apply_installs(
+        &self,
+        installs: Vec<InstallAddinOperation>,
+        report: &mut UpdateCycleReportModel,
+    ) -> Result<(), String> {
+        for install in installs {
+            let operation = install.to_pending_operation();
+            let outcome = if let Some(reason) =
+                pending_updates::waiting_for_retry(&self.local_db, &operation).await?
+            {
+                OperationOutcome::Skipped { reason }
+            } else {
+                match install.execute().await {
+                    Ok(()) => {
+                        // It may have failed before
+                        self.local_db
+                            .pending_operations_table()
+                            .remove(&operation.key)
+                            .await?;
+                        OperationOutcome::Succeeded
+                    }
+                    Err(e) => {
+                        pending_updates::record_failure(&self.local_db, operation, e.to_string())
+                            .await?
+                    }
+                }
+            };
+            report
+                .push(&install.addin.name, UpdateOperationType::Install, outcome)
+                .with_versions(None, helpers::get_addin_version(&install.addin));
         }
-        notifications::with(&self.app_handle).emit_update(&notifications);
+        Ok(())
     }
 }
@@ -1,7 +1,12 @@
+use log::error;
+use revit_check::RevitProcessChange;
 use tauri::{AppHandle, Emitter};
 
 use crate::services::addin_updater::{
-    models::UpdateNotificationType,
+    models::{
+        OperationOutcome, RevitRestartProgressModel, RevitRestartStage, UpdateCycleReportModel,
+        UpdateNotificationType, UpdateOperationType,
+    },
     update_checker::{
         allowed_addins_manager::{InstallAddinOperation, Operation},
         *,
@@ -19,7 +24,7 @@ pub struct Notifier {
 impl Notifier {
     pub fn emit_update(&self, notifications: &[UpdateNotificationModel]) {
         if let Err(e) = self.app.emit("addin_updates_available", notifications) {
-            eprintln!("Failed to emit addin updates event: {}", e);
+            error!("Failed to emit addin updates event: {}", e);
         }
     }
 
@@ -33,7 +38,7 @@ impl Notifier {
             .app
             .emit("addin_updates_available", &no_updates_notification)
         {
-            eprintln!("Failed to emit no updates notification: {}", e);
+            error!("Failed to emit no updates notification: {}", e);
         }
     }
 
@@ -66,6 +71,58 @@ impl Notifier {
         self.emit_update(&notifications);
     }
 
+    pub fn operations_failed(&self, report: &UpdateCycleReportModel) {
+        let mut notifications = Vec::new();
+        for failure in report.failures() {
+            let OperationOutcome::Failed {
+                cause, retry_at, ..
+            } = &failure.outcome
+            else {
+                continue;
+            };
+            let action = match failure.operation_type {
+                UpdateOperationType::Update => "update",
+                UpdateOperationType::Install => "install",
+                UpdateOperationType::Uninstall => "uninstall",
+            };
+            let retry = match retry_at {
+                Some(retry_at) => format!("it will be retried after {}", retry_at),
+                None => "it will be retried later".to_string(),
+            };
+            notifications.push(UpdateNotificationModel {
+                title: format!("Failed to {} {}", action, failure.addin_name),
+                description: format!("{}, {}", cause, retry),
+                notification_type: UpdateNotificationType::Error,
+            });
+        }
+        if !notifications.is_empty() {
+            self.emit_update(&notifications);
+        }
+    }
+
+    pub fn update_cycle_report(&self, report: &UpdateCycleReportModel) {
+        if let Err(e) = self.app.emit("update_cycle_report", report) {
+            error!("Failed to emit update cycle report: {}", e);
+        }
+    }
+
+    pub fn revit_restart_progress(&self, stage: RevitRestartStage, message: &str) {
+        let progress = RevitRestartProgressModel {
+            stage,
+            message: message.to_string(),
+        };
+        if let Err(e) = self.app.emit("revit_restart_progress", progress) {
+            error!("Failed to emit Revit restart progress: {}", e);
+        }
+    }
+
+    /// Lets the UI know that Revit started or exited
+    pub fn revit_processes_changed(&self, change: &RevitProcessChange) {
+        if let Err(e) = self.app.emit("revit_processes_changed", change) {
+            error!("Failed to emit Revit processes event: {}", e);
+        }
+    }
+
     pub fn allowed_addin_installed(&self, addin: &AddinModel) {
         let notification = UpdateNotificationModel {
             title: format!("{} installed", addin.name),
@@ -1,85 +1,425 @@
+use log::{error, info};
+use revit_check::RunningRevitVersions;
 use tauri::AppHandle;
+use tokio::sync::Mutex;
 
-use crate::services::addin_updater::update_checker::*;
+use crate::services::{
+    addin_updater::{
+        models::{
+            AddinVersionModel, OperationOutcome, PendingOperationKind, PendingOperationModel,
+            UpdateCycleReportModel,
+        },
+        update_checker::{allowed_addins_manager::InstallAddinOperation, *},
+    },
+    local_addins::{
+        install_activity, models::uninstall_result_model::UninstallTargetModel, uninstall,
+    },
+};
 
+static APPLYING: Mutex<()> = Mutex::const_new(());
+
+/// What happened when a pending operation was applied
+enum Outcome {
+    Applied {
+        notification: Option<UpdateNotificationModel>,
+        version_before: Option<AddinVersionModel>,
+        version_after: Option<AddinVersionModel>,
+    },
+    /// Applied for the Revit versions that are closed, the rest still waits for the others to close
+    PartlyApplied {
+        version_after: Option<AddinVersionModel>,
+        remaining: PendingOperationKind,
+    },
+    /// A Revit version it targets is still running
+    Deferred,
+    /// Nothing left to do, e.g. the addin was updated or uninstalled in the meantime
+    Obsolete(String),
+}
+
+/// Stores the updates and installs that have to wait for Revit to close
 pub async fn store_pending_updates(
-    update_state: &PendingUpdatesStateType,
+    local_db: &LocalDbService,
     addins_needing_updates: &[AddinNeedingUpdate],
-) {
-    let pending_updates: Vec<AddinNeedingUpdatePaths> = addins_needing_updates
+    addins_needing_installs: &[InstallAddinOperation],
+) -> Result<(), String> {
+    let updates = addins_needing_updates.iter().map(update_operation);
+    let installs = addins_needing_installs
         .iter()
-        .map(|addin_needing_update| AddinNeedingUpdatePaths {
-            registry_addin_path: addin_needing_update
+        .map(InstallAddinOperation::to_pending_operation);
+    for operation in updates.chain(installs) {
+        local_db.pending_operations_table().save(operation).await?;
+    }
+    Ok(())
+}
+
+/// The update to apply once Revit is closed or to retry
+pub fn update_operation(addin_needing_update: &AddinNeedingUpdate) -> PendingOperationModel {
+    PendingOperationModel::new(
+        &addin_needing_update.local_addin.name,
+        PendingOperationKind::Update {
+            registry_dll_folder: addin_needing_update
                 .registry_addin
                 .path_to_addin_dll_folder
                 .clone(),
-            local_addin_path: addin_needing_update
+            local_dll_folder: addin_needing_update
                 .local_addin
                 .path_to_addin_dll_folder
                 .clone(),
-        })
-        .collect();
-    let mut state = update_state.lock().await;
-    state.pending_updates = Some(pending_updates);
+        },
+    )
+}
+
+/// If the operation failed before and its retry isn't due yet, says until when it waits
+pub async fn waiting_for_retry(
+    local_db: &LocalDbService,
+    operation: &PendingOperationModel,
+) -> Result<Option<String>, String> {
+    let previous = local_db
+        .pending_operations_table()
+        .get(&operation.key)
+        .await?;
+    Ok(previous
+        .filter(|previous| !previous.is_due(chrono::Utc::now()))
+        .map(|previous| {
+            format!(
+                "it failed {} time(s), the next try is at {}",
+                previous.attempts,
+                previous.next_attempt_at.unwrap_or_default()
+            )
+        }))
+}
+
+/// Keeps the failed operation to retry it later
+pub async fn record_failure(
+    local_db: &LocalDbService,
+    operation: PendingOperationModel,
+    error: String,
+) -> Result<OperationOutcome, String> {
+    error!("Failed to apply operation {}: {}", operation.key, error);
+    let failed = local_db
+        .pending_operations_table()
+        .record_failure(operation, error.clone())
+        .await?;
+    Ok(OperationOutcome::Failed {
+        cause: error,
+        attempts: failed.attempts,
+        retry_at: failed.next_attempt_at,
+    })
 }
 
-pub async fn clear_pending_updates(update_state: &PendingUpdatesStateType) {
-    let mut state = update_state.lock().await;
-    state.pending_updates = None;
+/// Remembers uninstalls that failed because Revit held the files, so they are finished once Revit closes
+pub async fn store_pending_uninstalls(
+    local_db: &LocalDbService,
+    targets: Vec<UninstallTargetModel>,
+) -> Result<(), String> {
+    for target in targets {
+        let addin_name = target.addin_name.clone();
+        let operation =
+            PendingOperationModel::new(&addin_name, PendingOperationKind::Uninstall { target });
+        local_db.pending_operations_table().save(operation).await?;
+    }
+    Ok(())
 }
 
-pub async fn try_apply_pending_updates(
+/// Whether an operation on an addin of the Revit version has to wait for that version to close.
+/// Without a version, it waits for every Revit to close
+pub fn is_deferred(running_revit: &RunningRevitVersions, revit_version: Option<&str>) -> bool {
+    match revit_version {
+        Some(version) => running_revit.is_running(version),
+        None => running_revit.is_any_running(),
+    }
+}
+
+/// Applies the pending operations whose Revit version is closed and whose retry is due, oldest first
+///
+/// Each operation checks whether it is still needed, so applying one twice, e.g. after a crash, does no harm.
+/// The ones that fail are kept for the next try. Operations that still wait are left out of the report,
+/// as they were reported when they were deferred
+pub async fn try_apply_pending_operations(
+    addins_registry: &AsyncAddinsRegistryServiceType,
+    local_addins: &LocalAddinsService,
+    local_db: &LocalDbService,
+    app: &AppHandle,
+    report: &mut UpdateCycleReportModel,
+) -> Result<(), String> {
+    apply_pending_operations(addins_registry, local_addins, local_db, app, report, false).await
+}
+
+/// Applies every pending operation whose Revit version is closed, including the ones whose retry isn't due yet.
+/// Used when the user closed Revit to update, so nothing is left waiting for its retry
+pub async fn apply_all_pending_operations(
     addins_registry: &AsyncAddinsRegistryServiceType,
-    update_state: &PendingUpdatesStateType,
+    local_addins: &LocalAddinsService,
+    local_db: &LocalDbService,
     app: &AppHandle,
+    report: &mut UpdateCycleReportModel,
 ) -> Result<(), String> {
-    let pending_updates = {
-        let mut state = update_state.lock().await;
-        state.pending_updates.take()
-    };
-    if let Some(pending_paths) = pending_updates {
-        let notifications = apply_pending_updates_by_path(addins_registry, &pending_paths).await?;
-        if !notifications.is_empty() {
-            notifications::with(app).emit_update(&notifications);
+    apply_pending_operations(addins_registry, local_addins, local_db, app, report, true).await
+}
+
+async fn apply_pending_operations(
+    addins_registry: &AsyncAddinsRegistryServiceType,
+    local_addins: &LocalAddinsService,
+    local_db: &LocalDbService,
+    app: &AppHandle,
+    report: &mut UpdateCycleReportModel,
+    ignore_retry_delay: bool,
+) -> Result<(), String> {
+    // The update checker and "close Revit and update" can both get here when Revit exits
+    let _applying = APPLYING.lock().await;
+    let table = local_db.pending_operations_table();
+    let now = chrono::Utc::now();
+    let operations: Vec<_> = table
+        .get_all()
+        .await?
+        .into_iter()
+        .filter(|operation| ignore_retry_delay || operation.is_due(now))
+        .collect();
+    if operations.is_empty() {
+        return Ok(());
+    }
+    let _installing = install_activity::begin().await;
+    let running_revit = revit_check::get_running_revit_versions().await?;
+    let mut registry_addins = RegistryAddins::new(addins_registry);
+
+    let mut update_notifications = Vec::new();
+    for operation in operations {
+        let result = apply_operation(
+            addins_registry,
+            &mut registry_addins,
+            local_addins,
+            local_db,
+            app,
+            &running_revit,
+            &operation,
+        )
+        .await;
+        match result {
+            Ok(Outcome::Deferred) => {}
+            Ok(Outcome::Applied {
+                notification,
+                version_before,
+                version_after,
+            }) => {
+                info!("Applied pending operation {}", operation.key);
+                update_notifications.extend(notification);
+                table.remove(&operation.key).await?;
+                report
+                    .push(
+                        &operation.addin_name,
+                        operation.operation_type(),
+                        OperationOutcome::Succeeded,
+                    )
+                    .with_versions(version_before, version_after);
+            }
+            Ok(Outcome::PartlyApplied {
+                version_after,
+                remaining,
+            }) => {
+                info!(
+                    "Applied pending operation {} for the closed Revit versions",
+                    operation.key
+                );
+                // Replaced instead of saved over, the failures of the whole operation don't carry over to the rest
+                table.remove(&operation.key).await?;
+                table
+                    .save(PendingOperationModel::new(&operation.addin_name, remaining))
+                    .await?;
+                report
+                    .push(
+                        &operation.addin_name,
+                        operation.operation_type(),
+                        OperationOutcome::Succeeded,
+                    )
+                    .with_versions(None, version_after);
+            }
+            Ok(Outcome::Obsolete(reason)) => {
+                info!("Dropping pending operation {}: {}", operation.key, reason);
+                table.remove(&operation.key).await?;
+                report.push(
+                    &operation.addin_name,
+                    operation.operation_type(),
+                    OperationOutcome::Skipped { reason },
+                );
+            }
+            Err(e) => {
+                let addin_name = operation.addin_name.clone();
+                let operation_type = operation.operation_type();
+                let outcome = record_failure(local_db, operation, e).await?;
+                report.push(&addin_name, operation_type, outcome);
+            }
         }
     }
+    if !update_notifications.is_empty() {
+        notifications::with(app).emit_update(&update_notifications);
+    }
     Ok(())
 }
 
-pub async fn apply_pending_updates_by_path(
+async fn apply_operation(
     addins_registry: &AsyncAddinsRegistryServiceType,
-    pending_paths: &[AddinNeedingUpdatePaths],
-) -> Result<Vec<UpdateNotificationModel>, String> {
-    let addins = addins_registry
-        .get_addins()
-        .await
-        .map_err(|e| format!("Registry error: {}", e))?;
-    let current_local_addins =
-        LocalAddinsService::get_local_addins().map_err(|e| format!("Local addins error: {}", e))?;
-    let mut update_notifications = Vec::new();
-    for addin_needing_update_paths in pending_paths {
-        let registry_path = &addin_needing_update_paths.registry_addin_path;
-        let local_path = &addin_needing_update_paths.local_addin_path;
-        if let Some(registry_addin) = addins
-            .iter()
-            .find(|a| a.path_to_addin_dll_folder == *registry_path)
-        {
-            if let Some(local_addin) = current_local_addins
+    registry_addins: &mut RegistryAddins<'_>,
+    local_addins: &LocalAddinsService,
+    local_db: &LocalDbService,
+    app: &AppHandle,
+    running_revit: &RunningRevitVersions,
+    operation: &PendingOperationModel,
+) -> Result<Outcome, String> {
+    match &operation.operation {
+        PendingOperationKind::Update {
+            registry_dll_folder,
+            local_dll_folder,
+        } => {
+            let current_local_addins = local_addins
+                .get_local_addins()
+                .map_err(|e| format!("Local addins error: {}", e))?;
+            let Some(local_addin) = current_local_addins
                 .iter()
-                .find(|a| a.path_to_addin_dll_folder == *local_path)
-            {
-                println!("Applying pending update for addin: {}", local_addin.name);
-                match helpers::install_addin(registry_addin, local_addin) {
-                    Ok(update_notification) => update_notifications.push(update_notification),
-                    Err(e) => {
-                        eprintln!(
-                            "Failed to apply pending update for addin {}: {:?}",
-                            local_addin.name, e
-                        );
-                    }
-                }
+                .find(|addin| addin.path_to_addin_dll_folder == *local_dll_folder)
+            else {
+                return Ok(Outcome::Obsolete("it is no longer installed".into()));
+            };
+            if is_deferred(running_revit, local_addin.revit_version.as_deref()) {
+                return Ok(Outcome::Deferred);
+            }
+            let Some(registry_addin) = registry_addins.find(registry_dll_folder).await? else {
+                return Ok(Outcome::Obsolete("it is no longer in the registry".into()));
+            };
+            let registry_modified = helpers::get_addin_modification_time(&registry_addin)
+                .map_err(|e| format!("{:?}", e))?;
+            let local_modified = helpers::get_addin_modification_time(local_addin)
+                .map_err(|e| format!("{:?}", e))?;
+            if registry_modified <= local_modified {
+                return Ok(Outcome::Obsolete("it is already up to date".into()));
+            }
+            let receipts = local_db.install_receipts_table().get_all().await?;
+            if helpers::is_rolled_back_from(&receipts, &registry_addin, local_addin) {
+                return Ok(Outcome::Obsolete(format!(
+                    "it was rolled back from version {}",
+                    registry_addin.version
+                )));
+            }
+            let version_before = helpers::get_addin_version(local_addin);
+            helpers::back_up_for_rollback(local_db, local_addin).await;
+            let update_notification = helpers::install_addin(&registry_addin, local_addin)
+                .map_err(|e| format!("{:?}", e))?;
+            helpers::record_update_receipt(local_db, &registry_addin, local_addin).await;
+            Ok(Outcome::Applied {
+                notification: Some(update_notification),
+                version_before,
+                version_after: helpers::get_addin_version(&registry_addin),
+            })
+        }
+        PendingOperationKind::Install {
+            registry_dll_folder,
+            for_revit_versions,
+            install_scope,
+        } => {
+            // The versions that are closed are installed now, the running ones keep waiting
+            let (deferred_versions, versions): (Vec<String>, Vec<String>) = for_revit_versions
+                .iter()
+                .cloned()
+                .partition(|version| is_deferred(running_revit, Some(version)));
+            if versions.is_empty() {
+                return Ok(Outcome::Deferred);
+            }
+            let Some(registry_addin) = registry_addins.find(registry_dll_folder).await? else {
+                return Ok(Outcome::Obsolete("it is no longer in the registry".into()));
+            };
+            // Checked per version, as the closed versions may have been installed before the rest
+            let installed_versions = local_addins.installed_revit_versions(&registry_addin)?;
+            let versions: Vec<String> = versions
+                .into_iter()
+                .filter(|version| !installed_versions.contains(version))
+                .collect();
+            if versions.is_empty() {
+                return Ok(if deferred_versions.is_empty() {
+                    Outcome::Obsolete("it is already installed".into())
+                } else {
+                    Outcome::Deferred
+                });
             }
+            let version_after = helpers::get_addin_version(&registry_addin);
+            InstallAddinOperation::new_install(
+                registry_addin,
+                versions,
+                *install_scope,
+                app.clone(),
+                addins_registry.clone(),
+            )
+            .execute()
+            .await
+            .map_err(|e| e.to_string())?;
+            if deferred_versions.is_empty() {
+                return Ok(Outcome::Applied {
+                    notification: None,
+                    version_before: None,
+                    version_after,
+                });
+            }
+            Ok(Outcome::PartlyApplied {
+                version_after,
+                remaining: PendingOperationKind::Install {
+                    registry_dll_folder: registry_dll_folder.clone(),
+                    for_revit_versions: deferred_versions,
+                    install_scope: *install_scope,
+                },
+            })
+        }
+        PendingOperationKind::Uninstall { target } => {
+            if is_deferred(running_revit, Some(&target.revit_version)) {
+                return Ok(Outcome::Deferred);
+            }
+            let outcome = if uninstall::is_installed(target) {
+                uninstall::remove_target(target).map_err(|e| e.to_string())?;
+                Outcome::Applied {
+                    notification: None,
+                    version_before: None,
+                    version_after: None,
+                }
+            } else {
+                Outcome::Obsolete("it is already uninstalled".into())
+            };
+            local_db
+                .install_receipts_table()
+                .remove(&target.xml_file)
+                .await?;
+            Ok(outcome)
+        }
+    }
+}
+
+/// The registry addins for one batch of pending operations. The registry is only read once an operation that isn't
+/// deferred needs it, and then at most once, as reading it scans the whole share
+struct RegistryAddins<'a> {
+    addins_registry: &'a AsyncAddinsRegistryServiceType,
+    addins: Option<Result<Vec<AddinModel>, String>>,
+}
+
+impl<'a> RegistryAddins<'a> {
+    fn new(addins_registry: &'a AsyncAddinsRegistryServiceType) -> Self {
+        Self {
+            addins_registry,
+            addins: None,
+        }
+    }
+
+    async fn find(&mut self, dll_folder: &str) -> Result<Option<AddinModel>, String> {
+        if self.addins.is_none() {
+            let addins = self
+                .addins_registry
+                .get_addins()
+                .await
+                .map_err(|e| format!("Registry error: {}", e));
+            self.addins = Some(addins);
+        }
+        match self.addins.as_ref() {
+            Some(Ok(addins)) => Ok(addins
+                .iter()
+                .find(|addin| addin.path_to_addin_dll_folder == dll_folder)
+                .cloned()),
+            Some(Err(e)) => Err(e.clone()),
+            None => Ok(None),
         }
     }
-    Ok(update_notifications)
 }
@@ -0,0 +1,109 @@
+use log::{error, info};
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use tokio::sync::Notify;
+
+/// Wakes the update checker up as soon as something in the registry changes
+///
+/// Change notifications don't work on every network share, so the update checker still polls the registry's
+/// change token. The watcher only makes changes show up sooner
+#[derive(Default)]
+pub struct RegistryWatcher {
+    watcher: Option<RecommendedWatcher>,
+    watched_root: Option<PathBuf>,
+    /// The root that couldn't be watched last time, so the failure is only logged once
+    failed_root: Option<PathBuf>,
+    /// When the registry last changed, until the change is taken
+    last_change: Arc<Mutex<Option<Instant>>>,
+    wake: Arc<Notify>,
+}
+
+impl RegistryWatcher {
+    /// Starts watching the registry root, or switches to it if another root was watched.
+    /// If it can't be watched, it is tried again on the next call
+    pub fn watch(&mut self, registry_root: &Path) {
+        if self.watcher.is_some() && self.watched_root.as_deref() == Some(registry_root) {
+            return;
+        }
+        self.watcher = None;
+        self.watched_root = None;
+        match self.create_watcher(registry_root) {
+            Ok(watcher) => {
+                info!("Watching the registry at {}", registry_root.display());
+                self.watcher = Some(watcher);
+                self.watched_root = Some(registry_root.to_path_buf());
+                self.failed_root = None;
+            }
+            Err(e) => {
+                if self.failed_root.as_deref() != Some(registry_root) {
+                    info!(
+                        "Could not watch the registry at {}, polling it instead: {}",
+                        registry_root.display(),
+                        e
+                    );
+                }
+                self.failed_root = Some(registry_root.to_path_buf());
+            }
+        }
+    }
+
+    /// Whether changes to the registry are reported by the watcher
+    pub fn is_active(&self) -> bool {
+        self.watcher.is_some()
+    }
+
+    /// Whether the registry changed since the last call and has been quiet for `debounce` since.
+    /// A change that is still going on, e.g. files being copied, is kept until it settles
+    pub fn take_changed(&self, debounce: Duration) -> bool {
+        let mut last_change = self.last_change.lock().unwrap();
+        match *last_change {
+            Some(changed_at) if changed_at.elapsed() >= debounce => {
+                *last_change = None;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Waits for the registry to change and then to be quiet for `debounce`, so that copying many files counts as
+    /// one change. Every further change restarts the wait
+    pub async fn changed(&self, debounce: Duration) {
+        loop {
+            let last_change = *self.last_change.lock().unwrap();
+            match last_change {
+                None => self.wake.notified().await,
+                Some(changed_at) => {
+                    let quiet_at = tokio::time::Instant::from_std(changed_at + debounce);
+                    tokio::select! {
+                        _ = self.wake.notified() => {}
+                        _ = tokio::time::sleep_until(quiet_at) => return,
+                    }
+                }
+            }
+        }
+    }
+
+    fn create_watcher(&self, registry_root: &Path) -> notify::Result<RecommendedWatcher> {
+        let last_change = self.last_change.clone();
+        let wake = self.wake.clone();
+        let mut watcher =
+            notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+                match event {
+                    // Reading the registry, e.g. while scanning it, is not a change
+                    Ok(event) if matches!(event.kind, EventKind::Access(_)) => {}
+                    Ok(_) => {
+                        *last_change.lock().unwrap() = Some(Instant::now());
+                        wake.notify_one();
+                    }
+                    Err(e) => error!("Registry watcher error: {}", e),
+                }
+            })?;
+        watcher.watch(registry_root, RecursiveMode::Recursive)?;
+        Ok(watcher)
+    }
+}
@@ -0,0 +1,202 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use super::*;
+use crate::services::{
+    config::app_paths::AppPaths,
+    local_addins::{disabling, install_manifest, models::install_scope_model::InstallScope},
+    test_fixtures::publish_registry_addin,
+};
+
+fn temp_paths() -> (Arc<AppPaths>, PathBuf) {
+    let root = std::env::temp_dir().join(format!("addin_update_flow_{}", uuid::Uuid::new_v4()));
+    (Arc::new(AppPaths::rooted_at(&root)), root)
+}
+
+#[test]
+fn test_install_update_uninstall_flow() {
+    let (paths, root) = temp_paths();
+    let local_addins = LocalAddinsService::without_events(paths.clone());
+    let registry_root = paths.registry_root.clone().unwrap();
+    let registry_addin = publish_registry_addin(&registry_root, "v1");
+    let versions = vec!["2025".to_string(), "2026".to_string()];
+
+    // Install
+    local_addins
+        .install_addin(&registry_addin, &versions, InstallScope::User)
+        .unwrap();
+    let installed = local_addins.get_local_addins().unwrap();
+    assert_eq!(installed.len(), 2);
+    assert!(installed
+        .iter()
+        .all(|addin| addin.install_scope == Some(InstallScope::User)));
+    assert!(installed
+        .iter()
+        .all(|addin| Path::new(&addin.path_to_addin_dll_folder).starts_with(&root)));
+    assert!(local_addins
+        .is_addin_installed_locally_model(&registry_addin)
+        .unwrap());
+    assert!(AddinUpdateChecker::detect_addins_needing_update(
+        std::slice::from_ref(&registry_addin),
+        &installed
+    )
+    .unwrap()
+    .is_empty());
+
+    // Update: publish a newer build to the registry
+    let registry_addin = publish_registry_addin(&registry_root, "v2");
+    fs::File::options()
+        .write(true)
+        .open(Path::new(&registry_addin.path_to_addin_dll_folder).join("MyAddin.dll"))
+        .unwrap()
+        .set_modified(SystemTime::now() + Duration::from_secs(60))
+        .unwrap();
+    let needing_update = AddinUpdateChecker::detect_addins_needing_update(
+        std::slice::from_ref(&registry_addin),
+        &installed,
+    )
+    .unwrap();
+    assert_eq!(needing_update.len(), 2);
+    for addin_needing_update in &needing_update {
+        helpers::install_addin(
+            &addin_needing_update.registry_addin,
+            &addin_needing_update.local_addin,
+        )
+        .unwrap();
+    }
+    for addin in local_addins.get_local_addins().unwrap() {
+        let dll = Path::new(&addin.path_to_addin_dll_folder).join("MyAddin.dll");
+        assert_eq!(fs::read_to_string(dll).unwrap(), "v2");
+    }
+
+    // Uninstall
+    local_addins
+        .uninstall_addin(&registry_addin, &versions, InstallScope::User)
+        .unwrap();
+    assert!(local_addins.get_local_addins().unwrap().is_empty());
+    assert!(registry_root.join("Tools").join("MyAddin.addin").exists());
+
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn test_scopes_are_installed_separately() {
+    let (paths, root) = temp_paths();
+    let local_addins = LocalAddinsService::without_events(paths.clone());
+    let registry_addin = publish_registry_addin(paths.registry_root.as_ref().unwrap(), "v1");
+
+    local_addins
+        .install_addin(
+            &registry_addin,
+            &["2025".to_string()],
+            InstallScope::Machine,
+        )
+        .unwrap();
+    assert!(paths
+        .machine_addins_root
+        .as_ref()
+        .unwrap()
+        .join("2025")
+        .join("MyAddin.addin")
+        .exists());
+    assert!(local_addins
+        .get_revit_versions(InstallScope::User)
+        .unwrap()
+        .is_empty());
+
+    local_addins
+        .uninstall_addin(&registry_addin, &["2025".to_string()], InstallScope::User)
+        .unwrap();
+    assert_eq!(local_addins.get_local_addins().unwrap().len(), 1);
+
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn test_partly_installed_addin_still_misses_the_other_versions() {
+    let (paths, root) = temp_paths();
+    let local_addins = LocalAddinsService::without_events(paths.clone());
+    let registry_addin = publish_registry_addin(paths.registry_root.as_ref().unwrap(), "v1");
+
+    // 2026 was running, so only 2025 was installed
+    local_addins
+        .install_addin(&registry_addin, &["2025".to_string()], InstallScope::User)
+        .unwrap();
+    assert!(local_addins
+        .is_addin_installed_locally_model(&registry_addin)
+        .unwrap());
+    assert_eq!(
+        local_addins
+            .installed_revit_versions(&registry_addin)
+            .unwrap(),
+        vec!["2025".to_string()]
+    );
+
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn test_update_removes_stale_files_but_keeps_addin_files() {
+    let (paths, root) = temp_paths();
+    let local_addins = LocalAddinsService::without_events(paths.clone());
+    let registry_root = paths.registry_root.clone().unwrap();
+    let registry_addin = publish_registry_addin(&registry_root, "v1");
+    let registry_dll_folder = Path::new(&registry_addin.path_to_addin_dll_folder);
+    fs::write(registry_dll_folder.join("Legacy.dll"), "legacy").unwrap();
+
+    local_addins
+        .install_addin(&registry_addin, &["2025".to_string()], InstallScope::User)
+        .unwrap();
+    let local_addin = local_addins.get_local_addins().unwrap().remove(0);
+    let local_dll_folder = Path::new(&local_addin.path_to_addin_dll_folder);
+    // Written by the addin itself, not by us
+    fs::write(local_dll_folder.join("settings.json"), "{}").unwrap();
+
+    // The next build no longer ships Legacy.dll
+    fs::remove_file(registry_dll_folder.join("Legacy.dll")).unwrap();
+    helpers::install_addin(&registry_addin, &local_addin).unwrap();
+
+    assert!(!local_dll_folder.join("Legacy.dll").exists());
+    assert!(local_dll_folder.join("MyAddin.dll").exists());
+    assert!(local_dll_folder.join("settings.json").exists());
+    let manifest = install_manifest::read_manifest(local_dll_folder)
+        .unwrap()
+        .unwrap();
+    assert_eq!(manifest.files, vec!["MyAddin.dll"]);
+
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn test_update_keeps_disabled_addin_disabled() {
+    let (paths, root) = temp_paths();
+    let local_addins = LocalAddinsService::without_events(paths.clone());
+    let registry_root = paths.registry_root.clone().unwrap();
+    let registry_addin = publish_registry_addin(&registry_root, "v1");
+
+    local_addins
+        .install_addin(&registry_addin, &["2025".to_string()], InstallScope::User)
+        .unwrap();
+    let local_addin = local_addins.get_local_addins().unwrap().remove(0);
+    disabling::disable(&local_addin).unwrap();
+    let disabled_addin = local_addins.get_local_addins().unwrap().remove(0);
+    assert!(disabled_addin.is_disabled);
+    assert_eq!(
+        disabled_addin.path_to_addin_dll_folder,
+        local_addin.path_to_addin_dll_folder
+    );
+
+    let registry_addin = publish_registry_addin(&registry_root, "v2");
+    helpers::install_addin(&registry_addin, &disabled_addin).unwrap();
+
+    let updated_addin = local_addins.get_local_addins().unwrap().remove(0);
+    assert!(updated_addin.is_disabled);
+    assert!(!Path::new(&local_addin.path_to_addin_xml_file).exists());
+    let dll = Path::new(&updated_addin.path_to_addin_dll_folder).join("MyAddin.dll");
+    assert_eq!(fs::read_to_string(dll).unwrap(), "v2");
+
+    fs::remove_dir_all(&root).unwrap();
+}
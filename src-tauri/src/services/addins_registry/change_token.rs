@@ -0,0 +1,40 @@
+//! A small file at the registry root that is rewritten whenever the registry changes. Reading it is much
+//! cheaper than scanning the registry, so clients use it to skip the scan when nothing changed
+
+use std::{fs, io, path::Path};
+
+pub const CHANGE_TOKEN_FILE_NAME: &str = ".change-token";
+
+/// Writes a new token. Call it after changing anything in the registry
+pub fn touch(registry_root: &Path) -> io::Result<()> {
+    fs::write(
+        registry_root.join(CHANGE_TOKEN_FILE_NAME),
+        uuid::Uuid::new_v4().to_string(),
+    )
+}
+
+/// The current token, or None if the registry has none (yet) or can't be reached
+pub fn read(registry_root: &Path) -> Option<String> {
+    fs::read_to_string(registry_root.join(CHANGE_TOKEN_FILE_NAME))
+        .ok()
+        .map(|token| token.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_touch_changes_the_token() {
+        let root = std::env::temp_dir().join(format!("change-token-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&root).unwrap();
+        assert_eq!(read(&root), None);
+
+        touch(&root).unwrap();
+        let first = read(&root).unwrap();
+        touch(&root).unwrap();
+        assert_ne!(read(&root).unwrap(), first);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -1,3 +1,4 @@
+pub mod change_token;
 pub mod models;
 pub mod services;
 pub mod tauri_exports;
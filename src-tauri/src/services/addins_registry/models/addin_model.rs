@@ -1,3 +1,7 @@
+use crate::services::local_addins::models::{
+    addin_management_model::AddinManagement, install_scope_model::InstallScope,
+};
+
 #[derive(Clone, serde::Serialize, serde::Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct AddinModel {
@@ -23,6 +27,15 @@ pub struct AddinModel {
     pub revit_version: Option<String>,
     /// Whether the addin is installed locally
     pub is_installed_locally: bool,
+    /// Whether the addin is installed for the current user or for the whole machine. Will only be present if the addin is installed locally
+    #[serde(default)]
+    pub install_scope: Option<InstallScope>,
+    /// Whether the app manages the addin. Will only be present if the addin is installed locally
+    #[serde(default)]
+    pub management: Option<AddinManagement>,
+    /// Whether Revit skips the addin because its .addin file was moved to the disabled folder
+    #[serde(default)]
+    pub is_disabled: bool,
 }
 
 impl AddinModel {
@@ -34,7 +47,7 @@ impl AddinModel {
         dll_folder_path: String,
         xml_addin: &crate::services::addins_registry::models::addin_xml_model::AddIn,
         revit_version: Option<String>,
-        is_installed_locally: bool,
+        install_scope: Option<InstallScope>,
     ) -> Option<Self> {
         xml_addin.addin_id.as_ref()?;
 
@@ -48,8 +61,11 @@ impl AddinModel {
             email: xml_addin.vendor_email.clone().unwrap_or_default(),
             addin_type: xml_addin.addin_type.clone().unwrap_or_default(),
             vendor_description: xml_addin.vendor_description.clone().unwrap_or_default(),
+            is_installed_locally: install_scope.is_some(),
             revit_version,
-            is_installed_locally,
+            install_scope,
+            management: None,
+            is_disabled: false,
         })
     }
 
@@ -59,7 +75,7 @@ impl AddinModel {
         dll_folder_path: String,
         xml_addin: &crate::services::addins_registry::models::addin_xml_model::AddIn,
     ) -> Option<Self> {
-        Self::from_xml_common(xml_file_path, dll_folder_path, xml_addin, None, false)
+        Self::from_xml_common(xml_file_path, dll_folder_path, xml_addin, None, None)
     }
 
     /// Create a new AddinModel from XML data and file paths. Should be used for addins in the user's local addins folder
@@ -68,13 +84,14 @@ impl AddinModel {
         dll_folder_path: String,
         xml_addin: &crate::services::addins_registry::models::addin_xml_model::AddIn,
         revit_version: String,
+        install_scope: InstallScope,
     ) -> Option<Self> {
         Self::from_xml_common(
             xml_file_path,
             dll_folder_path,
             xml_addin,
             Some(revit_version),
-            true,
+            Some(install_scope),
         )
     }
 }
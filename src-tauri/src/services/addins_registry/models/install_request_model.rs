@@ -1,4 +1,7 @@
-use crate::services::addins_registry::models::addin_model::AddinModel;
+use crate::services::{
+    addins_registry::models::addin_model::AddinModel,
+    local_addins::models::install_scope_model::InstallScope,
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -6,4 +9,6 @@ use serde::{Deserialize, Serialize};
 pub struct InstallAddinRequestModel {
     pub addin: AddinModel,
     pub for_revit_versions: Vec<String>,
+    #[serde(default)]
+    pub install_scope: InstallScope,
 }
@@ -11,6 +11,7 @@ use crate::services::{
 /// Recursively search a directory for .addin files
 pub fn search_directory_recursively(
     dir_path: &Path,
+    local_addins_service: &LocalAddinsService,
     addins: &mut Vec<AddinModel>,
 ) -> Result<(), std::io::Error> {
     let entries = fs::read_dir(dir_path)?;
@@ -21,14 +22,14 @@ pub fn search_directory_recursively(
 
         if path.is_dir() {
             // Recursively search subdirectories
-            if let Err(e) = search_directory_recursively(&path, addins) {
+            if let Err(e) = search_directory_recursively(&path, local_addins_service, addins) {
                 warn!("Error searching subdirectory {:?}: {}", path, e);
             }
         } else if path.is_file() {
             // Check if this is a .addin file
             if let Some(extension) = path.extension() {
                 if extension == "addin" {
-                    if let Err(e) = process_addin_file(&path, addins) {
+                    if let Err(e) = process_addin_file(&path, local_addins_service, addins) {
                         warn!("Error processing addin file {:?}: {}", path, e);
                     }
                 }
@@ -42,6 +43,7 @@ pub fn search_directory_recursively(
 /// Process a single .addin file and extract addin information
 pub fn process_addin_file(
     xml_file_path: &Path,
+    local_addins_service: &LocalAddinsService,
     addins: &mut Vec<AddinModel>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     // info!("Processing addin file: {:?}", xml_file_path);
@@ -77,7 +79,7 @@ pub fn process_addin_file(
             dll_folder_path.to_string_lossy().to_string(),
             xml_addin,
         ) {
-            let is_installed = LocalAddinsService::is_addin_installed_locally(
+            let is_installed = local_addins_service.is_addin_installed_locally(
                 &addin_model.name,
                 &addin_model.vendor,
                 &addin_model.addin_type,
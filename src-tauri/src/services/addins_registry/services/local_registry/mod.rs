@@ -1,7 +1,7 @@
 use std::{fs, path::Path, sync::Arc};
 
 use super::*;
-use log::{error, info};
+use log::{error, info, warn};
 mod helpers;
 use helpers::*;
 
@@ -9,40 +9,100 @@ use crate::{
     constants::Fut,
     models::kv_store_value::KvStoreValue,
     services::{
-        addins_registry::{models::addin_model::AddinModel, services::AddinsRegistry},
+        addins_registry::{
+            change_token,
+            models::{addin_model::AddinModel, addin_xml_model::RevitAddIns},
+            services::AddinsRegistry,
+        },
         admin::addin_exporter::models::category_model::CategoryModel,
-        config::keys::LOCAL_ADDIN_REGISTRY_PATH,
-        local_addins::service::LocalAddinsService,
+        config::{app_paths::AppPaths, keys::LOCAL_ADDIN_REGISTRY_PATH},
+        local_addins::{
+            install_activity, models::install_receipt_model::InstallActor,
+            service::LocalAddinsService,
+        },
         local_db::service::LocalDbService,
+        logging::span,
+        registry_mirror::service::RegistryMirrorService,
     },
 };
 
 pub struct LocalAddinsRegistryService {
     registry_location: KvStoreValue<String>,
+    local_db: Arc<LocalDbService>,
+    paths: Arc<AppPaths>,
     local_addins_service: Arc<LocalAddinsService>,
+    registry_mirror: Arc<RegistryMirrorService>,
 }
 
 impl LocalAddinsRegistryService {
     pub fn new(
         local_db: Arc<LocalDbService>,
         local_addins_service: Arc<LocalAddinsService>,
+        paths: Arc<AppPaths>,
+        registry_mirror: Arc<RegistryMirrorService>,
     ) -> Self {
         Self {
             registry_location: KvStoreValue::new_default(
                 LOCAL_ADDIN_REGISTRY_PATH,
                 local_db.clone(),
             ),
+            local_db,
+            paths,
             local_addins_service,
+            registry_mirror,
         }
     }
+
+    /// Lists the registry, or the mirrored snapshot of it if the registry can't be reached
+    pub async fn get_addins_or_snapshot(&self) -> Result<Vec<AddinModel>, GetAddinsError> {
+        match self.get_addins().await {
+            Err(GetAddinsError::RegistryNotFound(registry_path)) => {
+                match self.registry_mirror.snapshot_addins() {
+                    Ok(Some(addins)) => {
+                        warn!(
+                            "Registry {} can't be reached, listing the mirrored snapshot instead",
+                            registry_path
+                        );
+                        Ok(addins)
+                    }
+                    Ok(None) => Err(GetAddinsError::RegistryNotFound(registry_path)),
+                    Err(e) => {
+                        error!("Could not read the registry mirror: {}", e);
+                        Err(GetAddinsError::RegistryNotFound(registry_path))
+                    }
+                }
+            }
+            result => result,
+        }
+    }
+
+    /// Writes a new change token at the registry root, so that clients pick up the change. Errors are only logged
+    pub async fn mark_changed(&self) {
+        match self.registry_path().await {
+            Ok(registry_path) if !registry_path.is_empty() => {
+                if let Err(e) = change_token::touch(Path::new(&registry_path)) {
+                    warn!("Could not update the registry change token: {}", e);
+                }
+            }
+            Ok(_) => {}
+            Err(e) => warn!("Could not update the registry change token: {}", e),
+        }
+    }
+
+    /// The registry root from `AppPaths` if it is set, otherwise the path stored in the config
+    async fn registry_path(&self) -> Result<String, String> {
+        if let Some(registry_root) = &self.paths.registry_root {
+            return Ok(registry_root.to_string_lossy().to_string());
+        }
+        self.registry_location.get_data_updated().await
+    }
 }
 
 impl AddinsRegistry for LocalAddinsRegistryService {
     fn get_addins(&self) -> Fut<Result<Vec<AddinModel>, GetAddinsError>> {
         Box::pin(async move {
             let dir_path = self
-                .registry_location
-                .get_data_updated()
+                .registry_path()
                 .await
                 .map_err(GetAddinsError::LocalDbError)?;
 
@@ -52,7 +112,6 @@ impl AddinsRegistry for LocalAddinsRegistryService {
                 ));
             }
 
-            let mut addins = Vec::new();
             let path = Path::new(&dir_path);
 
             if !path.exists() {
@@ -63,13 +122,20 @@ impl AddinsRegistry for LocalAddinsRegistryService {
                 return Err(GetAddinsError::InvalidPath);
             }
 
-            info!("Searching for addins in: {}", dir_path);
-
-            if let Err(e) = search_directory_recursively(path, &mut addins) {
-                error!("Error searching directory {}: {}", dir_path, e);
+            let addins = span::in_span("registry_scan", &dir_path, async {
+                let mut addins = Vec::new();
+                if let Err(e) =
+                    search_directory_recursively(path, &self.local_addins_service, &mut addins)
+                {
+                    error!("Error searching directory {}: {}", dir_path, e);
+                }
+                info!("Found {} addins in {}", addins.len(), dir_path);
+                addins
+            })
+            .await;
+            if let Err(e) = self.registry_mirror.save_snapshot(&dir_path, &addins) {
+                error!("Could not update the registry mirror: {}", e);
             }
-
-            info!("Found {} addins in {}", addins.len(), dir_path);
             Ok(addins)
         })
     }
@@ -78,11 +144,37 @@ impl AddinsRegistry for LocalAddinsRegistryService {
         &self,
         addin: AddinModel,
         for_revit_versions: Vec<String>,
+        install_scope: InstallScope,
+        installed_by: InstallActor,
     ) -> Fut<Result<(), super::InstallAddinError>> {
         Box::pin(async move {
+            let _installing = install_activity::begin().await;
             self.local_addins_service
-                .install_addin(&addin, &for_revit_versions)
+                .install_addin(&addin, &for_revit_versions, install_scope)
                 .map_err(|e| InstallAddinError::InstallationError(e.to_string()))?;
+
+            // The addin is installed either way, so a missing receipt is only logged
+            let receipts = match self.local_addins_service.build_install_receipts(
+                &addin,
+                &for_revit_versions,
+                install_scope,
+                installed_by,
+            ) {
+                Ok(receipts) => receipts,
+                Err(e) => {
+                    error!("{}", e);
+                    Vec::new()
+                }
+            };
+            let receipts_table = self.local_db.install_receipts_table();
+            for receipt in receipts {
+                if let Err(e) = receipts_table.record(receipt).await {
+                    error!(
+                        "Could not save the install receipt of {}: {}",
+                        addin.name, e
+                    );
+                }
+            }
             Ok(())
         })
     }
@@ -90,8 +182,7 @@ impl AddinsRegistry for LocalAddinsRegistryService {
     fn delist_addin(&self, addin: AddinModel) -> Fut<Result<(), DelistAddinError>> {
         Box::pin(async move {
             let registry_path = self
-                .registry_location
-                .get_data_updated()
+                .registry_path()
                 .await
                 .map_err(DelistAddinError::LocalDbError)?;
 
@@ -110,13 +201,14 @@ impl AddinsRegistry for LocalAddinsRegistryService {
                     } else if entry_path.is_file() {
                         if let Some(ext) = entry_path.extension() {
                             if ext == "addin" {
-                                let addin_info = revitcli::get_addin_file_info_from_file(
-                                    entry_path.to_str().unwrap(),
-                                )
-                                .map_err(|e| {
-                                    DelistAddinError::DirectoryRecursionError(e.to_string())
-                                })?;
-                                if addin_info.addin_id == addin.addin_id {
+                                let addin_file =
+                                    RevitAddIns::from_file(&entry_path).map_err(|e| {
+                                        DelistAddinError::DirectoryRecursionError(e.to_string())
+                                    })?;
+                                let is_same_addin = addin_file.add_in.iter().any(|xml_addin| {
+                                    xml_addin.addin_id.as_deref() == Some(addin.addin_id.as_str())
+                                });
+                                if is_same_addin {
                                     // Remove the .addin file
                                     fs::remove_file(&entry_path).map_err(|e| {
                                         DelistAddinError::DirectoryRecursionError(e.to_string())
@@ -135,7 +227,9 @@ impl AddinsRegistry for LocalAddinsRegistryService {
                 Ok(false)
             }
             let registry_path = Path::new(&registry_path);
-            recurse_dir(registry_path, &addin)?;
+            if recurse_dir(registry_path, &addin)? {
+                self.mark_changed().await;
+            }
             Ok(())
         })
     }
@@ -144,8 +238,7 @@ impl AddinsRegistry for LocalAddinsRegistryService {
         let full_category_path = full_category_path.to_owned();
         Box::pin(async move {
             let registry_path = self
-                .registry_location
-                .get_data_updated()
+                .registry_path()
                 .await
                 .map_err(AddCategoryError::LocalDbError)?;
 
@@ -167,6 +260,7 @@ impl AddinsRegistry for LocalAddinsRegistryService {
             if !category_path.exists() {
                 fs::create_dir_all(category_path).map_err(AddCategoryError::FileError)?;
                 info!("Created category directory: {}", full_category_path);
+                self.mark_changed().await;
             } else if !category_path.is_dir() {
                 return Err(AddCategoryError::InvalidPath);
             }
@@ -178,8 +272,7 @@ impl AddinsRegistry for LocalAddinsRegistryService {
     fn get_categories(&self) -> Fut<Result<Vec<CategoryModel>, GetCategoriesError>> {
         Box::pin(async move {
             let registry_path = self
-                .registry_location
-                .get_data_updated()
+                .registry_path()
                 .await
                 .map_err(GetCategoriesError::LocalDbError)?;
 
@@ -5,6 +5,9 @@ use crate::{
     services::{
         admin::addin_exporter::models::category_model::CategoryModel,
         addins_registry::models::addin_model::AddinModel,
+        local_addins::models::{
+            install_receipt_model::InstallActor, install_scope_model::InstallScope,
+        },
     },
 };
 mod enums;
@@ -20,6 +23,8 @@ pub trait AddinsRegistry {
         &self,
         addin: AddinModel,
         for_revit_versions: Vec<String>,
+        install_scope: InstallScope,
+        installed_by: InstallActor,
     ) -> Fut<Result<(), InstallAddinError>>;
 
     fn delist_addin(&self, addin: AddinModel) -> Fut<Result<(), DelistAddinError>>;
@@ -15,6 +15,9 @@ impl AddinsRegistry for WebAddinsRegistryService {
         &self,
         addin: AddinModel,
         for_revit_versions: Vec<String>,
+        // Not implemented yet, the web registry doesn't install anything to scope or record a receipt for
+        _install_scope: InstallScope,
+        _installed_by: InstallActor,
     ) -> Fut<Result<(), InstallAddinError>> {
         todo!()
     }
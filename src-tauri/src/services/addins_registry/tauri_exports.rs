@@ -1,3 +1,4 @@
+use log::info;
 use std::sync::Arc;
 
 use crate::services::{
@@ -6,16 +7,18 @@ use crate::services::{
         services::{local_registry::LocalAddinsRegistryService, AddinsRegistry},
     },
     admin::addin_exporter::models::category_model::CategoryModel,
+    local_addins::models::install_receipt_model::InstallActor,
 };
 use futures::stream::{FuturesUnordered, StreamExt};
 use tauri::{AppHandle, Emitter, State};
 
+/// Lists the registry, or its offline snapshot if it can't be reached. See `get_registry_mirror_status`
 #[tauri::command]
 pub async fn get_addins(
     addins_registry_service: State<'_, Arc<LocalAddinsRegistryService>>,
 ) -> Result<Vec<AddinModel>, String> {
     addins_registry_service
-        .get_addins()
+        .get_addins_or_snapshot()
         .await
         .map_err(|e| e.to_string())
 }
@@ -35,10 +38,11 @@ pub async fn install_addins(
         futures.push(async move {
             let addin = install_request.addin;
             let for_revit_versions = install_request.for_revit_versions;
+            let install_scope = install_request.install_scope;
             let addin_id = addin.addin_id.clone();
-            println!("Installing {}", &addin.name);
+            info!("Installing {}", &addin.name);
             addins_registry_service
-                .install_addin(addin, for_revit_versions)
+                .install_addin(addin, for_revit_versions, install_scope, InstallActor::User)
                 .await
                 .map_err(|e| e.to_string())?;
             app.emit("addin_installed", addin_id)
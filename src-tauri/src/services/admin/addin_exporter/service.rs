@@ -1,6 +1,6 @@
 use std::path::Path;
 
-use revitcli::{AddinFileInfo, ErrorList};
+use crate::platform::revitcli::{self, AddinFileInfo, ErrorList};
 
 use crate::services::admin::addin_exporter::models::dll_model::DllModel;
 use crate::services::admin::addin_exporter::models::simplified_addin_info_model::SimplifiedAddinInfoModel;
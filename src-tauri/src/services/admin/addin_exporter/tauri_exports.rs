@@ -1,4 +1,10 @@
-use revitcli::ErrorList;
+use log::info;
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::platform::revitcli::ErrorList;
+use crate::services::addins_registry::services::local_registry::LocalAddinsRegistryService;
 
 use crate::services::admin::addin_exporter::models::dll_model::DllModel;
 use crate::services::admin::addin_exporter::models::simplified_addin_info_model::SimplifiedAddinInfoModel;
@@ -7,23 +13,27 @@ use crate::services::admin::addin_exporter::service::AddinExporterService;
 /// Will return an empty error list if the addin is exported successfully
 #[tauri::command]
 pub async fn export_addin(
+    addins_registry_service: State<'_, Arc<LocalAddinsRegistryService>>,
     project_dir: &str,
     addin_file_info: SimplifiedAddinInfoModel,
     extra_dlls: Vec<String>,
     destination_dir: &str,
 ) -> Result<ErrorList, String> {
-    println!("Exporting addin from project directory: {}", project_dir);
+    info!("Exporting addin from project directory: {}", project_dir);
     match AddinExporterService::create_addin_file_for_project(project_dir, addin_file_info)
         .map_err(|e| ErrorList::new_with_error(&e))
     {
         Ok(path) => {
-            println!("Addin file created at: {}", path);
-            Ok(AddinExporterService::export_locally(
+            info!("Addin file created at: {}", path);
+            let errors = AddinExporterService::export_locally(
                 project_dir,
                 &extra_dlls.iter().map(|x| x.as_str()).collect::<Vec<&str>>(),
                 destination_dir,
             )
-            .await)
+            .await;
+            // Addins are usually exported into the registry
+            addins_registry_service.mark_changed().await;
+            Ok(errors)
         }
         Err(e) => Ok(e),
     }
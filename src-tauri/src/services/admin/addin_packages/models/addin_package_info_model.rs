@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::services::local_addins::revit_versions::RevitVersionRange;
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct AddinPackageInfoModel {
@@ -19,4 +21,17 @@ pub struct AddinPackageInfoModel {
     /// addin FOR bases. Even if an addin was related to bases it should not set the discipline package unless it is the default one!
     pub discipline_package: Option<String>,
     pub emoji: Option<String>,
+    /// The oldest Revit version that the addin supports. Leave empty if there is no lower bound
+    #[serde(default)]
+    pub min_revit_version: Option<String>,
+    /// The newest Revit version that the addin supports. Leave empty if there is no upper bound
+    #[serde(default)]
+    pub max_revit_version: Option<String>,
+}
+
+impl AddinPackageInfoModel {
+    /// The Revit versions that the addin declares support for
+    pub fn supported_revit_versions(&self) -> RevitVersionRange {
+        RevitVersionRange::new(self.min_revit_version.clone(), self.max_revit_version.clone())
+    }
 }
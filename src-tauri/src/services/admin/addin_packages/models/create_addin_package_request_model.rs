@@ -14,4 +14,10 @@ pub struct CreateAddinPackageRequestModel {
     pub discipline_package: Option<String>,
     /// Should only be set if the addin is connected to a discipline
     pub emoji: Option<String>,
+    /// The oldest Revit version that the addin supports
+    #[serde(default)]
+    pub min_revit_version: Option<String>,
+    /// The newest Revit version that the addin supports
+    #[serde(default)]
+    pub max_revit_version: Option<String>,
 }
@@ -1,32 +1,38 @@
+use log::warn;
 use serde_json;
 use std::{
     fs::{self},
-    os::windows::process::CommandExt,
     path::{Path, PathBuf},
     sync::Arc,
 };
 
+use crate::platform;
 use crate::services::{
-    addins_registry::models::addin_model::AddinModel,
+    addins_registry::{change_token, models::addin_model::AddinModel},
     admin::addin_packages::models::{AddinPackageInfoModel, CreateAddinPackageRequestModel},
     app_save::service::AppSaveService,
-    config::keys,
+    config::{app_paths::AppPaths, keys},
     local_db::service::LocalDbService,
 };
 
-const CREATE_NO_WINDOW: u32 = 0x08000000;
 pub const JSON_FILE_NAME: &str = "info.json";
 
 pub struct AddinPackagesService {
     app_save_service: Arc<AppSaveService>,
     local_db: Arc<LocalDbService>,
+    paths: Arc<AppPaths>,
 }
 
 impl AddinPackagesService {
-    pub fn new(local_db: Arc<LocalDbService>, app_save_service: Arc<AppSaveService>) -> Self {
+    pub fn new(
+        local_db: Arc<LocalDbService>,
+        app_save_service: Arc<AppSaveService>,
+        paths: Arc<AppPaths>,
+    ) -> Self {
         Self {
             local_db,
             app_save_service,
+            paths,
         }
     }
 
@@ -115,6 +121,8 @@ impl AddinPackagesService {
             display_name: request.display_name.clone(),
             discipline_package: request.discipline_package.clone(),
             emoji: request.emoji.clone(),
+            min_revit_version: request.min_revit_version.clone(),
+            max_revit_version: request.max_revit_version.clone(),
         };
 
         // Write the info.json file
@@ -124,6 +132,10 @@ impl AddinPackagesService {
         fs::write(info_json_path, json_content)
             .map_err(|e| format!("Failed to write info.json: {}", e))?;
 
+        // Let clients know that the package changed
+        if let Err(e) = change_token::touch(&registry_path) {
+            warn!("Could not update the registry change token: {}", e);
+        }
         Ok(())
     }
     pub async fn get_all_addin_packages(&self) -> Result<Vec<AddinPackageInfoModel>, String> {
@@ -145,14 +157,16 @@ impl AddinPackagesService {
     }
     /// The absolute path to where the addin packages are stored
     async fn get_addin_packages_path(&self) -> Result<PathBuf, String> {
-        let registry_path_str = &keys::get_addins_registry_path(self.local_db.clone()).await?;
+        let registry_path_str =
+            &keys::get_addins_registry_path(&self.paths, self.local_db.clone()).await?;
         let registry_path = Path::new(registry_path_str);
         Ok(Path::join(registry_path, "AddinPackages"))
     }
 
     /// Get the addins registry path
     async fn get_addins_registry_path(&self) -> Result<PathBuf, String> {
-        let registry_path_str = keys::get_addins_registry_path(self.local_db.clone()).await?;
+        let registry_path_str =
+            keys::get_addins_registry_path(&self.paths, self.local_db.clone()).await?;
         Ok(PathBuf::from(registry_path_str))
     }
 
@@ -161,7 +175,15 @@ impl AddinPackagesService {
         addin: &AddinModel,
     ) -> Result<Option<AddinPackageInfoModel>, String> {
         let all_packages = self.get_all_addin_packages().await?;
-        let package = all_packages.iter().find(|p| {
+        Ok(Self::find_package_for_registry_addin(&all_packages, addin).cloned())
+    }
+
+    /// Finds the package that belongs to the registry addin by comparing the name of the addin's DLL folder
+    pub fn find_package_for_registry_addin<'a>(
+        packages: &'a [AddinPackageInfoModel],
+        addin: &AddinModel,
+    ) -> Option<&'a AddinPackageInfoModel> {
+        packages.iter().find(|p| {
             let package_addin_name = p
                 .relative_path_to_addin
                 .split('/')
@@ -175,11 +197,7 @@ impl AddinPackagesService {
 
             // println!("Comparing {} to {}", package_addin_name, addin_name);
             package_addin_name == addin_name
-        });
-        if let Some(package) = package {
-            return Ok(Some(package.clone()));
-        }
-        Ok(None)
+        })
     }
 
     pub async fn load_image_data_for_package(
@@ -245,19 +263,7 @@ impl AddinPackagesService {
             .map_err(|e| format!("Failed to copy help file: {}", e))?;
 
         // Open the file with the default application (usually Microsoft Word for .docx files)
-        // Use 'cmd /c start' to leverage Windows file associations
-        let status = std::process::Command::new("cmd")
-            .creation_flags(CREATE_NO_WINDOW)
-            .args(["/c", "start", "", &dest_path.to_string_lossy()])
-            .status()
-            .map_err(|e| format!("Failed to open file: {}", e))?;
-
-        if !status.success() {
-            return Err(
-                "Failed to open file. Please ensure you have Microsoft Word installed.".to_string(),
-            );
-        }
-
-        Ok(())
+        platform::open_path(&dest_path)
+            .map_err(|e| format!("{}. Please ensure you have Microsoft Word installed.", e))
     }
 }
@@ -24,7 +24,7 @@ impl AddinPermissionsService {
         user_email: String,
         user_discipline: String,
     ) -> Result<UserModel, String> {
-        let table = self.get_table();
+        let table = self.get_table().await?;
         let user = table
             .create_user(user_email, user_discipline)
             .await
@@ -36,7 +36,7 @@ impl AddinPermissionsService {
     }
 
     pub async fn get_user(&self, user_email: String) -> Result<Option<UserModel>, String> {
-        let table = self.get_table();
+        let table = self.get_table().await?;
         let user = table
             .get_user(user_email)
             .await
@@ -55,16 +55,14 @@ impl AddinPermissionsService {
         user_email: String,
         addin_paths: Vec<String>,
     ) -> Result<(), String> {
-        let table = self.get_table();
+        let table = self.get_table().await?;
         table
             .set_allowed_addin_paths(user_email, addin_paths)
             .await
             .map_err(|e| e.to_string())
     }
 
-
-
-    fn get_table(&self) -> &UserAddinsTable {
-        self.local_stats.stats_db.user_addins_table()
+    async fn get_table(&self) -> Result<UserAddinsTable, String> {
+        self.local_stats.stats_db.user_addins_table().await
     }
 }
@@ -1,3 +1,4 @@
+use log::info;
 use std::sync::Arc;
 
 use tauri::State;
@@ -31,13 +32,13 @@ pub async fn register_user(
     };
 
     // Check if user exists in user stats table
-    let user_stats_table = user_stats.stats_db.user_stats_table();
+    let user_stats_table = user_stats.stats_db.user_stats_table().await?;
     let existing_user_stats = user_stats_table.get_user(user_email.clone()).await;
 
     match existing_user_stats {
         Ok(Some(_)) => {
             // User already exists in user stats table
-            println!("User {} already exists in user stats table", user_email);
+            info!("User {} already exists in user stats table", user_email);
         }
         Ok(None) => {
             // User doesn't exist in user stats table, create them
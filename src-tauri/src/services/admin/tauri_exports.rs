@@ -43,8 +43,8 @@ pub async fn unregister_user(
 ) -> Result<(), String> {
     // Ensure that the user trying to unregister the user is an super adim:
     if admin_service.is_super_admin().await {
-        let user_stats_table = user_stats.stats_db.user_stats_table();
-        let user_addins_table = user_stats.stats_db.user_addins_table();
+        let user_stats_table = user_stats.stats_db.user_stats_table().await?;
+        let user_addins_table = user_stats.stats_db.user_addins_table().await?;
 
         user_stats_table.delete_user(&user_email).await?;
         user_addins_table.delete_user(&user_email).await?;
@@ -0,0 +1,36 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+/// Where the service container is in its startup
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "camelCase")]
+pub enum AppReadinessModel {
+    /// The services are still being built, so most commands are not available yet
+    Initializing,
+    Ready,
+    /// Every command is available, but some features won't work. For example, the stats database is offline
+    Degraded {
+        reasons: Vec<String>,
+    },
+    /// A service that everything else depends on could not be initialized
+    Failed {
+        error: String,
+    },
+}
+
+/// Returned to commands that can't run because the services are not ready
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum NotReadyError {
+    /// The services were still initializing when the wait timed out
+    Initializing,
+    Failed {
+        error: String,
+    },
+}
+impl Display for NotReadyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
@@ -0,0 +1,2 @@
+pub mod app_readiness_model;
+pub use app_readiness_model::*;
@@ -0,0 +1,127 @@
+use log::{error, warn};
+use std::time::Duration;
+
+use tauri::{AppHandle, Emitter};
+use tokio::sync::watch;
+
+use crate::services::app_readiness::models::{AppReadinessModel, NotReadyError};
+
+pub const APP_READINESS_CHANGED_EVENT: &str = "app_readiness_changed";
+
+/// Tracks the startup of the service container, so that commands and the UI can wait for it
+///
+/// This is managed before any other service is built, so it is always available
+pub struct AppReadinessService {
+    /// Used to emit `APP_READINESS_CHANGED_EVENT`. None when running without a UI, e.g. in tests
+    app_handle: Option<AppHandle>,
+    state: watch::Sender<AppReadinessModel>,
+}
+
+impl AppReadinessService {
+    pub fn new(app_handle: AppHandle) -> Self {
+        Self {
+            app_handle: Some(app_handle),
+            state: watch::channel(AppReadinessModel::Initializing).0,
+        }
+    }
+
+    /// Creates a service that doesn't emit any events
+    #[cfg(test)]
+    pub fn without_events() -> Self {
+        Self {
+            app_handle: None,
+            state: watch::channel(AppReadinessModel::Initializing).0,
+        }
+    }
+
+    pub fn state(&self) -> AppReadinessModel {
+        self.state.borrow().clone()
+    }
+
+    /// Every service has been built. If there are any reasons, the app is degraded instead of ready.
+    /// Called again when the reasons are only found later
+    pub fn mark_ready(&self, degraded_reasons: Vec<String>) {
+        if degraded_reasons.is_empty() {
+            self.set_state(AppReadinessModel::Ready);
+        } else {
+            self.set_state(AppReadinessModel::Degraded {
+                reasons: degraded_reasons,
+            });
+        }
+    }
+
+    pub fn mark_failed(&self, error: String) {
+        error!("The app failed to initialize: {}", error);
+        self.set_state(AppReadinessModel::Failed { error });
+    }
+
+    /// Waits until the app is no longer initializing.
+    ///
+    /// Returns the ready or degraded state, or an error if the app failed or is still initializing after `timeout`
+    pub async fn wait_until_ready(
+        &self,
+        timeout: Duration,
+    ) -> Result<AppReadinessModel, NotReadyError> {
+        let mut receiver = self.state.subscribe();
+        let state = tokio::time::timeout(
+            timeout,
+            receiver.wait_for(|state| *state != AppReadinessModel::Initializing),
+        )
+        .await
+        .map_err(|_| NotReadyError::Initializing)?
+        .map_err(|_| NotReadyError::Initializing)?
+        .clone();
+        match state {
+            AppReadinessModel::Failed { error } => Err(NotReadyError::Failed { error }),
+            state => Ok(state),
+        }
+    }
+
+    fn set_state(&self, state: AppReadinessModel) {
+        self.state.send_replace(state.clone());
+        let Some(app_handle) = &self.app_handle else {
+            return;
+        };
+        if let Err(e) = app_handle.emit(APP_READINESS_CHANGED_EVENT, state) {
+            warn!("Error emitting the app readiness event: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_waits_until_ready() {
+        let readiness = std::sync::Arc::new(AppReadinessService::without_events());
+        assert_eq!(
+            readiness.wait_until_ready(Duration::from_millis(10)).await,
+            Err(NotReadyError::Initializing)
+        );
+
+        let waiter = {
+            let readiness = readiness.clone();
+            tokio::spawn(async move { readiness.wait_until_ready(Duration::from_secs(5)).await })
+        };
+        readiness.mark_ready(vec!["The stats database is offline".to_string()]);
+        assert_eq!(
+            waiter.await.unwrap(),
+            Ok(AppReadinessModel::Degraded {
+                reasons: vec!["The stats database is offline".to_string()]
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failure_is_returned_as_not_ready() {
+        let readiness = AppReadinessService::without_events();
+        readiness.mark_failed("Could not open the local database".to_string());
+        assert_eq!(
+            readiness.wait_until_ready(Duration::from_secs(5)).await,
+            Err(NotReadyError::Failed {
+                error: "Could not open the local database".to_string()
+            })
+        );
+    }
+}
@@ -0,0 +1,33 @@
+use std::{sync::Arc, time::Duration};
+
+use tauri::State;
+
+use crate::services::app_readiness::{
+    models::{AppReadinessModel, NotReadyError},
+    service::AppReadinessService,
+};
+
+/// How long `wait_for_app_ready` waits if the frontend doesn't say
+const DEFAULT_READY_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Returns where the service container is in its startup
+#[tauri::command]
+pub async fn get_app_readiness(
+    app_readiness_service: State<'_, Arc<AppReadinessService>>,
+) -> Result<AppReadinessModel, String> {
+    Ok(app_readiness_service.state())
+}
+
+/// Waits until every service is available.
+///
+/// Returns the ready or degraded state, or a `NotReadyError` if the app failed to initialize or the wait timed out
+#[tauri::command]
+pub async fn wait_for_app_ready(
+    app_readiness_service: State<'_, Arc<AppReadinessService>>,
+    timeout_ms: Option<u64>,
+) -> Result<AppReadinessModel, NotReadyError> {
+    let timeout = timeout_ms
+        .map(Duration::from_millis)
+        .unwrap_or(DEFAULT_READY_TIMEOUT);
+    app_readiness_service.wait_until_ready(timeout).await
+}
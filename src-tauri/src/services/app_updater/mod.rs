@@ -0,0 +1,3 @@
+pub mod models;
+pub mod service;
+pub mod tauri_exports;
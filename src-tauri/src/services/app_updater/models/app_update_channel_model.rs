@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+
+/// Which releases of the app are installed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AppUpdateChannel {
+    #[default]
+    Stable,
+    /// Pre-releases, to try changes before everyone gets them
+    Beta,
+}
+
+impl AppUpdateChannel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AppUpdateChannel::Stable => "stable",
+            AppUpdateChannel::Beta => "beta",
+        }
+    }
+
+    /// Where the releases of the channel are published when no endpoint is configured
+    pub fn default_endpoint(&self) -> &'static str {
+        match self {
+            AppUpdateChannel::Stable => {
+                "https://github.com/graysonrie/emaaddinmanager/releases/latest/download/latest.json"
+            }
+            // The "beta" release is replaced by every pre-release
+            AppUpdateChannel::Beta => {
+                "https://github.com/graysonrie/emaaddinmanager/releases/download/beta/latest.json"
+            }
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+
+use crate::services::app_updater::models::AppUpdateChannel;
+
+/// A newer version of the app
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppUpdateInfoModel {
+    pub version: String,
+    pub current_version: String,
+    /// The release notes
+    pub notes: Option<String>,
+    /// When it was released, in RFC 3339 format
+    pub date: Option<String>,
+    pub channel: AppUpdateChannel,
+}
+
+/// Where the app is in updating itself
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "stage", rename_all = "camelCase")]
+pub enum AppUpdateStateModel {
+    /// No check has been made yet
+    Idle,
+    Checking,
+    UpToDate,
+    Available {
+        update: AppUpdateInfoModel,
+    },
+    Downloading {
+        update: AppUpdateInfoModel,
+        downloaded: u64,
+        /// None if the server didn't say how big the update is
+        total: Option<u64>,
+    },
+    /// Ready to be applied
+    Downloaded {
+        update: AppUpdateInfoModel,
+    },
+    /// The app restarts once no addin is being installed, updated or uninstalled
+    WaitingForInstalls {
+        update: AppUpdateInfoModel,
+    },
+    /// The app restarts once this is done
+    Installing {
+        update: AppUpdateInfoModel,
+    },
+    Failed {
+        error: String,
+        update: Option<AppUpdateInfoModel>,
+    },
+}
@@ -0,0 +1,4 @@
+pub mod app_update_channel_model;
+pub mod app_update_state_model;
+pub use app_update_channel_model::*;
+pub use app_update_state_model::*;
@@ -0,0 +1,289 @@
+use log::{error, info};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use tauri::{AppHandle, Emitter, Url};
+use tauri_plugin_updater::{Update, UpdaterExt};
+use tokio::sync::{watch, Mutex};
+
+use crate::services::{
+    app_updater::models::{AppUpdateChannel, AppUpdateInfoModel, AppUpdateStateModel},
+    config::keys,
+    local_addins::install_activity,
+    local_db::service::LocalDbService,
+};
+
+pub const APP_UPDATE_STATE_CHANGED_EVENT: &str = "app_update_state_changed";
+
+/// How often the download progress is sent at most
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+/// Replaced with the channel in the configured endpoint
+const CHANNEL_PLACEHOLDER: &str = "{{channel}}";
+
+/// How far the newest update has been fetched
+enum StagedUpdate {
+    Nothing,
+    Available {
+        update: Box<Update>,
+        info: AppUpdateInfoModel,
+    },
+    Downloaded {
+        update: Box<Update>,
+        info: AppUpdateInfoModel,
+        bytes: Vec<u8>,
+    },
+}
+
+/// Updates the app itself from the configured channel and endpoint
+///
+/// Checking, downloading and applying are separate steps, so that the update can be fetched in the background and
+/// applied when the user agrees. Applying waits until no addin is being installed, updated or uninstalled, because
+/// the app restarts right after
+pub struct AppUpdaterService {
+    app_handle: AppHandle,
+    local_db: Arc<LocalDbService>,
+    state: watch::Sender<AppUpdateStateModel>,
+    /// Held for the whole of a check or download, so that two of them don't run at once. Applying takes the download
+    /// out instead, so that waiting for the addin operations doesn't hold it
+    staged: Mutex<StagedUpdate>,
+}
+
+impl AppUpdaterService {
+    pub fn new(app_handle: AppHandle, local_db: Arc<LocalDbService>) -> Self {
+        Self {
+            app_handle,
+            local_db,
+            state: watch::channel(AppUpdateStateModel::Idle).0,
+            staged: Mutex::new(StagedUpdate::Nothing),
+        }
+    }
+
+    pub fn state(&self) -> AppUpdateStateModel {
+        self.state.borrow().clone()
+    }
+
+    /// Checks for an update without waiting for the result. The frontend is told with `APP_UPDATE_STATE_CHANGED_EVENT`
+    pub fn check_in_background(self: &Arc<Self>) {
+        let service = Arc::clone(self);
+        tauri::async_runtime::spawn(async move {
+            // A failed check is logged and sent as the state
+            let _ = service.check().await;
+        });
+    }
+
+    /// Looks for a newer version on the configured channel. None if the app is up to date
+    pub async fn check(&self) -> Result<Option<AppUpdateInfoModel>, String> {
+        let mut staged = self.staged.lock().await;
+        let channel = keys::get_app_update_channel(self.local_db.clone()).await?;
+        self.set_state(AppUpdateStateModel::Checking);
+        let update = match self.find_update(channel).await {
+            Ok(update) => update,
+            Err(e) => {
+                let e = format!("Could not check for app updates: {}", e);
+                self.fail(e.clone(), None);
+                return Err(e);
+            }
+        };
+        let Some(update) = update else {
+            *staged = StagedUpdate::Nothing;
+            self.set_state(AppUpdateStateModel::UpToDate);
+            return Ok(None);
+        };
+
+        let info = AppUpdateInfoModel {
+            version: update.version.clone(),
+            current_version: update.current_version.clone(),
+            notes: update.body.clone(),
+            date: update
+                .raw_json
+                .get("pub_date")
+                .and_then(|date| date.as_str())
+                .map(str::to_string),
+            channel,
+        };
+        // A second check doesn't throw away the download of the same version
+        let is_downloaded = matches!(
+            &*staged,
+            StagedUpdate::Downloaded { info: downloaded, .. } if *downloaded == info
+        );
+        if is_downloaded {
+            self.set_state(AppUpdateStateModel::Downloaded {
+                update: info.clone(),
+            });
+        } else {
+            info!(
+                "App update {} is available on the {} channel",
+                info.version,
+                channel.as_str()
+            );
+            *staged = StagedUpdate::Available {
+                update: Box::new(update),
+                info: info.clone(),
+            };
+            self.set_state(AppUpdateStateModel::Available {
+                update: info.clone(),
+            });
+        }
+        Ok(Some(info))
+    }
+
+    /// Downloads the update found by the last check. The progress is sent with `APP_UPDATE_STATE_CHANGED_EVENT`
+    pub async fn download(&self) -> Result<(), String> {
+        let mut staged = self.staged.lock().await;
+        let (update, info) = match &*staged {
+            StagedUpdate::Nothing => {
+                return Err("There is no app update to download, check for one first".to_string())
+            }
+            StagedUpdate::Downloaded { .. } => return Ok(()),
+            StagedUpdate::Available { update, info } => (update.clone(), info.clone()),
+        };
+
+        self.set_state(AppUpdateStateModel::Downloading {
+            update: info.clone(),
+            downloaded: 0,
+            total: None,
+        });
+        let mut downloaded = 0;
+        let mut last_progress = Instant::now();
+        let result = update
+            .download(
+                |chunk_length, total| {
+                    downloaded += chunk_length as u64;
+                    if last_progress.elapsed() >= PROGRESS_INTERVAL {
+                        last_progress = Instant::now();
+                        self.set_state(AppUpdateStateModel::Downloading {
+                            update: info.clone(),
+                            downloaded,
+                            total,
+                        });
+                    }
+                },
+                || {},
+            )
+            .await;
+        match result {
+            Ok(bytes) => {
+                info!("Downloaded app update {}", info.version);
+                self.set_state(AppUpdateStateModel::Downloaded {
+                    update: info.clone(),
+                });
+                *staged = StagedUpdate::Downloaded {
+                    update,
+                    info,
+                    bytes,
+                };
+                Ok(())
+            }
+            Err(e) => {
+                let e = format!("Could not download app update {}: {}", info.version, e);
+                self.fail(e.clone(), Some(info));
+                Err(e)
+            }
+        }
+    }
+
+    /// Installs the downloaded update and restarts the app
+    ///
+    /// Waits until no addin is being installed, updated or uninstalled, and keeps new ones from starting meanwhile.
+    /// If installing fails, the download is kept so that it can be applied again
+    pub async fn apply(&self) -> Result<(), String> {
+        let (update, info, bytes) = {
+            let mut staged = self.staged.lock().await;
+            match std::mem::replace(&mut *staged, StagedUpdate::Nothing) {
+                StagedUpdate::Downloaded {
+                    update,
+                    info,
+                    bytes,
+                } => (update, info, bytes),
+                not_downloaded => {
+                    *staged = not_downloaded;
+                    return Err("The app update hasn't been downloaded yet".to_string());
+                }
+            }
+        };
+
+        if install_activity::is_running() {
+            info!("Waiting for the addin operations to finish before updating the app");
+            self.set_state(AppUpdateStateModel::WaitingForInstalls {
+                update: info.clone(),
+            });
+        }
+        let _app_updating = install_activity::lock_for_app_update().await;
+        self.set_state(AppUpdateStateModel::Installing {
+            update: info.clone(),
+        });
+        info!("Installing app update {}", info.version);
+        if let Err(e) = update.install(&bytes) {
+            let e = format!("Could not install app update {}: {}", info.version, e);
+            self.fail(e.clone(), Some(info.clone()));
+            *self.staged.lock().await = StagedUpdate::Downloaded {
+                update,
+                info,
+                bytes,
+            };
+            return Err(e);
+        }
+        info!("Restarting to finish the app update");
+        self.app_handle.restart();
+    }
+
+    async fn find_update(&self, channel: AppUpdateChannel) -> Result<Option<Update>, String> {
+        let endpoint = keys::get_app_update_endpoint(self.local_db.clone()).await?;
+        let endpoint = resolve_endpoint(&endpoint, channel)?;
+        info!("Checking for app updates at {}", endpoint);
+        self.app_handle
+            .updater_builder()
+            .endpoints(vec![endpoint])
+            .map_err(|e| e.to_string())?
+            .build()
+            .map_err(|e| e.to_string())?
+            .check()
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    fn fail(&self, error: String, update: Option<AppUpdateInfoModel>) {
+        error!("{}", error);
+        self.set_state(AppUpdateStateModel::Failed { error, update });
+    }
+
+    fn set_state(&self, state: AppUpdateStateModel) {
+        self.state.send_replace(state.clone());
+        if let Err(e) = self.app_handle.emit(APP_UPDATE_STATE_CHANGED_EVENT, state) {
+            error!("Could not emit the app update state: {}", e);
+        }
+    }
+}
+
+/// The configured endpoint with the channel filled in, or the channel's default endpoint if none is configured
+fn resolve_endpoint(configured: &str, channel: AppUpdateChannel) -> Result<Url, String> {
+    let endpoint = match configured.trim() {
+        "" => channel.default_endpoint().to_string(),
+        configured => configured.replace(CHANNEL_PLACEHOLDER, channel.as_str()),
+    };
+    Url::parse(&endpoint).map_err(|e| format!("Invalid app update endpoint {}: {}", endpoint, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_endpoint_fills_in_the_channel() {
+        let endpoint = resolve_endpoint(
+            " http://updates.local/{{channel}}/latest.json ",
+            AppUpdateChannel::Beta,
+        )
+        .unwrap();
+        assert_eq!(endpoint.as_str(), "http://updates.local/beta/latest.json");
+
+        let default = resolve_endpoint("", AppUpdateChannel::Stable).unwrap();
+        assert_eq!(
+            default.as_str(),
+            AppUpdateChannel::Stable.default_endpoint()
+        );
+        assert!(resolve_endpoint("not a url", AppUpdateChannel::Stable).is_err());
+    }
+}
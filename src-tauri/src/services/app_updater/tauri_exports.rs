@@ -0,0 +1,40 @@
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::services::app_updater::{
+    models::{AppUpdateInfoModel, AppUpdateStateModel},
+    service::AppUpdaterService,
+};
+
+/// Where the app is in updating itself. Changes are also sent with the "app_update_state_changed" event
+#[tauri::command]
+pub async fn get_app_update_state(
+    app_updater_service: State<'_, Arc<AppUpdaterService>>,
+) -> Result<AppUpdateStateModel, String> {
+    Ok(app_updater_service.state())
+}
+
+/// Looks for a newer version of the app on the configured channel. None if the app is up to date
+#[tauri::command]
+pub async fn check_for_app_update(
+    app_updater_service: State<'_, Arc<AppUpdaterService>>,
+) -> Result<Option<AppUpdateInfoModel>, String> {
+    app_updater_service.check().await
+}
+
+/// Downloads the update found by the last check
+#[tauri::command]
+pub async fn download_app_update(
+    app_updater_service: State<'_, Arc<AppUpdaterService>>,
+) -> Result<(), String> {
+    app_updater_service.download().await
+}
+
+/// Installs the downloaded update and restarts the app, once no addin is being installed, updated or uninstalled
+#[tauri::command]
+pub async fn apply_app_update(
+    app_updater_service: State<'_, Arc<AppUpdaterService>>,
+) -> Result<(), String> {
+    app_updater_service.apply().await
+}
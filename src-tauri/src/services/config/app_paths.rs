@@ -0,0 +1,70 @@
+use std::path::{Path, PathBuf};
+
+use crate::{platform, services::revit_installations::providers::RevitInstallationsProvider};
+
+/// The folders the services read from and write to
+///
+/// The service container builds this once at startup and hands it to every service that touches the file system,
+/// so that none of them compute their own roots. Tests can root everything in a temp directory instead.
+#[derive(Debug, Clone)]
+pub struct AppPaths {
+    /// Typically C:\Users\<username>\AppData\Roaming\Autodesk\Revit\Addins
+    pub user_addins_root: PathBuf,
+    /// Typically C:\ProgramData\Autodesk\Revit\Addins. None if the platform has no machine-wide folder
+    pub machine_addins_root: Option<PathBuf>,
+    /// The Program Files folders. Revit is installed to `<Program Files>\Autodesk\Revit <version>`
+    pub program_files_dirs: Vec<PathBuf>,
+    /// If set, the registry is read from here instead of the path stored in the config
+    pub registry_root: Option<PathBuf>,
+    /// The default folder of the shared stats database, used until another one is configured
+    pub stats_db_dir: PathBuf,
+    /// The app save dir is created below this folder, typically C:\Users\<username>\AppData\Roaming
+    pub app_save_root: PathBuf,
+}
+
+impl AppPaths {
+    /// The standard locations of the current platform
+    pub fn system() -> Result<Self, String> {
+        let data_roaming = dirs::data_dir().ok_or("Could not get data roaming directory")?;
+        Ok(Self {
+            user_addins_root: revit_addins_root(&data_roaming),
+            machine_addins_root: platform::program_data_dir()
+                .map(|program_data| revit_addins_root(&program_data)),
+            program_files_dirs: platform::program_files_dirs(),
+            registry_root: None,
+            stats_db_dir: PathBuf::from("S:\\BasesRevitAddinsRegistry"),
+            app_save_root: data_roaming,
+        })
+    }
+
+    /// Mirrors the Windows folder layout below a single root folder
+    #[cfg(test)]
+    pub fn rooted_at(root: &Path) -> Self {
+        Self {
+            user_addins_root: revit_addins_root(&root.join("AppData").join("Roaming")),
+            machine_addins_root: Some(revit_addins_root(&root.join("ProgramData"))),
+            program_files_dirs: vec![root.join("Program Files")],
+            registry_root: Some(root.join("Registry")),
+            stats_db_dir: root.join("Stats"),
+            app_save_root: root.join("AppData").join("Roaming"),
+        }
+    }
+}
+
+fn revit_addins_root(base: &Path) -> PathBuf {
+    base.join("Autodesk").join("Revit").join("Addins")
+}
+
+impl RevitInstallationsProvider for AppPaths {
+    fn program_files_dirs(&self) -> Vec<PathBuf> {
+        self.program_files_dirs.clone()
+    }
+
+    fn user_addins_root(&self) -> Option<PathBuf> {
+        Some(self.user_addins_root.clone())
+    }
+
+    fn machine_addins_root(&self) -> Option<PathBuf> {
+        self.machine_addins_root.clone()
+    }
+}
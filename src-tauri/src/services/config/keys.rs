@@ -2,7 +2,12 @@ use std::sync::Arc;
 
 use crate::{
     models::kv_store_value::KvStoreValue,
-    services::{config::models::AddinToInstallModel, local_db::service::LocalDbService},
+    services::{
+        app_updater::models::AppUpdateChannel,
+        config::{app_paths::AppPaths, models::AddinToInstallModel},
+        local_addins::models::install_scope_model::InstallScope,
+        local_db::service::LocalDbService,
+    },
 };
 
 pub const LOCAL_ADDIN_REGISTRY_PATH: &str = "localAddinRegistryPath";
@@ -13,8 +18,32 @@ pub const USER_DISCIPLINES: &str = "userDisciplines";
 pub const ADDINS_TO_INSTALL: &str = "addinsToInstall";
 // A list of all of the addins to uninstall
 pub const ADDINS_TO_UNINSTALL: &str = "addinsToUninstall";
+// Whether new addins are installed for the current user or for the whole machine
+pub const DEFAULT_INSTALL_SCOPE: &str = "defaultInstallScope";
+// The folder that holds the shared stats database. Empty to use the default location
+pub const STATS_DB_PATH: &str = "statsDbPath";
+// How many minutes the update checker waits at most between two full registry scans, even if nothing changed
+pub const UPDATE_CHECK_INTERVAL_MINUTES: &str = "updateCheckIntervalMinutes";
+pub const DEFAULT_UPDATE_CHECK_INTERVAL_MINUTES: u64 = 30;
+pub const MIN_UPDATE_CHECK_INTERVAL_MINUTES: u64 = 1;
+pub const MAX_UPDATE_CHECK_INTERVAL_MINUTES: u64 = 24 * 60;
+// The level of the messages that are logged, e.g. "info". Applied at startup and whenever it is changed
+pub const LOG_LEVEL: &str = "logLevel";
+pub const DEFAULT_LOG_LEVEL: &str = "info";
+// Which releases of the app itself are installed, "stable" or "beta"
+pub const APP_UPDATE_CHANNEL: &str = "appUpdateChannel";
+// Where the app looks for its own updates, e.g. a manifest on a local server. "{{channel}}" is replaced with the
+// channel. Empty to use the default endpoint of the channel
+pub const APP_UPDATE_ENDPOINT: &str = "appUpdateEndpoint";
 
-pub async fn get_addins_registry_path(local_db: Arc<LocalDbService>) -> Result<String, String> {
+/// Get the addins registry path. `AppPaths::registry_root` takes precedence over the path in the local database
+pub async fn get_addins_registry_path(
+    paths: &AppPaths,
+    local_db: Arc<LocalDbService>,
+) -> Result<String, String> {
+    if let Some(registry_root) = &paths.registry_root {
+        return Ok(registry_root.to_string_lossy().to_string());
+    }
     let path = KvStoreValue::<String>::new_default(LOCAL_ADDIN_REGISTRY_PATH, local_db.clone())
         .get_data_updated()
         .await?;
@@ -66,3 +95,59 @@ pub async fn get_addins_to_uninstall(
     .await?;
     Ok(addins_to_uninstall)
 }
+
+/// Get the scope that new addins are installed in. Defaults to the current user
+pub async fn get_default_install_scope(
+    local_db: Arc<LocalDbService>,
+) -> Result<InstallScope, String> {
+    let install_scope =
+        KvStoreValue::<InstallScope>::new_default(DEFAULT_INSTALL_SCOPE, local_db.clone())
+            .get_data_updated()
+            .await?;
+    Ok(install_scope)
+}
+
+/// Get the folder that holds the shared stats database. Empty if the default location should be used
+pub async fn get_stats_db_path(local_db: Arc<LocalDbService>) -> Result<String, String> {
+    let stats_db_path = KvStoreValue::<String>::new_default(STATS_DB_PATH, local_db.clone())
+        .get_data_updated()
+        .await?;
+    Ok(stats_db_path)
+}
+
+/// Get the channel the app itself is updated from. Defaults to stable
+pub async fn get_app_update_channel(
+    local_db: Arc<LocalDbService>,
+) -> Result<AppUpdateChannel, String> {
+    let channel =
+        KvStoreValue::<AppUpdateChannel>::new_default(APP_UPDATE_CHANNEL, local_db.clone())
+            .get_data_updated()
+            .await?;
+    Ok(channel)
+}
+
+/// Get where the app looks for its own updates. Empty if the channel's default endpoint should be used
+pub async fn get_app_update_endpoint(local_db: Arc<LocalDbService>) -> Result<String, String> {
+    let endpoint = KvStoreValue::<String>::new_default(APP_UPDATE_ENDPOINT, local_db.clone())
+        .get_data_updated()
+        .await?;
+    Ok(endpoint)
+}
+
+/// Get the longest time between two full registry scans, in minutes. Defaults to 30 and is clamped to one minute
+/// to one day
+pub async fn get_update_check_interval_minutes(
+    local_db: Arc<LocalDbService>,
+) -> Result<u64, String> {
+    let interval = KvStoreValue::<u64>::new(
+        DEFAULT_UPDATE_CHECK_INTERVAL_MINUTES,
+        UPDATE_CHECK_INTERVAL_MINUTES,
+        local_db.clone(),
+    )
+    .get_data_updated()
+    .await?;
+    Ok(interval.clamp(
+        MIN_UPDATE_CHECK_INTERVAL_MINUTES,
+        MAX_UPDATE_CHECK_INTERVAL_MINUTES,
+    ))
+}
@@ -1,2 +1,3 @@
+pub mod app_paths;
 pub mod keys;
 pub mod models;
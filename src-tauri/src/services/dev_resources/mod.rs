@@ -5,7 +5,9 @@ use std::{
 };
 
 use crate::services::{
-    config::keys, dev_resources::vs_templates::models::*, local_db::service::LocalDbService,
+    config::{app_paths::AppPaths, keys},
+    dev_resources::vs_templates::models::*,
+    local_db::service::LocalDbService,
 };
 pub mod tauri_exports;
 
@@ -27,11 +29,12 @@ impl std::fmt::Display for TemplateInstallError {
 
 pub struct DevResourcesService {
     local_db: Arc<LocalDbService>,
+    paths: Arc<AppPaths>,
 }
 
 impl DevResourcesService {
-    pub fn new(local_db: Arc<LocalDbService>) -> Self {
-        Self { local_db }
+    pub fn new(local_db: Arc<LocalDbService>, paths: Arc<AppPaths>) -> Self {
+        Self { local_db, paths }
     }
     /// Only gets the templates that are verified to exist
     pub async fn get_visual_studio_templates(&self) -> Result<Vec<VsTemplateModel>, String> {
@@ -125,7 +128,8 @@ impl DevResourcesService {
         Ok(vs_path.join("Templates\\ProjectTemplates"))
     }
     async fn get_app_vstemplates_path(&self) -> Result<PathBuf, String> {
-        let local_db_path = keys::get_addins_registry_path(self.local_db.clone()).await?;
+        let local_db_path =
+            keys::get_addins_registry_path(&self.paths, self.local_db.clone()).await?;
         let local_db_path = Path::new(&local_db_path);
 
         let dev_resources_path = local_db_path.join("DevResources");
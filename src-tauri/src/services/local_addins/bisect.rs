@@ -0,0 +1,190 @@
+use std::{collections::HashSet, path::Path};
+
+use crate::services::{
+    addins_registry::models::addin_model::AddinModel,
+    local_addins::{
+        disabling,
+        models::{
+            addin_management_model::AddinManagement,
+            bisect_session_model::{BisectCandidateModel, BisectSessionModel},
+        },
+    },
+};
+
+/// Starts a session over the enabled managed addins of the Revit version. `local_addins` must be classified
+pub fn start(
+    revit_version: &str,
+    local_addins: &[AddinModel],
+) -> Result<BisectSessionModel, String> {
+    let mut seen = HashSet::new();
+    let candidates: Vec<BisectCandidateModel> = local_addins
+        .iter()
+        .filter(|addin| {
+            addin.revit_version.as_deref() == Some(revit_version)
+                && addin.management == Some(AddinManagement::Managed)
+                && !addin.is_disabled
+        })
+        .map(|addin| BisectCandidateModel {
+            xml_file: enabled_xml_file(addin),
+            name: addin.name.clone(),
+            install_scope: addin.install_scope.unwrap_or_default(),
+        })
+        // An .addin file can declare several addins, but it is enabled and disabled as a whole
+        .filter(|candidate| seen.insert(candidate.xml_file.clone()))
+        .collect();
+    if candidates.len() < 2 {
+        return Err(format!(
+            "Revit {} needs at least two enabled managed addins to narrow down",
+            revit_version
+        ));
+    }
+
+    let mut session = BisectSessionModel {
+        revit_version: revit_version.to_string(),
+        suspects: candidates
+            .iter()
+            .map(|candidate| candidate.xml_file.clone())
+            .collect(),
+        candidates,
+        testing: Vec::new(),
+        steps_taken: 0,
+        culprit: None,
+        started_at: chrono::Utc::now().to_rfc3339(),
+    };
+    next_step(&mut session);
+    Ok(session)
+}
+
+/// Narrows the suspects down to the half that was enabled if the problem was reproduced, or the other half if not
+pub fn answer(session: &mut BisectSessionModel, problem_reproduced: bool) -> Result<(), String> {
+    if session.culprit.is_some() {
+        return Err("The session already found the addin causing the problem".to_string());
+    }
+    if problem_reproduced {
+        session.suspects = session.testing.clone();
+    } else {
+        session
+            .suspects
+            .retain(|suspect| !session.testing.contains(suspect));
+    }
+    session.steps_taken += 1;
+    next_step(session);
+    Ok(())
+}
+
+fn next_step(session: &mut BisectSessionModel) {
+    if session.suspects.len() <= 1 {
+        session.testing.clear();
+        session.culprit = session.suspects.first().and_then(|suspect| {
+            session
+                .candidates
+                .iter()
+                .find(|candidate| &candidate.xml_file == suspect)
+                .cloned()
+        });
+        return;
+    }
+    let half = session.suspects.len() / 2;
+    session.testing = session.suspects[..half].to_vec();
+}
+
+/// Enables the addins under test and disables the other candidates. Once the session is over, every candidate is
+/// enabled again
+pub fn apply(session: &BisectSessionModel, local_addins: &[AddinModel]) -> Result<(), String> {
+    let is_over = session.culprit.is_some();
+    toggle_candidates(session, local_addins, |xml_file| {
+        is_over || session.testing.iter().any(|testing| testing == xml_file)
+    })
+}
+
+/// Enables every candidate again, e.g. when the session is ended early
+pub fn restore(session: &BisectSessionModel, local_addins: &[AddinModel]) -> Result<(), String> {
+    toggle_candidates(session, local_addins, |_| true)
+}
+
+/// Candidates that were uninstalled in the meantime are skipped
+fn toggle_candidates(
+    session: &BisectSessionModel,
+    local_addins: &[AddinModel],
+    should_enable: impl Fn(&str) -> bool,
+) -> Result<(), String> {
+    let mut seen = HashSet::new();
+    for addin in local_addins {
+        if addin.revit_version.as_deref() != Some(session.revit_version.as_str()) {
+            continue;
+        }
+        let xml_file = enabled_xml_file(addin);
+        let is_candidate = session
+            .candidates
+            .iter()
+            .any(|candidate| candidate.xml_file == xml_file);
+        if !is_candidate || !seen.insert(xml_file.clone()) {
+            continue;
+        }
+        let result = if should_enable(&xml_file) {
+            disabling::enable(addin)
+        } else {
+            disabling::disable(addin)
+        };
+        result.map_err(|e| format!("Failed to toggle {}: {}", addin.name, e))?;
+    }
+    Ok(())
+}
+
+fn enabled_xml_file(addin: &AddinModel) -> String {
+    disabling::enabled_xml_path(Path::new(&addin.path_to_addin_xml_file))
+        .to_string_lossy()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::{local_addins::models::install_scope_model::InstallScope, test_fixtures};
+
+    fn managed_addin(name: &str) -> AddinModel {
+        let xml_addin = test_fixtures::xml_addin(name, name);
+        let mut addin = AddinModel::from_local_xml_data(
+            format!("Addins/2025/{}.addin", name),
+            format!("Addins/2025/{}", name),
+            &xml_addin,
+            "2025".to_string(),
+            InstallScope::User,
+        )
+        .unwrap();
+        addin.management = Some(AddinManagement::Managed);
+        addin
+    }
+
+    #[test]
+    fn test_narrows_down_to_the_culprit() {
+        let addins: Vec<AddinModel> = ["A", "B", "C", "D", "E"]
+            .iter()
+            .map(|name| managed_addin(name))
+            .collect();
+        let culprit = "Addins/2025/D.addin".to_string();
+        let mut session = start("2025", &addins).unwrap();
+
+        while session.culprit.is_none() {
+            assert!(!session.testing.is_empty());
+            let problem_reproduced = session.testing.contains(&culprit);
+            answer(&mut session, problem_reproduced).unwrap();
+        }
+
+        assert_eq!(session.culprit.unwrap().xml_file, culprit);
+        assert!(session.steps_taken <= 3);
+        assert!(session.testing.is_empty());
+    }
+
+    #[test]
+    fn test_only_enabled_managed_addins_are_candidates() {
+        let mut foreign = managed_addin("Foreign");
+        foreign.management = Some(AddinManagement::Foreign);
+        let mut disabled = managed_addin("Disabled");
+        disabled.is_disabled = true;
+        let addins = vec![managed_addin("A"), foreign, disabled];
+
+        assert!(start("2025", &addins).is_err());
+        assert!(start("2026", &[managed_addin("A"), managed_addin("B")]).is_err());
+    }
+}
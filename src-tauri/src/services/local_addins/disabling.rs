@@ -0,0 +1,161 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::services::addins_registry::models::addin_model::AddinModel;
+
+/// Revit only loads the .addin files directly in the version folder, so a disabled addin's .addin file is moved
+/// to this folder inside it, e.g. ...\Addins\2025\.disabled\MyAddin.addin. The DLL folder stays where it is
+pub const DISABLED_FOLDER_NAME: &str = ".disabled";
+
+/// Whether the .addin file is in the disabled folder
+pub fn is_disabled_xml_file(xml_file: &Path) -> bool {
+    xml_file
+        .parent()
+        .and_then(Path::file_name)
+        .is_some_and(|name| name == DISABLED_FOLDER_NAME)
+}
+
+/// Where the .addin file goes when the addin is enabled. Returns the path as is if it isn't disabled
+pub fn enabled_xml_path(xml_file: &Path) -> PathBuf {
+    if !is_disabled_xml_file(xml_file) {
+        return xml_file.to_path_buf();
+    }
+    match (
+        xml_file.parent().and_then(Path::parent),
+        xml_file.file_name(),
+    ) {
+        (Some(version_dir), Some(file_name)) => version_dir.join(file_name),
+        _ => xml_file.to_path_buf(),
+    }
+}
+
+/// Where the .addin file goes when the addin is disabled
+pub fn disabled_xml_path(xml_file: &Path) -> Option<PathBuf> {
+    let enabled = enabled_xml_path(xml_file);
+    let version_dir = enabled.parent()?;
+    Some(
+        version_dir
+            .join(DISABLED_FOLDER_NAME)
+            .join(enabled.file_name()?),
+    )
+}
+
+/// Where the .addin file currently is, given where it goes when enabled
+pub fn installed_xml_path(enabled_xml_file: &Path) -> PathBuf {
+    if !enabled_xml_file.exists() {
+        if let Some(disabled) = disabled_xml_path(enabled_xml_file) {
+            if disabled.exists() {
+                return disabled;
+            }
+        }
+    }
+    enabled_xml_file.to_path_buf()
+}
+
+/// Moves the .addin file to the disabled folder, so that Revit skips the addin. Does nothing if it's already disabled
+///
+/// Returns the new path of the .addin file
+pub fn disable(addin: &AddinModel) -> io::Result<PathBuf> {
+    let xml_file = Path::new(&addin.path_to_addin_xml_file);
+    let disabled = disabled_xml_path(xml_file).ok_or_else(|| invalid_path(xml_file))?;
+    if is_disabled_xml_file(xml_file) {
+        return Ok(disabled);
+    }
+    if let Some(disabled_dir) = disabled.parent() {
+        fs::create_dir_all(disabled_dir)?;
+    }
+    fs::rename(xml_file, &disabled)?;
+    Ok(disabled)
+}
+
+/// Moves the .addin file back to the version folder. Does nothing if it's already enabled
+///
+/// If an enabled .addin file was installed again in the meantime, that one is kept and the disabled one is removed
+///
+/// Returns the new path of the .addin file
+pub fn enable(addin: &AddinModel) -> io::Result<PathBuf> {
+    let xml_file = Path::new(&addin.path_to_addin_xml_file);
+    let enabled = enabled_xml_path(xml_file);
+    if !is_disabled_xml_file(xml_file) {
+        return Ok(enabled);
+    }
+    if enabled.exists() {
+        fs::remove_file(xml_file)?;
+    } else {
+        fs::rename(xml_file, &enabled)?;
+    }
+    remove_disabled_folder_if_empty(xml_file)?;
+    Ok(enabled)
+}
+
+/// Removes the disabled copy of the .addin file, if there is one. Used when uninstalling
+pub fn remove_disabled_xml_file(xml_file: &Path) -> io::Result<()> {
+    let Some(disabled) = disabled_xml_path(xml_file) else {
+        return Ok(());
+    };
+    if disabled.exists() {
+        fs::remove_file(&disabled)?;
+        remove_disabled_folder_if_empty(&disabled)?;
+    }
+    Ok(())
+}
+
+fn remove_disabled_folder_if_empty(disabled_xml_file: &Path) -> io::Result<()> {
+    let Some(disabled_dir) = disabled_xml_file.parent() else {
+        return Ok(());
+    };
+    if disabled_dir.is_dir() && fs::read_dir(disabled_dir)?.next().is_none() {
+        fs::remove_dir(disabled_dir)?;
+    }
+    Ok(())
+}
+
+fn invalid_path(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("Invalid path: {}", path.display()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::test_fixtures;
+
+    #[test]
+    fn test_disable_and_enable() {
+        let root = std::env::temp_dir().join(format!("disabling-{}", uuid::Uuid::new_v4()));
+        let version_dir = root.join("2025");
+        fs::create_dir_all(version_dir.join("MyAddin")).unwrap();
+        let xml_file = version_dir.join("MyAddin.addin");
+        fs::write(&xml_file, "<RevitAddIns />").unwrap();
+        fs::write(version_dir.join("MyAddin").join("MyAddin.dll"), "dll").unwrap();
+
+        let xml_addin = test_fixtures::xml_addin("MyAddin", "my-addin");
+        let mut addin = AddinModel::from_xml_data(
+            xml_file.to_string_lossy().to_string(),
+            version_dir.join("MyAddin").to_string_lossy().to_string(),
+            &xml_addin,
+        )
+        .unwrap();
+
+        let disabled = disable(&addin).unwrap();
+        assert!(!xml_file.exists());
+        assert!(disabled.exists());
+        assert!(is_disabled_xml_file(&disabled));
+        assert_eq!(enabled_xml_path(&disabled), xml_file);
+        assert_eq!(installed_xml_path(&xml_file), disabled);
+        // The DLLs stay where they are
+        assert!(version_dir.join("MyAddin").join("MyAddin.dll").exists());
+
+        addin.path_to_addin_xml_file = disabled.to_string_lossy().to_string();
+        let enabled = enable(&addin).unwrap();
+        assert_eq!(enabled, xml_file);
+        assert!(xml_file.exists());
+        assert!(!version_dir.join(DISABLED_FOLDER_NAME).exists());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,128 @@
+//! Counts the operations that change addin files, e.g. installs, updates, uninstalls, repairs and disabling, so that
+//! the app isn't updated and restarted in the middle of one
+
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use tokio::time::sleep;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+/// Stored instead of a count while the app is being updated, so that no operation starts
+const APP_UPDATING: usize = usize::MAX;
+
+static ACTIVITIES: InstallActivities = InstallActivities::new();
+
+/// The count of running operations. The app uses one shared instance through the functions below
+pub struct InstallActivities {
+    running: AtomicUsize,
+}
+
+/// Marks an operation as running until it is dropped
+pub struct InstallActivity<'a>(&'a InstallActivities);
+
+impl Drop for InstallActivity<'_> {
+    fn drop(&mut self) {
+        self.0.running.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Keeps operations from starting until it is dropped
+pub struct AppUpdateLock<'a>(&'a InstallActivities);
+
+impl Drop for AppUpdateLock<'_> {
+    fn drop(&mut self) {
+        self.0.running.store(0, Ordering::SeqCst);
+    }
+}
+
+impl InstallActivities {
+    const fn new() -> Self {
+        Self {
+            running: AtomicUsize::new(0),
+        }
+    }
+
+    /// Marks an operation as running. Waits while the app is being updated. Operations may nest
+    pub async fn begin(&self) -> InstallActivity<'_> {
+        loop {
+            match self.running.load(Ordering::SeqCst) {
+                APP_UPDATING => sleep(POLL_INTERVAL).await,
+                running => {
+                    if self
+                        .running
+                        .compare_exchange(running, running + 1, Ordering::SeqCst, Ordering::SeqCst)
+                        .is_ok()
+                    {
+                        return InstallActivity(self);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Whether an operation is running
+    pub fn is_running(&self) -> bool {
+        !matches!(self.running.load(Ordering::SeqCst), 0 | APP_UPDATING)
+    }
+
+    /// Waits until no operation is running, then keeps new ones from starting until the lock is dropped
+    pub async fn lock_for_app_update(&self) -> AppUpdateLock<'_> {
+        while self
+            .running
+            .compare_exchange(0, APP_UPDATING, Ordering::SeqCst, Ordering::SeqCst)
+            .is_err()
+        {
+            sleep(POLL_INTERVAL).await;
+        }
+        AppUpdateLock(self)
+    }
+}
+
+/// Marks an operation as running. Waits while the app is being updated. Operations may nest
+pub async fn begin() -> InstallActivity<'static> {
+    ACTIVITIES.begin().await
+}
+
+/// Whether an operation is running
+pub fn is_running() -> bool {
+    ACTIVITIES.is_running()
+}
+
+/// Waits until no operation is running, then keeps new ones from starting until the lock is dropped
+pub async fn lock_for_app_update() -> AppUpdateLock<'static> {
+    ACTIVITIES.lock_for_app_update().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_app_update_waits_for_running_operations() {
+        // Its own instance, so operations of other tests running at the same time don't count
+        static ACTIVITIES: InstallActivities = InstallActivities::new();
+        let activity = ACTIVITIES.begin().await;
+        let nested = ACTIVITIES.begin().await;
+        assert!(ACTIVITIES.is_running());
+
+        let locking = tokio::spawn(ACTIVITIES.lock_for_app_update());
+        sleep(POLL_INTERVAL * 2).await;
+        assert!(!locking.is_finished());
+
+        drop(nested);
+        drop(activity);
+        let lock = locking.await.unwrap();
+        assert!(!ACTIVITIES.is_running());
+
+        let starting = tokio::spawn(ACTIVITIES.begin());
+        sleep(POLL_INTERVAL * 2).await;
+        assert!(!starting.is_finished());
+
+        drop(lock);
+        let activity = starting.await.unwrap();
+        assert!(ACTIVITIES.is_running());
+        drop(activity);
+    }
+}
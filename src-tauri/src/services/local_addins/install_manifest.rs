@@ -0,0 +1,140 @@
+use std::{
+    collections::HashSet,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::services::local_addins::models::install_manifest_model::InstallManifestModel;
+
+const MANIFEST_EXTENSION: &str = "manifest.json";
+/// Addins installed before manifests existed don't have one, so their assemblies are assumed to be ours.
+/// Addins don't write assemblies at runtime, so these are safe to remove when they are no longer in the registry
+const LEGACY_OWNED_EXTENSIONS: [&str; 2] = ["dll", "pdb"];
+
+/// The manifest sits next to the DLL folder, e.g. ...\Addins\2025\MyAddin.manifest.json, so that it
+/// doesn't show up as one of the addin's files
+pub fn manifest_path(dll_folder: &Path) -> Option<PathBuf> {
+    let folder_name = dll_folder.file_name()?.to_string_lossy();
+    Some(dll_folder.with_file_name(format!("{}.{}", folder_name, MANIFEST_EXTENSION)))
+}
+
+pub fn read_manifest(dll_folder: &Path) -> io::Result<Option<InstallManifestModel>> {
+    let Some(path) = manifest_path(dll_folder) else {
+        return Ok(None);
+    };
+    if !path.exists() {
+        return Ok(None);
+    }
+    let contents = fs::read_to_string(&path)?;
+    let manifest = serde_json::from_str(&contents)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    Ok(Some(manifest))
+}
+
+pub fn write_manifest(dll_folder: &Path, manifest: &InstallManifestModel) -> io::Result<()> {
+    let path = manifest_path(dll_folder)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "Invalid DLL folder path"))?;
+    let contents = serde_json::to_string_pretty(manifest)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    fs::write(path, contents)
+}
+
+pub fn remove_manifest(dll_folder: &Path) -> io::Result<()> {
+    match manifest_path(dll_folder) {
+        Some(path) if path.exists() => fs::remove_file(path),
+        _ => Ok(()),
+    }
+}
+
+/// Every file below `dir`, relative to it and separated by forward slashes
+pub fn list_files(dir: &Path) -> io::Result<Vec<String>> {
+    fn visit(dir: &Path, prefix: Option<&str>, files: &mut Vec<String>) -> io::Result<()> {
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().to_string();
+            let relative_path = match prefix {
+                Some(prefix) => format!("{}/{}", prefix, name),
+                None => name,
+            };
+            if entry.file_type()?.is_dir() {
+                visit(&entry.path(), Some(&relative_path), files)?;
+            } else {
+                files.push(relative_path);
+            }
+        }
+        Ok(())
+    }
+
+    let mut files = Vec::new();
+    if dir.is_dir() {
+        visit(dir, None, &mut files)?;
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Call after copying `source_dll_folder` over `dll_folder`. Removes the files we installed before that are no
+/// longer in the source, and records the source's files as ours
+///
+/// Returns the files that were removed
+pub fn converge_to_source(source_dll_folder: &Path, dll_folder: &Path) -> io::Result<Vec<String>> {
+    let source_files = list_files(source_dll_folder)?;
+    let source_file_set: HashSet<&String> = source_files.iter().collect();
+
+    let owned_files = match read_manifest(dll_folder)? {
+        Some(manifest) => manifest.files,
+        None => list_files(dll_folder)?
+            .into_iter()
+            .filter(|file| is_legacy_owned(file))
+            .collect(),
+    };
+
+    let mut removed_files = Vec::new();
+    for file in owned_files {
+        if source_file_set.contains(&file) {
+            continue;
+        }
+        let path = dll_folder.join(&file);
+        if path.is_file() {
+            fs::remove_file(&path)?;
+            remove_empty_parents(dll_folder, &path)?;
+            removed_files.push(file);
+        }
+    }
+
+    write_manifest(
+        dll_folder,
+        &InstallManifestModel {
+            files: source_files,
+        },
+    )?;
+    Ok(removed_files)
+}
+
+fn is_legacy_owned(file: &str) -> bool {
+    Path::new(file)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            LEGACY_OWNED_EXTENSIONS
+                .iter()
+                .any(|owned| ext.eq_ignore_ascii_case(owned))
+        })
+}
+
+/// Removes the folders between `path` and `root` that became empty
+pub fn remove_empty_parents(root: &Path, path: &Path) -> io::Result<()> {
+    let mut parent = path.parent();
+    while let Some(dir) = parent {
+        if dir == root || !dir.starts_with(root) {
+            break;
+        }
+        let is_empty = fs::read_dir(dir)?.next().is_none();
+        if !is_empty {
+            break;
+        }
+        fs::remove_dir(dir)?;
+        parent = dir.parent();
+    }
+    Ok(())
+}
@@ -0,0 +1,337 @@
+use std::{
+    collections::HashSet,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use sha2::{Digest, Sha256};
+
+use crate::services::{
+    addins_registry::models::addin_model::AddinModel,
+    local_addins::{
+        disabling, install_manifest,
+        models::{
+            install_manifest_model::InstallManifestModel,
+            install_receipt_model::{InstallActor, InstallReceiptModel, ReceiptFileModel},
+            install_scope_model::InstallScope,
+        },
+    },
+};
+
+/// Before an update, the files of the receipt are copied here, e.g. ...\Addins\2025\.rollback\MyAddin.addin\,
+/// together with the receipt. Revit doesn't look in subfolders for .addin files
+pub const ROLLBACK_FOLDER_NAME: &str = ".rollback";
+const ROLLBACK_RECEIPT_FILE_NAME: &str = "receipt.json";
+
+/// Describes the files that were just installed from `registry_addin` next to `installed_xml_file`
+///
+/// Only the files in the install manifest are included, so call this after the install finished.
+/// A disabled .addin file is hashed where it is, but recorded where it goes when the addin is enabled
+pub fn build_receipt(
+    registry_addin: &AddinModel,
+    installed_xml_file: &Path,
+    revit_version: &str,
+    install_scope: InstallScope,
+    installed_by: InstallActor,
+) -> io::Result<InstallReceiptModel> {
+    let current_xml_file = installed_xml_file;
+    let installed_xml_file = &disabling::enabled_xml_path(current_xml_file);
+    let version_dir = installed_xml_file
+        .parent()
+        .ok_or_else(|| invalid_path(installed_xml_file))?;
+    let dll_folder_name = Path::new(&registry_addin.path_to_addin_dll_folder)
+        .file_name()
+        .ok_or_else(|| invalid_path(Path::new(&registry_addin.path_to_addin_dll_folder)))?;
+    let installed_dll_folder = version_dir.join(dll_folder_name);
+    let xml_file_name = installed_xml_file
+        .file_name()
+        .ok_or_else(|| invalid_path(installed_xml_file))?;
+
+    let mut files = vec![ReceiptFileModel {
+        relative_path: xml_file_name.to_string_lossy().to_string(),
+        sha256: sha256_file(current_xml_file)?,
+    }];
+    let owned_files = install_manifest::read_manifest(&installed_dll_folder)?
+        .map(|manifest| manifest.files)
+        .unwrap_or_default();
+    for owned_file in owned_files {
+        let path = installed_dll_folder.join(&owned_file);
+        files.push(ReceiptFileModel {
+            relative_path: format!("{}/{}", dll_folder_name.to_string_lossy(), owned_file),
+            sha256: sha256_file(&path)?,
+        });
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+    Ok(InstallReceiptModel {
+        installed_xml_file: installed_xml_file.to_string_lossy().to_string(),
+        installed_dll_folder: installed_dll_folder.to_string_lossy().to_string(),
+        source_xml_file: registry_addin.path_to_addin_xml_file.clone(),
+        source_dll_folder: registry_addin.path_to_addin_dll_folder.clone(),
+        addin_id: registry_addin.addin_id.clone(),
+        name: registry_addin.name.clone(),
+        vendor: registry_addin.vendor.clone(),
+        version: registry_addin.version.clone(),
+        revit_version: revit_version.to_string(),
+        install_scope,
+        files,
+        installed_by,
+        installed_at: now.clone(),
+        updated_at: now,
+        rolled_back_from: None,
+    })
+}
+
+/// The receipt for an installed addin that was just updated from `registry_addin`
+pub fn build_update_receipt(
+    registry_addin: &AddinModel,
+    local_addin: &AddinModel,
+) -> io::Result<InstallReceiptModel> {
+    let revit_version = local_addin.revit_version.clone().unwrap_or_default();
+    build_receipt(
+        registry_addin,
+        Path::new(&local_addin.path_to_addin_xml_file),
+        &revit_version,
+        local_addin.install_scope.unwrap_or_default(),
+        InstallActor::AutoUpdater,
+    )
+}
+
+/// Where one of the receipt's files is installed. The .addin file may be in the disabled folder
+pub fn receipt_file_path(receipt: &InstallReceiptModel, file: &ReceiptFileModel) -> PathBuf {
+    let path = Path::new(&receipt.installed_xml_file)
+        .parent()
+        .unwrap_or(Path::new(""))
+        .join(&file.relative_path);
+    if path == Path::new(&receipt.installed_xml_file) {
+        return disabling::installed_xml_path(&path);
+    }
+    path
+}
+
+/// Where the install of the .addin file is backed up before it is updated
+pub fn rollback_dir(installed_xml_file: &Path) -> Option<PathBuf> {
+    let file_name = installed_xml_file.file_name()?;
+    Some(
+        installed_xml_file
+            .parent()?
+            .join(ROLLBACK_FOLDER_NAME)
+            .join(file_name),
+    )
+}
+
+/// Copies the files of the receipt aside, so that the install can be rolled back after it is updated.
+/// Replaces the backup of an earlier version
+pub fn back_up_for_rollback(receipt: &InstallReceiptModel) -> io::Result<()> {
+    let installed_xml_file = Path::new(&receipt.installed_xml_file);
+    let backup_dir =
+        rollback_dir(installed_xml_file).ok_or_else(|| invalid_path(installed_xml_file))?;
+    if backup_dir.exists() {
+        fs::remove_dir_all(&backup_dir)?;
+    }
+    for file in &receipt.files {
+        let backup = backup_dir.join(&file.relative_path);
+        if let Some(parent) = backup.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::copy(receipt_file_path(receipt, file), backup)?;
+    }
+    // Written last, so that an incomplete backup is never rolled back to
+    let contents = serde_json::to_string_pretty(receipt)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    fs::write(backup_dir.join(ROLLBACK_RECEIPT_FILE_NAME), contents)
+}
+
+/// The receipt of the backed up install of the .addin file, if there is one to roll back to
+pub fn read_rollback(installed_xml_file: &Path) -> io::Result<Option<InstallReceiptModel>> {
+    let Some(backup_dir) = rollback_dir(installed_xml_file) else {
+        return Ok(None);
+    };
+    let path = backup_dir.join(ROLLBACK_RECEIPT_FILE_NAME);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let contents = fs::read_to_string(&path)?;
+    let receipt = serde_json::from_str(&contents)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    Ok(Some(receipt))
+}
+
+/// Puts the backed up files back in place of the ones `receipt` lists, after checking them against their receipt.
+/// A disabled addin stays disabled
+///
+/// Returns the receipt of the install that is now in place
+pub fn roll_back(receipt: &InstallReceiptModel) -> io::Result<InstallReceiptModel> {
+    let installed_xml_file = Path::new(&receipt.installed_xml_file);
+    let backup_dir =
+        rollback_dir(installed_xml_file).ok_or_else(|| invalid_path(installed_xml_file))?;
+    let Some(mut previous) = read_rollback(installed_xml_file)? else {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            "There is no earlier version to roll back to",
+        ));
+    };
+    for file in &previous.files {
+        if sha256_file(&backup_dir.join(&file.relative_path))? != file.sha256 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("The backup of {} was changed", file.relative_path),
+            ));
+        }
+    }
+
+    let previous_files: HashSet<&str> = previous
+        .files
+        .iter()
+        .map(|file| file.relative_path.as_str())
+        .collect();
+    let dll_folder = Path::new(&receipt.installed_dll_folder);
+    for file in &receipt.files {
+        let path = receipt_file_path(receipt, file);
+        if !previous_files.contains(file.relative_path.as_str()) && path.is_file() {
+            fs::remove_file(&path)?;
+            install_manifest::remove_empty_parents(dll_folder, &path)?;
+        }
+    }
+    for file in &previous.files {
+        let path = receipt_file_path(&previous, file);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::copy(backup_dir.join(&file.relative_path), path)?;
+    }
+
+    let previous_dll_folder = Path::new(&previous.installed_dll_folder);
+    if previous_dll_folder != dll_folder {
+        install_manifest::remove_manifest(dll_folder)?;
+        let _ = fs::remove_dir(dll_folder);
+    }
+    let dll_folder_prefix = previous_dll_folder
+        .file_name()
+        .map(|name| format!("{}/", name.to_string_lossy()))
+        .unwrap_or_default();
+    install_manifest::write_manifest(
+        previous_dll_folder,
+        &InstallManifestModel {
+            files: previous
+                .files
+                .iter()
+                .filter_map(|file| file.relative_path.strip_prefix(&dll_folder_prefix))
+                .map(str::to_string)
+                .collect(),
+        },
+    )?;
+    fs::remove_dir_all(&backup_dir)?;
+    let _ = fs::remove_dir(backup_dir.parent().unwrap_or(&backup_dir));
+
+    previous.updated_at = chrono::Utc::now().to_rfc3339();
+    previous.rolled_back_from = Some(receipt.version.clone());
+    Ok(previous)
+}
+
+pub fn sha256_file(path: &Path) -> io::Result<String> {
+    let mut file = fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    io::copy(&mut file, &mut hasher)?;
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect())
+}
+
+fn invalid_path(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("Invalid path: {}", path.display()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::test_fixtures::write_addin;
+
+    fn install(version_dir: &Path, registry_addin: &AddinModel, dll_files: &[&str]) {
+        let local_addin = write_addin(version_dir, "unused");
+        let dll_folder = Path::new(&local_addin.path_to_addin_dll_folder);
+        for file in dll_files {
+            fs::copy(
+                Path::new(&registry_addin.path_to_addin_dll_folder).join(file),
+                dll_folder.join(file),
+            )
+            .unwrap();
+        }
+        install_manifest::write_manifest(
+            dll_folder,
+            &InstallManifestModel {
+                files: dll_files.iter().map(|file| file.to_string()).collect(),
+            },
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_roll_back_restores_the_backed_up_install() {
+        let root = std::env::temp_dir().join(format!("receipts-{}", uuid::Uuid::new_v4()));
+        let version_dir = root.join("2025");
+        let xml_file = version_dir.join("MyAddin.addin");
+        let dll_folder = version_dir.join("MyAddin");
+
+        let mut registry_addin = write_addin(&root.join("Registry"), "v1");
+        registry_addin.version = "1.0.0".to_string();
+        install(&version_dir, &registry_addin, &["MyAddin.dll"]);
+        let receipt = build_receipt(
+            &registry_addin,
+            &xml_file,
+            "2025",
+            InstallScope::User,
+            InstallActor::User,
+        )
+        .unwrap();
+        back_up_for_rollback(&receipt).unwrap();
+
+        // The update brings a new file along
+        let mut registry_addin = write_addin(&root.join("Registry"), "v2");
+        registry_addin.version = "2.0.0".to_string();
+        fs::write(root.join("Registry/MyAddin/New.dll"), "new").unwrap();
+        install(&version_dir, &registry_addin, &["MyAddin.dll", "New.dll"]);
+        let updated = build_receipt(
+            &registry_addin,
+            &xml_file,
+            "2025",
+            InstallScope::User,
+            InstallActor::AutoUpdater,
+        )
+        .unwrap();
+
+        // A changed backup is not rolled back to
+        let backup_dll = rollback_dir(&xml_file).unwrap().join("MyAddin/MyAddin.dll");
+        fs::write(&backup_dll, "changed").unwrap();
+        assert_eq!(
+            roll_back(&updated).unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+        fs::write(&backup_dll, "v1").unwrap();
+
+        let rolled_back = roll_back(&updated).unwrap();
+        assert_eq!(rolled_back.version, "1.0.0");
+        assert_eq!(rolled_back.rolled_back_from, Some("2.0.0".to_string()));
+        assert_eq!(
+            fs::read_to_string(dll_folder.join("MyAddin.dll")).unwrap(),
+            "v1"
+        );
+        assert!(!dll_folder.join("New.dll").exists());
+        assert_eq!(
+            install_manifest::read_manifest(&dll_folder)
+                .unwrap()
+                .unwrap()
+                .files,
+            vec!["MyAddin.dll".to_string()]
+        );
+        assert!(read_rollback(&xml_file).unwrap().is_none());
+        assert!(!version_dir.join(ROLLBACK_FOLDER_NAME).exists());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
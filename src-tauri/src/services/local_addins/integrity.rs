@@ -0,0 +1,477 @@
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    services::{
+        addins_registry::models::{addin_model::AddinModel, addin_xml_model::RevitAddIns},
+        local_addins::{
+            disabling, install_receipts, management,
+            models::{
+                addin_management_model::AddinManagement,
+                addin_verification_model::{
+                    AddinRepairModel, AddinVerificationModel, FileDiscrepancyKind,
+                    FileDiscrepancyModel,
+                },
+                install_receipt_model::InstallReceiptModel,
+            },
+        },
+    },
+    utils,
+};
+
+/// The registry addin that a local addin was installed from
+///
+/// Matched by addin ID first. Addins the app installed are then matched by DLL folder name like the update checker
+/// does, e.g. when the registry addin got a new ID. Foreign addins never match, so they are never compared with or
+/// repaired from the registry
+pub fn find_registry_source<'a>(
+    local_addin: &AddinModel,
+    registry_addins: &'a [AddinModel],
+    receipts: &[InstallReceiptModel],
+) -> Option<&'a AddinModel> {
+    registry_addins
+        .iter()
+        .find(|addin| !addin.addin_id.is_empty() && addin.addin_id == local_addin.addin_id)
+        .or_else(|| {
+            if management::classify(local_addin, Some(registry_addins), receipts)
+                != AddinManagement::Managed
+            {
+                return None;
+            }
+            let local_dll_folder = Path::new(&local_addin.path_to_addin_dll_folder).file_name()?;
+            registry_addins.iter().find(|addin| {
+                Path::new(&addin.path_to_addin_dll_folder).file_name() == Some(local_dll_folder)
+            })
+        })
+}
+
+/// Compares the installed files of the addin with the ones in the registry, and checks that its assemblies exist
+pub fn verify_addin(
+    local_addin: &AddinModel,
+    registry_addin: Option<&AddinModel>,
+) -> AddinVerificationModel {
+    let unresolved_assemblies =
+        find_unresolved_assemblies(Path::new(&local_addin.path_to_addin_xml_file))
+            .unwrap_or_default();
+
+    let (discrepancies, error) = match registry_addin {
+        Some(registry_addin) => match find_discrepancies(local_addin, registry_addin) {
+            Ok(discrepancies) => (discrepancies, None),
+            Err(e) => (Vec::new(), Some(e)),
+        },
+        None => (
+            Vec::new(),
+            Some("The addin could not be found in the registry".to_string()),
+        ),
+    };
+
+    AddinVerificationModel {
+        addin: local_addin.clone(),
+        registry_xml_file: registry_addin.map(|addin| addin.path_to_addin_xml_file.clone()),
+        is_healthy: error.is_none() && discrepancies.is_empty() && unresolved_assemblies.is_empty(),
+        discrepancies,
+        unresolved_assemblies,
+        error,
+    }
+}
+
+/// Copies the missing and modified files from the registry again
+///
+/// Extra files are left alone, since they can't be told apart from files that the addin writes itself
+pub fn repair_addin(
+    local_addin: &AddinModel,
+    registry_addin: &AddinModel,
+) -> Result<AddinRepairModel, String> {
+    let version_dir = local_version_dir(local_addin)?;
+    let source_files = registry_files(registry_addin)?;
+    let discrepancies = find_discrepancies(local_addin, registry_addin)?;
+
+    let mut restored_files = Vec::new();
+    for discrepancy in discrepancies {
+        if discrepancy.kind == FileDiscrepancyKind::Extra {
+            continue;
+        }
+        let Some(source_path) = source_files.get(&discrepancy.relative_path) else {
+            continue;
+        };
+        let mut target_path = version_dir.join(&discrepancy.relative_path);
+        // A disabled addin's .addin file is restored to the disabled folder, so it stays disabled
+        if local_addin.is_disabled
+            && target_path
+                == disabling::enabled_xml_path(Path::new(&local_addin.path_to_addin_xml_file))
+        {
+            target_path = PathBuf::from(&local_addin.path_to_addin_xml_file);
+        }
+        if let Some(parent) = target_path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        fs::copy(source_path, &target_path).map_err(|e| {
+            format!(
+                "Failed to restore {} from {}: {}",
+                target_path.display(),
+                source_path.display(),
+                e
+            )
+        })?;
+        restored_files.push(discrepancy.relative_path);
+    }
+
+    Ok(AddinRepairModel {
+        restored_files,
+        verification: verify_addin(local_addin, Some(registry_addin)),
+        error: None,
+    })
+}
+
+/// Compares the installed files of the addin with the hashes in its install receipt
+///
+/// Used when the addin's source can't be found in the registry, e.g. while offline
+pub fn verify_against_receipt(
+    local_addin: &AddinModel,
+    receipt: &InstallReceiptModel,
+) -> AddinVerificationModel {
+    let unresolved_assemblies =
+        find_unresolved_assemblies(Path::new(&local_addin.path_to_addin_xml_file))
+            .unwrap_or_default();
+
+    let (discrepancies, error) = match find_receipt_discrepancies(receipt) {
+        Ok(discrepancies) => (discrepancies, None),
+        Err(e) => (Vec::new(), Some(e)),
+    };
+
+    AddinVerificationModel {
+        addin: local_addin.clone(),
+        registry_xml_file: Some(receipt.source_xml_file.clone()),
+        is_healthy: error.is_none() && discrepancies.is_empty() && unresolved_assemblies.is_empty(),
+        discrepancies,
+        unresolved_assemblies,
+        error,
+    }
+}
+
+fn find_receipt_discrepancies(
+    receipt: &InstallReceiptModel,
+) -> Result<Vec<FileDiscrepancyModel>, String> {
+    let mut discrepancies = Vec::new();
+    for file in &receipt.files {
+        let path = install_receipts::receipt_file_path(receipt, file);
+        let kind = if !path.is_file() {
+            FileDiscrepancyKind::Missing
+        } else {
+            let hash = install_receipts::sha256_file(&path)
+                .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+            if hash == file.sha256 {
+                continue;
+            }
+            FileDiscrepancyKind::Modified
+        };
+        discrepancies.push(FileDiscrepancyModel {
+            relative_path: file.relative_path.clone(),
+            kind,
+        });
+    }
+
+    let dll_folder = Path::new(&receipt.installed_dll_folder);
+    let mut installed_files = BTreeMap::new();
+    if dll_folder.is_dir() {
+        collect_files(dll_folder, &file_name(dll_folder)?, &mut installed_files)
+            .map_err(|e| format!("Failed to read {}: {}", dll_folder.display(), e))?;
+    }
+    for relative_path in installed_files.into_keys() {
+        if !receipt
+            .files
+            .iter()
+            .any(|file| file.relative_path == relative_path)
+        {
+            discrepancies.push(FileDiscrepancyModel {
+                relative_path,
+                kind: FileDiscrepancyKind::Extra,
+            });
+        }
+    }
+    Ok(discrepancies)
+}
+
+fn find_discrepancies(
+    local_addin: &AddinModel,
+    registry_addin: &AddinModel,
+) -> Result<Vec<FileDiscrepancyModel>, String> {
+    let source_files = registry_files(registry_addin)?;
+    let installed_files = installed_files(local_addin, registry_addin)?;
+
+    let mut discrepancies = Vec::new();
+    for (relative_path, source_path) in &source_files {
+        let kind = match installed_files.get(relative_path) {
+            None => FileDiscrepancyKind::Missing,
+            Some(installed_path) => {
+                let is_same = is_same_contents(source_path, installed_path).map_err(|e| {
+                    format!(
+                        "Failed to compare {} with {}: {}",
+                        installed_path.display(),
+                        source_path.display(),
+                        e
+                    )
+                })?;
+                if is_same {
+                    continue;
+                }
+                FileDiscrepancyKind::Modified
+            }
+        };
+        discrepancies.push(FileDiscrepancyModel {
+            relative_path: relative_path.clone(),
+            kind,
+        });
+    }
+    for relative_path in installed_files.keys() {
+        if !source_files.contains_key(relative_path) {
+            discrepancies.push(FileDiscrepancyModel {
+                relative_path: relative_path.clone(),
+                kind: FileDiscrepancyKind::Extra,
+            });
+        }
+    }
+    Ok(discrepancies)
+}
+
+/// The Revit version folder that the local addin is installed in, e.g. ...\Addins\2025
+fn local_version_dir(local_addin: &AddinModel) -> Result<PathBuf, String> {
+    disabling::enabled_xml_path(Path::new(&local_addin.path_to_addin_xml_file))
+        .parent()
+        .map(Path::to_path_buf)
+        .ok_or_else(|| {
+            format!(
+                "Invalid addin file path: {}",
+                local_addin.path_to_addin_xml_file
+            )
+        })
+}
+
+/// The files of the registry addin, keyed by where they go relative to the Revit version folder
+fn registry_files(registry_addin: &AddinModel) -> Result<BTreeMap<String, PathBuf>, String> {
+    let xml_file = Path::new(&registry_addin.path_to_addin_xml_file);
+    let xml_file_name = file_name(xml_file)?;
+    let dll_folder = Path::new(&registry_addin.path_to_addin_dll_folder);
+    let dll_folder_name = file_name(dll_folder)?;
+
+    let mut files = BTreeMap::new();
+    files.insert(xml_file_name, xml_file.to_path_buf());
+    if dll_folder.is_dir() {
+        collect_files(dll_folder, &dll_folder_name, &mut files)
+            .map_err(|e| format!("Failed to read {}: {}", dll_folder.display(), e))?;
+    }
+    Ok(files)
+}
+
+/// The installed files of the addin, keyed the same way as `registry_files`
+fn installed_files(
+    local_addin: &AddinModel,
+    registry_addin: &AddinModel,
+) -> Result<BTreeMap<String, PathBuf>, String> {
+    let version_dir = local_version_dir(local_addin)?;
+    let xml_file_name = file_name(Path::new(&registry_addin.path_to_addin_xml_file))?;
+    let dll_folder_name = file_name(Path::new(&registry_addin.path_to_addin_dll_folder))?;
+
+    let mut files = BTreeMap::new();
+    let xml_file = disabling::installed_xml_path(&version_dir.join(&xml_file_name));
+    if xml_file.is_file() {
+        files.insert(xml_file_name, xml_file);
+    }
+    let dll_folder = version_dir.join(&dll_folder_name);
+    if dll_folder.is_dir() {
+        collect_files(&dll_folder, &dll_folder_name, &mut files)
+            .map_err(|e| format!("Failed to read {}: {}", dll_folder.display(), e))?;
+    }
+    Ok(files)
+}
+
+/// Adds every file below `dir` to `files`, with paths like "<prefix>/Sub/File.dll"
+fn collect_files(
+    dir: &Path,
+    prefix: &str,
+    files: &mut BTreeMap<String, PathBuf>,
+) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let relative_path = format!("{}/{}", prefix, entry.file_name().to_string_lossy());
+        if entry.file_type()?.is_dir() {
+            collect_files(&path, &relative_path, files)?;
+        } else {
+            files.insert(relative_path, path);
+        }
+    }
+    Ok(())
+}
+
+fn is_same_contents(a: &Path, b: &Path) -> io::Result<bool> {
+    if fs::metadata(a)?.len() != fs::metadata(b)?.len() {
+        return Ok(false);
+    }
+    Ok(fs::read(a)? == fs::read(b)?)
+}
+
+/// The `Assembly` paths in the .addin file that don't point to a file. Relative paths are relative to the .addin file
+fn find_unresolved_assemblies(xml_file: &Path) -> Result<Vec<String>, String> {
+    let revit_addins = RevitAddIns::from_file(xml_file).map_err(|e| e.to_string())?;
+    let xml_dir = xml_file.parent().unwrap_or(Path::new(""));
+    Ok(revit_addins
+        .add_in
+        .iter()
+        .filter_map(|addin| addin.assembly.as_ref())
+        .filter(|assembly| {
+            let assembly_path =
+                PathBuf::from(utils::double_backslash_to_single_forward_slash(assembly));
+            !xml_dir.join(assembly_path).is_file()
+        })
+        .cloned()
+        .collect())
+}
+
+fn file_name(path: &Path) -> Result<String, String> {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .ok_or_else(|| format!("Invalid path: {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::{
+        local_addins::models::{
+            install_receipt_model::InstallActor, install_scope_model::InstallScope,
+        },
+        test_fixtures,
+    };
+
+    /// `MyAddin` with a second DLL
+    fn write_addin(dir: &Path) -> AddinModel {
+        let addin = test_fixtures::write_addin(dir, "v1");
+        fs::write(
+            Path::new(&addin.path_to_addin_dll_folder).join("Helper.dll"),
+            "helper",
+        )
+        .unwrap();
+        addin
+    }
+
+    #[test]
+    fn test_verify_and_repair() {
+        let root = std::env::temp_dir().join(format!("addin_integrity_{}", uuid::Uuid::new_v4()));
+        let registry_addin = write_addin(&root.join("Registry").join("Tools"));
+        let local_addin = write_addin(&root.join("Addins").join("2025"));
+        let local_dll_folder = Path::new(&local_addin.path_to_addin_dll_folder);
+
+        let verification = verify_addin(&local_addin, Some(&registry_addin));
+        assert!(verification.is_healthy);
+
+        fs::remove_file(local_dll_folder.join("MyAddin.dll")).unwrap();
+        fs::write(local_dll_folder.join("Helper.dll"), "tampered").unwrap();
+        fs::write(local_dll_folder.join("settings.json"), "{}").unwrap();
+        let verification = verify_addin(&local_addin, Some(&registry_addin));
+        assert!(!verification.is_healthy);
+        assert_eq!(
+            verification.unresolved_assemblies,
+            vec!["MyAddin\\MyAddin.dll"]
+        );
+        let kind_of = |relative_path: &str| {
+            verification
+                .discrepancies
+                .iter()
+                .find(|d| d.relative_path == relative_path)
+                .map(|d| d.kind)
+        };
+        assert_eq!(
+            kind_of("MyAddin/MyAddin.dll"),
+            Some(FileDiscrepancyKind::Missing)
+        );
+        assert_eq!(
+            kind_of("MyAddin/Helper.dll"),
+            Some(FileDiscrepancyKind::Modified)
+        );
+        assert_eq!(
+            kind_of("MyAddin/settings.json"),
+            Some(FileDiscrepancyKind::Extra)
+        );
+
+        let repair = repair_addin(&local_addin, &registry_addin).unwrap();
+        assert_eq!(
+            repair.restored_files,
+            vec!["MyAddin/Helper.dll", "MyAddin/MyAddin.dll"]
+        );
+        assert!(repair.verification.unresolved_assemblies.is_empty());
+        // Only the extra file that the addin could have written itself is left
+        assert_eq!(repair.verification.discrepancies.len(), 1);
+        assert!(local_dll_folder.join("settings.json").exists());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_find_registry_source_by_dll_folder_only_for_installed_addins() {
+        let root = std::env::temp_dir().join(format!("addin_source_{}", uuid::Uuid::new_v4()));
+        let mut registry_addin = write_addin(&root.join("Registry").join("Tools"));
+        let local_addin = write_addin(&root.join("Addins").join("2025"));
+        let registry_addins = vec![registry_addin.clone()];
+        assert!(find_registry_source(&local_addin, &registry_addins, &[]).is_some());
+
+        // The registry addin got a new ID, so only its DLL folder name is left to match by
+        registry_addin.addin_id = "new-id".to_string();
+        let registry_addins = vec![registry_addin.clone()];
+        assert!(find_registry_source(&local_addin, &registry_addins, &[]).is_none());
+
+        let receipt = install_receipts::build_receipt(
+            &registry_addin,
+            Path::new(&local_addin.path_to_addin_xml_file),
+            "2025",
+            InstallScope::User,
+            InstallActor::User,
+        )
+        .unwrap();
+        assert!(find_registry_source(&local_addin, &registry_addins, &[receipt]).is_some());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_verify_against_receipt() {
+        let root = std::env::temp_dir().join(format!("addin_receipt_{}", uuid::Uuid::new_v4()));
+        let registry_addin = write_addin(&root.join("Registry").join("Tools"));
+        let local_addin = write_addin(&root.join("Addins").join("2025"));
+        let local_dll_folder = Path::new(&local_addin.path_to_addin_dll_folder);
+        crate::services::local_addins::install_manifest::converge_to_source(
+            Path::new(&registry_addin.path_to_addin_dll_folder),
+            local_dll_folder,
+        )
+        .unwrap();
+        let receipt = install_receipts::build_receipt(
+            &registry_addin,
+            Path::new(&local_addin.path_to_addin_xml_file),
+            "2025",
+            InstallScope::User,
+            InstallActor::User,
+        )
+        .unwrap();
+        assert_eq!(receipt.files.len(), 3);
+
+        // The registry is gone, but the receipt still knows what was installed
+        fs::remove_dir_all(root.join("Registry")).unwrap();
+        assert!(verify_against_receipt(&local_addin, &receipt).is_healthy);
+
+        fs::write(local_dll_folder.join("Helper.dll"), "tampered").unwrap();
+        let verification = verify_against_receipt(&local_addin, &receipt);
+        assert_eq!(
+            verification.discrepancies,
+            vec![FileDiscrepancyModel {
+                relative_path: "MyAddin/Helper.dll".to_string(),
+                kind: FileDiscrepancyKind::Modified,
+            }]
+        );
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,154 @@
+use std::{io, path::Path};
+
+use crate::services::{
+    addins_registry::{
+        models::addin_model::AddinModel, services::local_registry::LocalAddinsRegistryService,
+    },
+    local_addins::{
+        disabling, install_manifest, install_receipts,
+        models::{
+            addin_management_model::AddinManagement,
+            install_manifest_model::InstallManifestModel,
+            install_receipt_model::{InstallActor, InstallReceiptModel},
+        },
+        service::LocalAddinsService,
+    },
+    local_db::service::LocalDbService,
+};
+
+/// Managed if the app installed it, unmanaged if the registry has an addin with the same ID, and foreign otherwise
+///
+/// If the registry couldn't be read, `registry_addins` is None and anything without a receipt is treated as
+/// foreign, so that it isn't touched until we know better
+pub fn classify(
+    addin: &AddinModel,
+    registry_addins: Option<&[AddinModel]>,
+    receipts: &[InstallReceiptModel],
+) -> AddinManagement {
+    // Receipts are keyed by where the .addin file goes when enabled
+    let xml_file = disabling::enabled_xml_path(Path::new(&addin.path_to_addin_xml_file));
+    if receipts
+        .iter()
+        .any(|receipt| Path::new(&receipt.installed_xml_file) == xml_file)
+    {
+        return AddinManagement::Managed;
+    }
+    let is_in_registry = registry_addins.is_some_and(|registry_addins| {
+        registry_addins
+            .iter()
+            .any(|registry_addin| registry_addin.addin_id == addin.addin_id)
+    });
+    if is_in_registry {
+        AddinManagement::Unmanaged
+    } else {
+        AddinManagement::Foreign
+    }
+}
+
+pub fn classify_all(
+    addins: &mut [AddinModel],
+    registry_addins: Option<&[AddinModel]>,
+    receipts: &[InstallReceiptModel],
+) {
+    for addin in addins.iter_mut() {
+        addin.management = Some(classify(addin, registry_addins, receipts));
+    }
+}
+
+/// The local addins, each with its `management` set
+pub async fn get_classified_local_addins(
+    local_addins: &LocalAddinsService,
+    addins_registry: &LocalAddinsRegistryService,
+    local_db: &LocalDbService,
+) -> Result<Vec<AddinModel>, String> {
+    let mut addins = local_addins.get_local_addins()?;
+    let registry_addins = addins_registry.get_addins_or_snapshot().await.ok();
+    let receipts = local_db.install_receipts_table().get_all().await?;
+    classify_all(&mut addins, registry_addins.as_deref(), &receipts);
+    Ok(addins)
+}
+
+/// Takes over an unmanaged copy of a registry addin, so that it is updated and uninstalled like one we installed
+///
+/// Only the files that are also in the registry become ours. The files aren't changed, the next update brings
+/// them in line with the registry
+pub fn adopt(
+    local_addin: &AddinModel,
+    registry_addin: &AddinModel,
+) -> io::Result<InstallReceiptModel> {
+    let source_files =
+        install_manifest::list_files(Path::new(&registry_addin.path_to_addin_dll_folder))?;
+    let dll_folder = Path::new(&local_addin.path_to_addin_dll_folder);
+    let owned_files = install_manifest::list_files(dll_folder)?
+        .into_iter()
+        .filter(|file| source_files.contains(file))
+        .collect();
+    install_manifest::write_manifest(dll_folder, &InstallManifestModel { files: owned_files })?;
+
+    install_receipts::build_receipt(
+        registry_addin,
+        Path::new(&local_addin.path_to_addin_xml_file),
+        &local_addin.revit_version.clone().unwrap_or_default(),
+        local_addin.install_scope.unwrap_or_default(),
+        InstallActor::User,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::{local_addins::models::install_scope_model::InstallScope, test_fixtures};
+
+    fn addin(addin_id: &str, xml_file: &str) -> AddinModel {
+        let xml_addin = test_fixtures::xml_addin("Addin", addin_id);
+        AddinModel::from_xml_data(xml_file.to_string(), String::new(), &xml_addin).unwrap()
+    }
+
+    fn receipt(installed_xml_file: &str) -> InstallReceiptModel {
+        InstallReceiptModel {
+            installed_xml_file: installed_xml_file.to_string(),
+            installed_dll_folder: String::new(),
+            source_xml_file: String::new(),
+            source_dll_folder: String::new(),
+            addin_id: "ours".to_string(),
+            name: "Addin".to_string(),
+            vendor: String::new(),
+            version: String::new(),
+            revit_version: "2025".to_string(),
+            install_scope: InstallScope::User,
+            files: Vec::new(),
+            installed_by: InstallActor::User,
+            installed_at: String::new(),
+            updated_at: String::new(),
+            rolled_back_from: None,
+        }
+    }
+
+    #[test]
+    fn test_classify() {
+        let registry_addins = vec![addin("ours", "Registry/Ours.addin")];
+        let registry_addins = Some(&registry_addins[..]);
+        let managed = addin("ours", "Addins/2025/Ours.addin");
+        let unmanaged = addin("ours", "Addins/2026/Ours.addin");
+        let foreign = addin("enscape", "Addins/2025/Enscape.addin");
+        let receipts = vec![receipt("Addins/2025/Ours.addin")];
+
+        assert_eq!(
+            classify(&managed, registry_addins, &receipts),
+            AddinManagement::Managed
+        );
+        assert_eq!(
+            classify(&unmanaged, registry_addins, &receipts),
+            AddinManagement::Unmanaged
+        );
+        assert_eq!(
+            classify(&foreign, registry_addins, &receipts),
+            AddinManagement::Foreign
+        );
+        // Without the registry, only what we installed can be told apart
+        assert_eq!(
+            classify(&unmanaged, None, &receipts),
+            AddinManagement::Foreign
+        );
+    }
+}
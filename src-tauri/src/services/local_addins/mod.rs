@@ -1,4 +1,13 @@
-mod models;
+pub mod bisect;
+pub mod disabling;
+pub mod install_activity;
+pub mod install_manifest;
+pub mod install_receipts;
+pub mod integrity;
+pub mod management;
+pub mod models;
+pub mod revit_versions;
 pub mod service;
 pub mod tauri_exports;
-mod events;
\ No newline at end of file
+pub mod uninstall;
+mod events;
@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+
+/// Whether a local addin is one of ours
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AddinManagement {
+    /// Installed by the app, so it has an install receipt
+    Managed,
+    /// The same addin is in the registry, but this copy wasn't installed by the app. It can be adopted
+    Unmanaged,
+    /// Not from the registry, e.g. an Autodesk or Enscape addin. Only shown, never updated or uninstalled
+    Foreign,
+}
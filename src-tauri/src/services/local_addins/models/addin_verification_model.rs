@@ -0,0 +1,59 @@
+use serde::{Deserialize, Serialize};
+
+use crate::services::addins_registry::models::addin_model::AddinModel;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FileDiscrepancyKind {
+    /// The file is in the registry but not installed
+    Missing,
+    /// The file is installed but not in the registry
+    Extra,
+    /// The installed file has different contents than the one in the registry
+    Modified,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileDiscrepancyModel {
+    /// Relative to the Revit version folder, e.g. "MyAddin/MyAddin.dll"
+    pub relative_path: String,
+    pub kind: FileDiscrepancyKind,
+}
+
+/// How an installed addin compares to its source in the registry, for one Revit version
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddinVerificationModel {
+    /// The installed addin
+    pub addin: AddinModel,
+    /// The .addin file in the registry that the addin was installed from. None if it couldn't be found
+    pub registry_xml_file: Option<String>,
+    pub discrepancies: Vec<FileDiscrepancyModel>,
+    /// The `Assembly` paths in the installed .addin file that don't point to a file
+    pub unresolved_assemblies: Vec<String>,
+    /// Set if the addin couldn't be verified, e.g. the registry can't be read
+    pub error: Option<String>,
+    pub is_healthy: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddinRepairModel {
+    /// The files that were copied from the registry again
+    pub restored_files: Vec<String>,
+    /// The state of the addin after the repair
+    pub verification: AddinVerificationModel,
+    /// Set if the addin couldn't be repaired, e.g. its source isn't in the registry
+    pub error: Option<String>,
+}
+
+impl AddinRepairModel {
+    pub fn failed(verification: AddinVerificationModel, error: String) -> Self {
+        Self {
+            restored_files: Vec::new(),
+            verification,
+            error: Some(error),
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+use crate::services::local_addins::models::install_scope_model::InstallScope;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BisectCandidateModel {
+    /// Where the .addin file goes when the addin is enabled. Identifies the addin across steps
+    pub xml_file: String,
+    pub name: String,
+    pub install_scope: InstallScope,
+}
+
+/// A guided search for the addin that causes a problem in one Revit version
+///
+/// Each step, half of the suspects are enabled and every other candidate is disabled. The user starts Revit, and
+/// whether the problem shows up tells which half the culprit is in
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BisectSessionModel {
+    pub revit_version: String,
+    /// The managed addins that were enabled when the session started. They are enabled again when it ends
+    pub candidates: Vec<BisectCandidateModel>,
+    /// The .addin files of the candidates that may still cause the problem
+    pub suspects: Vec<String>,
+    /// The .addin files of the suspects that are enabled for the current step
+    pub testing: Vec<String>,
+    pub steps_taken: u32,
+    /// Set once a single suspect remains. The original state has been restored by then
+    pub culprit: Option<BisectCandidateModel>,
+    pub started_at: String,
+}
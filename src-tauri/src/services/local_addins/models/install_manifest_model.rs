@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+/// The files in an installed addin's DLL folder that the manager put there
+///
+/// Anything else in the folder, e.g. settings written by the addin itself, is left alone by updates
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstallManifestModel {
+    /// Relative to the DLL folder, e.g. "MyAddin.dll" or "Resources/icon.png"
+    pub files: Vec<String>,
+}
@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+
+use crate::services::local_addins::models::install_scope_model::InstallScope;
+
+/// Who installed an addin
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum InstallActor {
+    /// The user installed it from the library
+    User,
+    /// The update checker installed it because the user is allowed to have it
+    AutoUpdater,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReceiptFileModel {
+    /// Relative to the Revit version folder, e.g. "MyAddin/MyAddin.dll"
+    pub relative_path: String,
+    pub sha256: String,
+}
+
+/// What the app installed for one Revit version and scope, when, and where it came from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstallReceiptModel {
+    pub installed_xml_file: String,
+    pub installed_dll_folder: String,
+    /// The .addin file in the registry that the addin was installed from
+    pub source_xml_file: String,
+    pub source_dll_folder: String,
+    pub addin_id: String,
+    pub name: String,
+    pub vendor: String,
+    pub version: String,
+    pub revit_version: String,
+    pub install_scope: InstallScope,
+    /// The files the app installed. Files the addin created itself are not included
+    pub files: Vec<ReceiptFileModel>,
+    pub installed_by: InstallActor,
+    /// RFC 3339
+    pub installed_at: String,
+    /// When the files were last updated, in RFC 3339 format. The same as `installed_at` until the first update
+    pub updated_at: String,
+    /// The version the install was rolled back from. The updater doesn't install that version again
+    #[serde(default)]
+    pub rolled_back_from: Option<String>,
+}
@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+
+/// Where an addin is installed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum InstallScope {
+    /// Only for the current user. Typically C:\Users\<username>\AppData\Roaming\Autodesk\Revit\Addins
+    #[default]
+    User,
+    /// For all users of the machine. Typically C:\ProgramData\Autodesk\Revit\Addins
+    Machine,
+}
+
+impl InstallScope {
+    pub const ALL: [InstallScope; 2] = [InstallScope::User, InstallScope::Machine];
+
+    /// The same as the serialized name, so it can be stored in keys
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            InstallScope::User => "user",
+            InstallScope::Machine => "machine",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_as_str_matches_the_serialized_name() {
+        for scope in InstallScope::ALL {
+            assert_eq!(
+                serde_json::to_value(scope).unwrap(),
+                serde_json::Value::String(scope.as_str().to_string())
+            );
+        }
+    }
+}
@@ -1 +1,8 @@
+pub mod addin_management_model;
+pub mod addin_verification_model;
+pub mod bisect_session_model;
+pub mod install_manifest_model;
+pub mod install_receipt_model;
+pub mod install_scope_model;
 pub mod uninstall_request_model;
+pub mod uninstall_result_model;
@@ -1,4 +1,7 @@
-use crate::services::addins_registry::models::addin_model::AddinModel;
+use crate::services::{
+    addins_registry::models::addin_model::AddinModel,
+    local_addins::models::install_scope_model::InstallScope,
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -6,4 +9,8 @@ use serde::{Deserialize, Serialize};
 pub struct UninstallAddinRequestModel {
     pub addin: AddinModel,
     pub for_revit_versions: Vec<String>,
+    /// The scope to uninstall from. Falls back to the addin's own scope, and if that isn't known either
+    /// the addin is uninstalled from every scope
+    #[serde(default)]
+    pub install_scope: Option<InstallScope>,
 }
@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::services::local_addins::models::{
+    install_receipt_model::InstallReceiptModel, install_scope_model::InstallScope,
+};
+
+/// The files of one addin install, for one Revit version and scope
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UninstallTargetModel {
+    pub addin_name: String,
+    pub addin_id: String,
+    pub revit_version: String,
+    pub install_scope: InstallScope,
+    /// Where the .addin file goes when the addin is enabled. A disabled copy is removed as well
+    pub xml_file: String,
+    pub dll_folder: String,
+    /// The files the app installed in the DLL folder, relative to it. Only these are removed, and the folder only
+    /// if nothing else is left in it. None if nothing recorded them, then the whole folder is removed
+    #[serde(default)]
+    pub dll_files: Option<Vec<String>>,
+}
+
+impl UninstallTargetModel {
+    pub fn from_receipt(receipt: &InstallReceiptModel) -> Self {
+        // The receipt's files are relative to the Revit version folder, e.g. MyAddin/MyAddin.dll
+        let dll_folder_prefix = Path::new(&receipt.installed_dll_folder)
+            .file_name()
+            .map(|name| format!("{}/", name.to_string_lossy()));
+        let dll_files = dll_folder_prefix.map(|prefix| {
+            receipt
+                .files
+                .iter()
+                .filter_map(|file| file.relative_path.strip_prefix(&prefix))
+                .map(str::to_string)
+                .collect()
+        });
+        Self {
+            addin_name: receipt.name.clone(),
+            addin_id: receipt.addin_id.clone(),
+            revit_version: receipt.revit_version.clone(),
+            install_scope: receipt.install_scope,
+            xml_file: receipt.installed_xml_file.clone(),
+            dll_folder: receipt.installed_dll_folder.clone(),
+            dll_files,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum UninstallStatus {
+    /// Nothing of the addin is left
+    Removed,
+    /// Revit holds some of the files, so the addin is removed once Revit closes. Until then it is fully installed
+    Pending,
+    /// The addin couldn't be removed and is still fully installed
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UninstallResultModel {
+    pub target: UninstallTargetModel,
+    pub status: UninstallStatus,
+    pub error: Option<String>,
+}
@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+
+/// The range of Revit versions that an addin supports. Either end of the range can be left open
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RevitVersionRange {
+    pub min: Option<String>,
+    pub max: Option<String>,
+}
+
+impl RevitVersionRange {
+    pub fn new(min: Option<String>, max: Option<String>) -> Self {
+        Self { min, max }
+    }
+
+    /// Whether the given version falls inside of the range. Versions that are not a valid year are never contained
+    pub fn contains(&self, version: &str) -> bool {
+        let Ok(version) = version.parse::<u16>() else {
+            return false;
+        };
+        let above_min = Self::parse_bound(&self.min).map_or(true, |min| version >= min);
+        let below_max = Self::parse_bound(&self.max).map_or(true, |max| version <= max);
+        above_min && below_max
+    }
+
+    /// Returns the versions that fall inside of the range, preserving their order
+    pub fn intersect(&self, versions: &[String]) -> Vec<String> {
+        versions
+            .iter()
+            .filter(|version| self.contains(version))
+            .cloned()
+            .collect()
+    }
+
+    /// Empty bounds are treated as open so that an unset form field does not exclude every version
+    fn parse_bound(bound: &Option<String>) -> Option<u16> {
+        bound
+            .as_deref()
+            .map(str::trim)
+            .filter(|bound| !bound.is_empty())
+            .and_then(|bound| bound.parse().ok())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_range_intersection() {
+        let installed = vec![
+            "2023".to_string(),
+            "2024".to_string(),
+            "2025".to_string(),
+            "2026".to_string(),
+        ];
+        let range = RevitVersionRange::new(Some("2024".to_string()), Some("2025".to_string()));
+        assert_eq!(range.intersect(&installed), vec!["2024", "2025"]);
+
+        let open_ended = RevitVersionRange::new(Some("2025".to_string()), Some("".to_string()));
+        assert_eq!(open_ended.intersect(&installed), vec!["2025", "2026"]);
+
+        assert_eq!(
+            RevitVersionRange::default().intersect(&installed),
+            installed
+        );
+    }
+}
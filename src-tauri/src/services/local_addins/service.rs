@@ -1,27 +1,66 @@
+use log::{error, info, warn};
 use tauri::AppHandle;
 
 use crate::services::addins_registry::models::addin_model::AddinModel;
 use crate::services::addins_registry::models::addin_xml_model::RevitAddIns;
+use crate::services::config::app_paths::AppPaths;
+use crate::services::local_addins::disabling;
 use crate::services::local_addins::events::AddinInstallProgressEvent;
+use crate::services::local_addins::install_manifest;
+use crate::services::local_addins::install_receipts;
+use crate::services::local_addins::models::install_receipt_model::{
+    InstallActor, InstallReceiptModel,
+};
+use crate::services::local_addins::models::install_scope_model::InstallScope;
+use crate::services::local_addins::models::uninstall_result_model::UninstallTargetModel;
 use crate::utils;
 use std::fs;
 use std::path::Path;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 
 pub struct LocalAddinsService {
-    app_handle: AppHandle,
+    paths: Arc<AppPaths>,
+    /// Used to emit install progress events. None when running without a UI, e.g. in tests
+    app_handle: Option<AppHandle>,
 }
 
 impl LocalAddinsService {
-    pub fn new(app_handle: AppHandle) -> Self {
-        Self { app_handle }
+    pub fn new(app_handle: AppHandle, paths: Arc<AppPaths>) -> Self {
+        Self {
+            paths,
+            app_handle: Some(app_handle),
+        }
+    }
+
+    /// Creates a service that doesn't emit any events
+    #[cfg(test)]
+    pub fn without_events(paths: Arc<AppPaths>) -> Self {
+        Self {
+            paths,
+            app_handle: None,
+        }
     }
 
     /// Typically C:\Users\<username>\AppData\Roaming\Autodesk\Revit\Addins\2024
-    pub fn path_to_local_addins_folder() -> Result<String, Box<dyn std::error::Error>> {
-        let data_roaming = dirs::data_dir().ok_or("Could not get data roaming directory")?;
+    pub fn path_to_local_addins_folder(&self) -> Result<String, Box<dyn std::error::Error>> {
+        let path_str = self
+            .paths
+            .user_addins_root
+            .to_str()
+            .ok_or("Path contains invalid UTF-8 characters")?;
 
-        let path = data_roaming.join("Autodesk").join("Revit").join("Addins");
+        Ok(path_str.to_string())
+    }
+
+    /// Typically C:\ProgramData\Autodesk\Revit\Addins\2024
+    ///
+    /// Addins installed here are loaded by Revit for every user of the machine
+    pub fn path_to_machine_addins_folder(&self) -> Result<String, Box<dyn std::error::Error>> {
+        let path = self
+            .paths
+            .machine_addins_root
+            .as_ref()
+            .ok_or("There is no machine-wide addins folder on this platform")?;
 
         let path_str = path
             .to_str()
@@ -30,17 +69,32 @@ impl LocalAddinsService {
         Ok(path_str.to_string())
     }
 
-    /// Returns a list of all the Revit versions that have addins installed
+    pub fn path_to_addins_folder(
+        &self,
+        scope: InstallScope,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        match scope {
+            InstallScope::User => self.path_to_local_addins_folder(),
+            InstallScope::Machine => self.path_to_machine_addins_folder(),
+        }
+    }
+
+    /// Returns a list of all the Revit versions that have addins installed in the given scope
     ///
     /// Example: ["2024", "2025", "2026"]
-    pub fn get_revit_versions() -> Result<Vec<String>, String> {
-        let path = match Self::path_to_local_addins_folder() {
+    pub fn get_revit_versions(&self, scope: InstallScope) -> Result<Vec<String>, String> {
+        let path = match self.path_to_addins_folder(scope) {
             Ok(path) => path,
             Err(e) => {
                 return Err(format!("Error getting local addins folder path: {}", e));
             }
         };
 
+        // Nothing has been installed in this scope yet
+        if !Path::new(&path).exists() {
+            return Ok(Vec::new());
+        }
+
         let mut versions = Vec::new();
 
         match std::fs::read_dir(&path) {
@@ -71,16 +125,22 @@ impl LocalAddinsService {
         Ok(versions)
     }
 
-    pub fn get_local_addins() -> Result<Vec<AddinModel>, String> {
+    /// Returns the addins installed for the current user and for the whole machine
+    pub fn get_local_addins(&self) -> Result<Vec<AddinModel>, String> {
         let mut all_addins = Vec::new();
-        let versions = match Self::get_revit_versions() {
-            Ok(versions) => versions,
-            Err(e) => {
-                return Err(e);
+        for scope in InstallScope::ALL {
+            if scope == InstallScope::Machine && self.paths.machine_addins_root.is_none() {
+                continue;
             }
-        };
+            self.scan_scope(scope, &mut all_addins)?;
+        }
+        Ok(all_addins)
+    }
+
+    fn scan_scope(&self, scope: InstallScope, addins: &mut Vec<AddinModel>) -> Result<(), String> {
+        let versions = self.get_revit_versions(scope)?;
 
-        let base_path = match Self::path_to_local_addins_folder() {
+        let base_path = match self.path_to_addins_folder(scope) {
             Ok(path) => path,
             Err(e) => {
                 return Err(format!("Error getting local addins folder path: {}", e));
@@ -90,25 +150,44 @@ impl LocalAddinsService {
         for version in versions {
             let version_path = Path::new(&base_path).join(&version);
 
-            if let Err(e) = Self::scan_version_folder(&version_path, &version, &mut all_addins) {
+            if let Err(e) = Self::scan_version_folder(&version_path, &version, scope, addins) {
                 return Err(format!("Error scanning version folder {}: {}", version, e));
             }
         }
 
-        Ok(all_addins)
+        Ok(())
     }
 
-    /// Scan a specific Revit version folder for .addin files
+    /// Scan a specific Revit version folder for .addin files, including the disabled ones
     fn scan_version_folder(
         version_path: &Path,
         revit_version: &str,
+        scope: InstallScope,
         addins: &mut Vec<AddinModel>,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        if !version_path.exists() || !version_path.is_dir() {
+        Self::scan_addin_files(version_path, version_path, revit_version, scope, addins)?;
+        Self::scan_addin_files(
+            &version_path.join(disabling::DISABLED_FOLDER_NAME),
+            version_path,
+            revit_version,
+            scope,
+            addins,
+        )
+    }
+
+    /// Scan a folder for .addin files whose DLL folders are in `version_path`
+    fn scan_addin_files(
+        xml_folder_path: &Path,
+        version_path: &Path,
+        revit_version: &str,
+        scope: InstallScope,
+        addins: &mut Vec<AddinModel>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if !xml_folder_path.exists() || !xml_folder_path.is_dir() {
             return Ok(());
         }
 
-        for entry in fs::read_dir(version_path)? {
+        for entry in fs::read_dir(xml_folder_path)? {
             let entry = entry?;
             let path = entry.path();
 
@@ -116,8 +195,14 @@ impl LocalAddinsService {
                 // Check if this is a .addin file
                 if let Some(extension) = path.extension() {
                     if extension == "addin" {
-                        if let Err(e) = Self::process_addin_file(&path, revit_version, addins) {
-                            println!("Error processing addin file {:?}: {}", path, e);
+                        if let Err(e) = Self::process_addin_file(
+                            &path,
+                            version_path,
+                            revit_version,
+                            scope,
+                            addins,
+                        ) {
+                            error!("Error processing addin file {:?}: {}", path, e);
                         }
                     }
                 }
@@ -130,16 +215,14 @@ impl LocalAddinsService {
     /// Process a single .addin file and extract addin information
     fn process_addin_file(
         xml_file_path: &Path,
+        version_path: &Path,
         revit_version: &str,
+        scope: InstallScope,
         addins: &mut Vec<AddinModel>,
     ) -> Result<(), Box<dyn std::error::Error>> {
         // Try to parse the XML file
         let revit_addins = RevitAddIns::from_file(xml_file_path)?;
-
-        // Get the directory containing the .addin file
-        let xml_dir = xml_file_path
-            .parent()
-            .ok_or("Could not get parent directory of XML file")?;
+        let is_disabled = disabling::is_disabled_xml_file(xml_file_path);
 
         // Get the base name of the .addin file (without extension)
         let xml_file_stem = xml_file_path
@@ -148,10 +231,11 @@ impl LocalAddinsService {
             .ok_or("Could not get file stem of XML file")?;
 
         // Look for the corresponding DLL folder
-        let dll_folder_path = xml_dir.join(xml_file_stem);
+        // The DLL folder is always in the version folder, also when the .addin file is disabled
+        let dll_folder_path = version_path.join(xml_file_stem);
 
         if !dll_folder_path.exists() {
-            println!(
+            info!(
                 "DLL folder not found for addin {:?}: {:?}",
                 xml_file_path, dll_folder_path
             );
@@ -159,12 +243,14 @@ impl LocalAddinsService {
 
         // Convert each addin in the XML to our AddinModel
         for xml_addin in &revit_addins.add_in {
-            if let Some(addin_model) = AddinModel::from_local_xml_data(
+            if let Some(mut addin_model) = AddinModel::from_local_xml_data(
                 xml_file_path.to_string_lossy().to_string(),
                 dll_folder_path.to_string_lossy().to_string(),
                 xml_addin,
                 revit_version.to_string(),
+                scope,
             ) {
+                addin_model.is_disabled = is_disabled;
                 addins.push(addin_model);
             }
         }
@@ -174,44 +260,65 @@ impl LocalAddinsService {
 
     /// Checks if an addin with the given name, vendor, and addinType is installed locally
     pub fn is_addin_installed_locally(
+        &self,
         name: &str,
         vendor: &str,
         addin_type: &str,
     ) -> Result<bool, String> {
-        let local_addins = Self::get_local_addins()?;
+        let local_addins = self.get_local_addins()?;
         Ok(local_addins.iter().any(|addin| {
             addin.name == name && addin.vendor == vendor && addin.addin_type == addin_type
         }))
     }
 
     /// Checks if an addin with the given name, vendor, and addinType is installed locally
-    pub fn is_addin_installed_locally_model(addin: &AddinModel) -> Result<bool, String> {
+    pub fn is_addin_installed_locally_model(&self, addin: &AddinModel) -> Result<bool, String> {
         let name = &addin.name;
         let vendor = &addin.vendor;
         let addin_type = &addin.addin_type;
-        Self::is_addin_installed_locally(name, vendor, addin_type)
+        self.is_addin_installed_locally(name, vendor, addin_type)
+    }
+
+    /// The Revit versions an addin with the same name, vendor, and addinType is installed for
+    pub fn installed_revit_versions(&self, addin: &AddinModel) -> Result<Vec<String>, String> {
+        let local_addins = self.get_local_addins()?;
+        Ok(local_addins
+            .into_iter()
+            .filter(|local_addin| {
+                local_addin.name == addin.name
+                    && local_addin.vendor == addin.vendor
+                    && local_addin.addin_type == addin.addin_type
+            })
+            .filter_map(|local_addin| local_addin.revit_version)
+            .collect())
     }
 
     pub fn install_addin(
         &self,
         addin: &AddinModel,
         for_revit_versions: &[String],
+        scope: InstallScope,
     ) -> Result<(), String> {
-        let base_path = Self::path_to_local_addins_folder().map_err(|e| e.to_string())?;
+        let base_path = self
+            .path_to_addins_folder(scope)
+            .map_err(|e| e.to_string())?;
 
         let progress = Mutex::new(0);
         let progress_value_per_version = 100 / for_revit_versions.len() as i32;
 
         let emit_progress_event = |progress| {
+            let Some(app_handle) = &self.app_handle else {
+                return;
+            };
             if let Err(e) = super::events::emit_progress_event(
-                self.app_handle.clone(),
+                app_handle.clone(),
                 AddinInstallProgressEvent {
                     progress,
                     addin_name: addin.name.clone(),
                     description: format!("{} is being installed...", addin.name),
                 },
             ) {
-                println!("Warning: error emitting progress event: {}", e);
+                warn!("Error emitting progress event: {}", e);
             }
         };
         // emit the initial event
@@ -234,6 +341,8 @@ impl LocalAddinsService {
                     .ok_or("Invalid addin XML file name")?,
             );
             fs::copy(addin_xml_src, &addin_xml_dst).map_err(|e| e.to_string())?;
+            // Installing again enables the addin, so a disabled copy would only be a duplicate
+            disabling::remove_disabled_xml_file(&addin_xml_dst).map_err(|e| e.to_string())?;
 
             // Bump up the progress:
             let mut progress = progress.lock().map_err(|e| e.to_string())?;
@@ -245,6 +354,9 @@ impl LocalAddinsService {
             let dll_dst = version_path.join(dll_src.file_name().ok_or("Invalid DLL folder name")?);
             if dll_src.exists() && dll_src.is_dir() {
                 utils::copy_dir_all(dll_src, &dll_dst).map_err(|e| e.to_string())?;
+                // A reinstall shouldn't leave the files of the previous install behind
+                install_manifest::converge_to_source(dll_src, &dll_dst)
+                    .map_err(|e| e.to_string())?;
             }
 
             Ok::<(), String>(())
@@ -257,35 +369,95 @@ impl LocalAddinsService {
         result.map_err(|e| e.to_string())
     }
 
-    pub fn uninstall_addin(
+    /// Builds the receipts of an install that just finished, one per Revit version
+    pub fn build_install_receipts(
+        &self,
         addin: &AddinModel,
         for_revit_versions: &[String],
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        let base_path = Self::path_to_local_addins_folder()?;
-
-        for version in for_revit_versions {
-            let version_path = Path::new(&base_path).join(version);
-
-            // Get the file names from the source paths
-            let addin_xml_src = Path::new(&addin.path_to_addin_xml_file);
-            let dll_src = Path::new(&addin.path_to_addin_dll_folder);
+        scope: InstallScope,
+        installed_by: InstallActor,
+    ) -> Result<Vec<InstallReceiptModel>, String> {
+        let base_path = self
+            .path_to_addins_folder(scope)
+            .map_err(|e| e.to_string())?;
+        let xml_file_name = Path::new(&addin.path_to_addin_xml_file)
+            .file_name()
+            .ok_or("Invalid addin XML file name")?;
+
+        for_revit_versions
+            .iter()
+            .map(|version| {
+                let installed_xml_file = Path::new(&base_path).join(version).join(xml_file_name);
+                install_receipts::build_receipt(
+                    addin,
+                    &installed_xml_file,
+                    version,
+                    scope,
+                    installed_by,
+                )
+                .map_err(|e| {
+                    format!(
+                        "Failed to build the install receipt for {}: {}",
+                        installed_xml_file.display(),
+                        e
+                    )
+                })
+            })
+            .collect()
+    }
 
-            // Remove the DLL folder first (recursively)
-            if let Some(folder_name) = dll_src.file_name() {
-                let dll_dst = version_path.join(folder_name);
-                if dll_dst.exists() && dll_dst.is_dir() {
-                    fs::remove_dir_all(&dll_dst)?;
+    /// What to remove to uninstall the addin, found from the paths of the addin. Used for addins without a receipt
+    pub fn uninstall_targets(
+        &self,
+        addin: &AddinModel,
+        for_revit_versions: &[String],
+        scope: InstallScope,
+    ) -> Result<Vec<UninstallTargetModel>, Box<dyn std::error::Error>> {
+        let base_path = self.path_to_addins_folder(scope)?;
+        let xml_file_name = Path::new(&addin.path_to_addin_xml_file)
+            .file_name()
+            .ok_or("Invalid addin XML file name")?;
+        let dll_folder_name = Path::new(&addin.path_to_addin_dll_folder)
+            .file_name()
+            .ok_or("Invalid DLL folder name")?;
+
+        Ok(for_revit_versions
+            .iter()
+            .map(|version| {
+                let version_path = Path::new(&base_path).join(version);
+                let dll_folder = version_path.join(dll_folder_name);
+                // Without a manifest either, nothing says which files are ours
+                let dll_files = install_manifest::read_manifest(&dll_folder)
+                    .ok()
+                    .flatten()
+                    .map(|manifest| manifest.files);
+                UninstallTargetModel {
+                    addin_name: addin.name.clone(),
+                    addin_id: addin.addin_id.clone(),
+                    revit_version: version.clone(),
+                    install_scope: scope,
+                    xml_file: version_path
+                        .join(xml_file_name)
+                        .to_string_lossy()
+                        .to_string(),
+                    dll_folder: dll_folder.to_string_lossy().to_string(),
+                    dll_files,
                 }
-            }
-            // If the DLL folder can't be deleted due to file locking, then don't try to remove the .addin file
+            })
+            .collect())
+    }
 
-            // Remove the .addin file
-            if let Some(file_name) = addin_xml_src.file_name() {
-                let addin_xml_dst = version_path.join(file_name);
-                if addin_xml_dst.exists() {
-                    fs::remove_file(&addin_xml_dst)?;
-                }
-            }
+    /// Each Revit version is removed completely or not at all. The app uses `uninstall_targets` instead, to report
+    /// each version separately
+    #[cfg(test)]
+    pub fn uninstall_addin(
+        &self,
+        addin: &AddinModel,
+        for_revit_versions: &[String],
+        scope: InstallScope,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        for target in self.uninstall_targets(addin, for_revit_versions, scope)? {
+            super::uninstall::remove_target(&target)?;
         }
         Ok(())
     }
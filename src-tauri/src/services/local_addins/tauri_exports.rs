@@ -1,25 +1,514 @@
+use log::{error, warn};
+use std::path::Path;
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::services::addin_updater::update_checker::pending_updates;
 use crate::services::addins_registry::models::addin_model::AddinModel;
+use crate::services::addins_registry::services::local_registry::LocalAddinsRegistryService;
+use crate::services::local_addins::bisect;
+use crate::services::local_addins::disabling;
+use crate::services::local_addins::install_activity;
+use crate::services::local_addins::install_receipts;
+use crate::services::local_addins::integrity;
+use crate::services::local_addins::management;
+use crate::services::local_addins::models::addin_management_model::AddinManagement;
+use crate::services::local_addins::models::addin_verification_model::{
+    AddinRepairModel, AddinVerificationModel,
+};
+use crate::services::local_addins::models::bisect_session_model::BisectSessionModel;
+use crate::services::local_addins::models::install_receipt_model::InstallReceiptModel;
+use crate::services::local_addins::models::install_scope_model::InstallScope;
 use crate::services::local_addins::models::uninstall_request_model::UninstallAddinRequestModel;
+use crate::services::local_addins::models::uninstall_result_model::{
+    UninstallResultModel, UninstallStatus, UninstallTargetModel,
+};
 use crate::services::local_addins::service::LocalAddinsService;
+use crate::services::local_addins::uninstall;
+use crate::services::local_db::service::LocalDbService;
+use crate::services::revit_installations::service::RevitInstallationsService;
+
+/// Returns the local addins, classified as managed, unmanaged or foreign
+#[tauri::command]
+pub async fn get_local_addins(
+    local_addins_service: State<'_, Arc<LocalAddinsService>>,
+    addins_registry_service: State<'_, Arc<LocalAddinsRegistryService>>,
+    local_db_service: State<'_, Arc<LocalDbService>>,
+) -> Result<Vec<AddinModel>, String> {
+    management::get_classified_local_addins(
+        &local_addins_service,
+        &addins_registry_service,
+        &local_db_service,
+    )
+    .await
+}
+
+/// Brings unmanaged copies of registry addins under management. Managed addins are left as they are
+#[tauri::command]
+pub async fn adopt_local_addins(
+    addins_registry_service: State<'_, Arc<LocalAddinsRegistryService>>,
+    local_db_service: State<'_, Arc<LocalDbService>>,
+    addins: Vec<AddinModel>,
+) -> Result<(), String> {
+    let _adopting = install_activity::begin().await;
+    let registry_addins = addins_registry_service
+        .get_addins_or_snapshot()
+        .await
+        .map_err(|e| e.to_string())?;
+    let receipts_table = local_db_service.install_receipts_table();
+    let receipts = receipts_table.get_all().await?;
+
+    for addin in dedup_by_addin_file(addins) {
+        match management::classify(&addin, Some(registry_addins.as_slice()), &receipts) {
+            AddinManagement::Managed => continue,
+            AddinManagement::Foreign => {
+                return Err(format!(
+                    "{} isn't in the registry, so it can't be adopted",
+                    addin.name
+                ));
+            }
+            AddinManagement::Unmanaged => {}
+        }
+        let registry_addin =
+            integrity::find_registry_source(&addin, &registry_addins, &receipts)
+                .ok_or_else(|| format!("{} could not be found in the registry", addin.name))?;
+        let receipt = management::adopt(&addin, registry_addin)
+            .map_err(|e| format!("Failed to adopt {}: {}", addin.name, e))?;
+        receipts_table.record(receipt).await?;
+    }
+    Ok(())
+}
+
+/// Makes Revit skip the given addins, without removing their files. Foreign addins can't be disabled
+#[tauri::command]
+pub async fn disable_local_addins(
+    addins_registry_service: State<'_, Arc<LocalAddinsRegistryService>>,
+    local_db_service: State<'_, Arc<LocalDbService>>,
+    addins: Vec<AddinModel>,
+) -> Result<(), String> {
+    let _disabling = install_activity::begin().await;
+    let addins = dedup_by_addin_file(addins);
+    ensure_none_foreign(
+        &addins_registry_service,
+        &local_db_service,
+        &addins,
+        "disabled",
+    )
+    .await?;
+    for addin in &addins {
+        disabling::disable(addin)
+            .map_err(|e| format!("Failed to disable {}: {}", addin.name, e))?;
+    }
+    Ok(())
+}
 
+/// Makes Revit load the given addins again
 #[tauri::command]
-pub fn get_local_addins() -> Result<Vec<AddinModel>, String> {
-    LocalAddinsService::get_local_addins()
+pub async fn enable_local_addins(addins: Vec<AddinModel>) -> Result<(), String> {
+    let _enabling = install_activity::begin().await;
+    for addin in dedup_by_addin_file(addins) {
+        disabling::enable(&addin).map_err(|e| format!("Failed to enable {}: {}", addin.name, e))?;
+    }
+    Ok(())
 }
 
+/// The bisect sessions in progress, and the finished ones that haven't been ended yet
 #[tauri::command]
-pub fn get_revit_versions() -> Result<Vec<String>, String> {
-    LocalAddinsService::get_revit_versions()
+pub async fn get_bisect_sessions(
+    local_db_service: State<'_, Arc<LocalDbService>>,
+) -> Result<Vec<BisectSessionModel>, String> {
+    local_db_service.bisect_sessions_table().get_all().await
 }
 
+/// Starts looking for the addin that causes a problem in the Revit version, and disables the first half
 #[tauri::command]
-pub fn uninstall_addins(uninstall_requests: Vec<UninstallAddinRequestModel>) -> Result<(), String> {
+pub async fn start_bisect_session(
+    local_addins_service: State<'_, Arc<LocalAddinsService>>,
+    addins_registry_service: State<'_, Arc<LocalAddinsRegistryService>>,
+    local_db_service: State<'_, Arc<LocalDbService>>,
+    revit_version: String,
+) -> Result<BisectSessionModel, String> {
+    let _bisecting = install_activity::begin().await;
+    let sessions_table = local_db_service.bisect_sessions_table();
+    if sessions_table.get(&revit_version).await?.is_some() {
+        return Err(format!(
+            "A bisect session for Revit {} is already in progress",
+            revit_version
+        ));
+    }
+    let local_addins = management::get_classified_local_addins(
+        &local_addins_service,
+        &addins_registry_service,
+        &local_db_service,
+    )
+    .await?;
+    let session = bisect::start(&revit_version, &local_addins)?;
+    if let Err(e) = bisect::apply(&session, &local_addins) {
+        // Disabling was only half done, so enable everything again
+        let local_addins = local_addins_service.get_local_addins()?;
+        if let Err(restore_error) = bisect::restore(&session, &local_addins) {
+            error!("Failed to restore the addins: {}", restore_error);
+        }
+        return Err(e);
+    }
+    sessions_table.save(&session).await?;
+    Ok(session)
+}
+
+/// Moves the session to the next step, based on whether the problem showed up with the addins under test.
+/// Once the culprit is found, the addins are enabled again
+#[tauri::command]
+pub async fn answer_bisect_step(
+    local_addins_service: State<'_, Arc<LocalAddinsService>>,
+    local_db_service: State<'_, Arc<LocalDbService>>,
+    revit_version: String,
+    problem_reproduced: bool,
+) -> Result<BisectSessionModel, String> {
+    let _bisecting = install_activity::begin().await;
+    let sessions_table = local_db_service.bisect_sessions_table();
+    let previous_session = sessions_table
+        .get(&revit_version)
+        .await?
+        .ok_or_else(|| format!("There is no bisect session for Revit {}", revit_version))?;
+    let mut session = previous_session.clone();
+    bisect::answer(&mut session, problem_reproduced)?;
+
+    let local_addins = local_addins_service.get_local_addins()?;
+    if let Err(e) = bisect::apply(&session, &local_addins) {
+        // Go back to the previous step, so that the question can be answered again
+        let local_addins = local_addins_service.get_local_addins()?;
+        if let Err(restore_error) = bisect::apply(&previous_session, &local_addins) {
+            error!("Failed to go back to the previous step: {}", restore_error);
+        }
+        return Err(e);
+    }
+    sessions_table.save(&session).await?;
+    Ok(session)
+}
+
+/// Forgets the session. If it didn't find the culprit yet, the addins are enabled again
+#[tauri::command]
+pub async fn end_bisect_session(
+    local_addins_service: State<'_, Arc<LocalAddinsService>>,
+    local_db_service: State<'_, Arc<LocalDbService>>,
+    revit_version: String,
+) -> Result<(), String> {
+    let _bisecting = install_activity::begin().await;
+    let sessions_table = local_db_service.bisect_sessions_table();
+    let Some(session) = sessions_table.get(&revit_version).await? else {
+        return Ok(());
+    };
+    // A finished session was restored already, and the user may have disabled the culprit since
+    if session.culprit.is_none() {
+        let local_addins = local_addins_service.get_local_addins()?;
+        bisect::restore(&session, &local_addins)?;
+    }
+    sessions_table.remove(&revit_version).await
+}
+
+/// Returns the Revit versions that are installed on this machine
+#[tauri::command]
+pub async fn get_revit_versions(
+    revit_installations_service: State<'_, Arc<RevitInstallationsService>>,
+) -> Result<Vec<String>, String> {
+    Ok(revit_installations_service.installed_versions())
+}
+
+/// Returns what the app has installed, one receipt per Revit version and scope
+#[tauri::command]
+pub async fn get_install_receipts(
+    local_db_service: State<'_, Arc<LocalDbService>>,
+) -> Result<Vec<InstallReceiptModel>, String> {
+    local_db_service.install_receipts_table().get_all().await
+}
+
+/// The earlier installs that can be rolled back to. Before an update replaces an addin the app installed, its files
+/// are backed up
+#[tauri::command]
+pub async fn get_rollback_receipts(
+    local_db_service: State<'_, Arc<LocalDbService>>,
+) -> Result<Vec<InstallReceiptModel>, String> {
+    let receipts = local_db_service.install_receipts_table().get_all().await?;
+    Ok(receipts
+        .iter()
+        .filter_map(|receipt| {
+            match install_receipts::read_rollback(Path::new(&receipt.installed_xml_file)) {
+                Ok(previous) => previous,
+                Err(e) => {
+                    warn!("Could not read the backup of {}: {}", receipt.name, e);
+                    None
+                }
+            }
+        })
+        .collect())
+}
+
+/// Puts back the version of the addin that was installed before its last update. The updater doesn't install the
+/// version it was rolled back from again
+///
+/// Returns the receipt of the install that is now in place
+#[tauri::command]
+pub async fn roll_back_addin(
+    local_db_service: State<'_, Arc<LocalDbService>>,
+    installed_xml_file: String,
+) -> Result<InstallReceiptModel, String> {
+    let _rolling_back = install_activity::begin().await;
+    let receipts_table = local_db_service.install_receipts_table();
+    let receipt = receipts_table
+        .get(&installed_xml_file)
+        .await?
+        .ok_or_else(|| format!("{} wasn't installed by the app", installed_xml_file))?;
+    let running_revit = revit_check::get_running_revit_versions().await?;
+    if pending_updates::is_deferred(&running_revit, Some(&receipt.revit_version)) {
+        return Err(format!(
+            "Close Revit {} before rolling back {}",
+            receipt.revit_version, receipt.name
+        ));
+    }
+    let previous = install_receipts::roll_back(&receipt)
+        .map_err(|e| format!("Failed to roll back {}: {}", receipt.name, e))?;
+    receipts_table.record(previous.clone()).await?;
+    Ok(previous)
+}
+
+/// Uninstalls exactly what the install receipt lists. Addins without a receipt, e.g. ones installed before
+/// receipts existed, are found from the paths of the addin instead. Foreign addins are never uninstalled
+///
+/// Returns one result per addin, Revit version and scope. Each of them is removed completely or not at all, and
+/// the ones Revit holds on to are finished once Revit closes
+#[tauri::command]
+pub async fn uninstall_addins(
+    local_addins_service: State<'_, Arc<LocalAddinsService>>,
+    addins_registry_service: State<'_, Arc<LocalAddinsRegistryService>>,
+    local_db_service: State<'_, Arc<LocalDbService>>,
+    uninstall_requests: Vec<UninstallAddinRequestModel>,
+) -> Result<Vec<UninstallResultModel>, String> {
+    let _uninstalling = install_activity::begin().await;
+    let receipts_table = local_db_service.install_receipts_table();
+    let registry_addins = addins_registry_service.get_addins_or_snapshot().await.ok();
+    let receipts = receipts_table.get_all().await?;
+    let running_revit = revit_check::get_running_revit_versions()
+        .await
+        .unwrap_or_else(|e| {
+            // Then the addins that are held by Revit wait, as if every Revit version was running
+            warn!("Could not find the running Revit versions: {}", e);
+            revit_check::RunningRevitVersions::unknown()
+        });
+
+    let mut results = Vec::new();
+    let mut pending_targets = Vec::new();
     for uninstall_request in uninstall_requests {
-        LocalAddinsService::uninstall_addin(
-            &uninstall_request.addin,
-            &uninstall_request.for_revit_versions,
-        )
+        let addin = &uninstall_request.addin;
+        let is_foreign = management::classify(addin, registry_addins.as_deref(), &receipts)
+            == AddinManagement::Foreign;
+        let scopes = match uninstall_request.install_scope.or(addin.install_scope) {
+            Some(scope) => vec![scope],
+            None => InstallScope::ALL.to_vec(),
+        };
+        for scope in scopes {
+            for version in &uninstall_request.for_revit_versions {
+                let target = match receipts_table.find(&addin.addin_id, version, scope).await {
+                    Ok(Some(receipt)) => UninstallTargetModel::from_receipt(&receipt),
+                    // Without a receipt, every scope is tried, but only the ones it is installed in are reported
+                    Ok(None) => {
+                        match find_installed_target(&local_addins_service, addin, version, scope) {
+                            Some(target) => target,
+                            None => continue,
+                        }
+                    }
+                    // Whether the receipt would list other files isn't known, so nothing is removed
+                    Err(e) => {
+                        if let Some(target) =
+                            find_installed_target(&local_addins_service, addin, version, scope)
+                        {
+                            results.push(UninstallResultModel {
+                                target,
+                                status: UninstallStatus::Failed,
+                                error: Some(format!("Failed to read the install receipt: {}", e)),
+                            });
+                        }
+                        continue;
+                    }
+                };
+                if is_foreign {
+                    results.push(UninstallResultModel {
+                        target,
+                        status: UninstallStatus::Failed,
+                        error: Some(format!(
+                            "{} wasn't installed from the registry, so it can't be uninstalled here",
+                            addin.name
+                        )),
+                    });
+                    continue;
+                }
+                let result = match uninstall::remove_target(&target) {
+                    // The files are gone either way, a receipt left behind only names an addin that isn't there
+                    Ok(()) => {
+                        let error = receipts_table
+                            .remove(&target.xml_file)
+                            .await
+                            .err()
+                            .map(|e| format!("Failed to remove the install receipt: {}", e));
+                        UninstallResultModel {
+                            target,
+                            status: UninstallStatus::Removed,
+                            error,
+                        }
+                    }
+                    // Most likely Revit loaded the addin and holds its DLLs
+                    Err(e)
+                        if pending_updates::is_deferred(
+                            &running_revit,
+                            Some(&target.revit_version),
+                        ) =>
+                    {
+                        pending_targets.push(target.clone());
+                        UninstallResultModel {
+                            target,
+                            status: UninstallStatus::Pending,
+                            error: Some(e.to_string()),
+                        }
+                    }
+                    Err(e) => UninstallResultModel {
+                        target,
+                        status: UninstallStatus::Failed,
+                        error: Some(e.to_string()),
+                    },
+                };
+                results.push(result);
+            }
+        }
+    }
+    // The files of the uninstalled addins are gone already, so the results are returned either way
+    if let Err(e) =
+        pending_updates::store_pending_uninstalls(&local_db_service, pending_targets).await
+    {
+        error!("Failed to save the pending uninstalls: {}", e);
+        for result in results
+            .iter_mut()
+            .filter(|result| result.status == UninstallStatus::Pending)
+        {
+            result.error = Some(format!(
+                "It won't be finished once Revit closes, uninstall it again then: {}",
+                e
+            ));
+        }
+    }
+    Ok(results)
+}
+
+/// Compares each of the given local addins with its source in the registry, file by file
+///
+/// Addins that share a .addin file are only verified once.
+/// If the source can't be found in the registry, the addin is compared with its install receipt instead
+#[tauri::command]
+pub async fn verify_local_addins(
+    addins_registry_service: State<'_, Arc<LocalAddinsRegistryService>>,
+    local_db_service: State<'_, Arc<LocalDbService>>,
+    addins: Vec<AddinModel>,
+) -> Result<Vec<AddinVerificationModel>, String> {
+    // Offline without a snapshot, the receipts are all there is to compare with
+    let registry_addins = addins_registry_service
+        .get_addins_or_snapshot()
+        .await
+        .unwrap_or_default();
+    let receipts = local_db_service.install_receipts_table().get_all().await?;
+
+    let mut verifications = Vec::new();
+    for addin in dedup_by_addin_file(addins) {
+        // Receipts are keyed by where the .addin file goes when enabled
+        let xml_file = disabling::enabled_xml_path(Path::new(&addin.path_to_addin_xml_file));
+        let receipt = receipts
+            .iter()
+            .find(|receipt| Path::new(&receipt.installed_xml_file) == xml_file);
+        let verification =
+            match integrity::find_registry_source(&addin, &registry_addins, &receipts) {
+                Some(registry_addin) => integrity::verify_addin(&addin, Some(registry_addin)),
+                None => match receipt {
+                    Some(receipt) => integrity::verify_against_receipt(&addin, receipt),
+                    None => integrity::verify_addin(&addin, None),
+                },
+            };
+        verifications.push(verification);
+    }
+    Ok(verifications)
+}
+
+/// Restores the missing and modified files of each of the given local addins from the registry
+///
+/// Returns one result per addin. An addin that can't be repaired doesn't keep the others from being repaired
+#[tauri::command]
+pub async fn repair_local_addins(
+    addins_registry_service: State<'_, Arc<LocalAddinsRegistryService>>,
+    local_db_service: State<'_, Arc<LocalDbService>>,
+    addins: Vec<AddinModel>,
+) -> Result<Vec<AddinRepairModel>, String> {
+    let _repairing = install_activity::begin().await;
+    let registry_addins = addins_registry_service
+        .get_addins_or_snapshot()
+        .await
         .map_err(|e| e.to_string())?;
+    let receipts = local_db_service.install_receipts_table().get_all().await?;
+    Ok(dedup_by_addin_file(addins)
+        .iter()
+        .map(
+            |addin| match integrity::find_registry_source(addin, &registry_addins, &receipts) {
+                Some(registry_addin) => integrity::repair_addin(addin, registry_addin)
+                    .unwrap_or_else(|e| {
+                        AddinRepairModel::failed(
+                            integrity::verify_addin(addin, Some(registry_addin)),
+                            e,
+                        )
+                    }),
+                None => AddinRepairModel::failed(
+                    integrity::verify_addin(addin, None),
+                    format!("{} could not be found in the registry", addin.name),
+                ),
+            },
+        )
+        .collect())
+}
+
+/// Where the addin is installed for the Revit version and scope, found from its paths. None if it isn't installed there
+fn find_installed_target(
+    local_addins_service: &LocalAddinsService,
+    addin: &AddinModel,
+    version: &str,
+    scope: InstallScope,
+) -> Option<UninstallTargetModel> {
+    match local_addins_service.uninstall_targets(addin, &[version.to_string()], scope) {
+        Ok(targets) => targets.into_iter().find(uninstall::is_installed),
+        Err(e) => {
+            warn!("Could not find where {} is installed: {}", addin.name, e);
+            None
+        }
+    }
+}
+
+fn dedup_by_addin_file(mut addins: Vec<AddinModel>) -> Vec<AddinModel> {
+    let mut seen = std::collections::HashSet::new();
+    addins.retain(|addin| seen.insert(addin.path_to_addin_xml_file.clone()));
+    addins
+}
+
+/// Fails if any of the addins is foreign, since the app never changes those
+async fn ensure_none_foreign(
+    addins_registry_service: &LocalAddinsRegistryService,
+    local_db_service: &LocalDbService,
+    addins: &[AddinModel],
+    action: &str,
+) -> Result<(), String> {
+    let registry_addins = addins_registry_service.get_addins_or_snapshot().await.ok();
+    let receipts = local_db_service.install_receipts_table().get_all().await?;
+    match addins.iter().find(|addin| {
+        management::classify(addin, registry_addins.as_deref(), &receipts)
+            == AddinManagement::Foreign
+    }) {
+        Some(foreign) => Err(format!(
+            "{} wasn't installed from the registry, so it can't be {} here",
+            foreign.name, action
+        )),
+        None => Ok(()),
     }
-    Ok(())
 }
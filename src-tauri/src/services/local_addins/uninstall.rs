@@ -0,0 +1,207 @@
+use log::{error, info};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::services::local_addins::{
+    disabling, install_manifest, install_receipts,
+    models::uninstall_result_model::UninstallTargetModel,
+};
+
+/// Files being removed are moved here first, e.g. ...\Addins\2025\.uninstalling\<id>\MyAddin. Revit doesn't look
+/// in subfolders for .addin files, and a rename on the same volume either moves everything or fails
+pub const STAGING_FOLDER_NAME: &str = ".uninstalling";
+
+/// Removes the .addin file, its disabled copy, the installed DLL files, the install manifest and the rollback backup
+/// of the target, or none of them. Files the app didn't install are left in the DLL folder, which is only removed once it is empty
+///
+/// Everything is moved to the staging folder first. If a file is locked, e.g. because Revit loaded the addin, the
+/// moves are undone and the addin stays fully installed. The staging folder is deleted afterwards, and whatever is
+/// still locked then is deleted on a later uninstall
+pub fn remove_target(target: &UninstallTargetModel) -> io::Result<()> {
+    let xml_file = Path::new(&target.xml_file);
+    let version_dir = xml_file.parent().ok_or_else(|| invalid_path(xml_file))?;
+    purge_staged_removals(version_dir);
+
+    let dll_folder = Path::new(&target.dll_folder);
+    let mut paths = vec![xml_file.to_path_buf()];
+    paths.extend(disabling::disabled_xml_path(xml_file));
+    paths.extend(dll_paths(target));
+    paths.extend(install_manifest::manifest_path(dll_folder));
+    paths.extend(install_receipts::rollback_dir(xml_file));
+    let paths: Vec<PathBuf> = paths.into_iter().filter(|path| path.exists()).collect();
+    if paths.is_empty() {
+        return Ok(());
+    }
+
+    let staging_dir = version_dir
+        .join(STAGING_FOLDER_NAME)
+        .join(uuid::Uuid::new_v4().to_string());
+    fs::create_dir_all(&staging_dir)?;
+    let mut moved: Vec<(PathBuf, PathBuf)> = Vec::new();
+    for (index, path) in paths.into_iter().enumerate() {
+        // Numbered, since the enabled and disabled .addin files have the same name
+        let staged = staging_dir.join(index.to_string());
+        if let Err(e) = fs::rename(&path, &staged) {
+            for (original, staged) in moved.iter().rev() {
+                if let Err(undo_error) = fs::rename(staged, original) {
+                    error!(
+                        "Failed to move {} back to {}: {}",
+                        staged.display(),
+                        original.display(),
+                        undo_error
+                    );
+                }
+            }
+            let _ = fs::remove_dir(&staging_dir);
+            return Err(e);
+        }
+        moved.push((path, staged));
+    }
+    // Only removed if nothing but the installed files were in them
+    if target.dll_files.is_some() {
+        for (original, _) in &moved {
+            if original.starts_with(dll_folder) {
+                let _ = install_manifest::remove_empty_parents(dll_folder, original);
+            }
+        }
+        let _ = fs::remove_dir(dll_folder);
+    }
+    // Only removed if the disabled copy and the backup were the last ones in them
+    let _ = fs::remove_dir(version_dir.join(disabling::DISABLED_FOLDER_NAME));
+    let _ = fs::remove_dir(version_dir.join(install_receipts::ROLLBACK_FOLDER_NAME));
+
+    purge_staged_removals(version_dir);
+    Ok(())
+}
+
+/// Whether any of the target's files are still there
+pub fn is_installed(target: &UninstallTargetModel) -> bool {
+    let xml_file = Path::new(&target.xml_file);
+    xml_file.exists()
+        || disabling::disabled_xml_path(xml_file).is_some_and(|path| path.exists())
+        || dll_paths(target).iter().any(|path| path.exists())
+}
+
+/// The installed files of the DLL folder, or the whole folder if they weren't recorded
+fn dll_paths(target: &UninstallTargetModel) -> Vec<PathBuf> {
+    let dll_folder = Path::new(&target.dll_folder);
+    match &target.dll_files {
+        Some(files) => files.iter().map(|file| dll_folder.join(file)).collect(),
+        None => vec![dll_folder.to_path_buf()],
+    }
+}
+
+/// Deletes what earlier uninstalls moved to the staging folder. Files that are still locked are left for next time
+pub fn purge_staged_removals(version_dir: &Path) {
+    let staging_root = version_dir.join(STAGING_FOLDER_NAME);
+    let Ok(entries) = fs::read_dir(&staging_root) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        if let Err(e) = fs::remove_dir_all(entry.path()) {
+            info!(
+                "Could not delete {} yet, will try again later: {}",
+                entry.path().display(),
+                e
+            );
+        }
+    }
+    let _ = fs::remove_dir(&staging_root);
+}
+
+fn invalid_path(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("Invalid path: {}", path.display()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::local_addins::models::{
+        install_manifest_model::InstallManifestModel, install_scope_model::InstallScope,
+    };
+
+    #[test]
+    fn test_remove_target_removes_everything_it_installed() {
+        let root = std::env::temp_dir().join(format!("uninstall-{}", uuid::Uuid::new_v4()));
+        let version_dir = root.join("2025");
+        let dll_folder = version_dir.join("MyAddin");
+        fs::create_dir_all(&dll_folder).unwrap();
+        fs::write(dll_folder.join("MyAddin.dll"), "dll").unwrap();
+        fs::create_dir_all(dll_folder.join("Resources")).unwrap();
+        fs::write(dll_folder.join("Resources/Icon.png"), "png").unwrap();
+        install_manifest::write_manifest(
+            &dll_folder,
+            &InstallManifestModel {
+                files: vec!["MyAddin.dll".to_string(), "Resources/Icon.png".to_string()],
+            },
+        )
+        .unwrap();
+        // Both an enabled and a disabled copy, e.g. after installing again while disabled
+        let xml_file = version_dir.join("MyAddin.addin");
+        fs::write(&xml_file, "<RevitAddIns />").unwrap();
+        let disabled_xml_file = disabling::disabled_xml_path(&xml_file).unwrap();
+        fs::create_dir_all(disabled_xml_file.parent().unwrap()).unwrap();
+        fs::write(&disabled_xml_file, "<RevitAddIns />").unwrap();
+
+        let target = UninstallTargetModel {
+            addin_name: "MyAddin".to_string(),
+            addin_id: "my-addin".to_string(),
+            revit_version: "2025".to_string(),
+            install_scope: InstallScope::User,
+            xml_file: xml_file.to_string_lossy().to_string(),
+            dll_folder: dll_folder.to_string_lossy().to_string(),
+            dll_files: Some(vec![
+                "MyAddin.dll".to_string(),
+                "Resources/Icon.png".to_string(),
+            ]),
+        };
+        assert!(is_installed(&target));
+        remove_target(&target).unwrap();
+
+        assert!(!is_installed(&target));
+        assert!(!dll_folder.exists());
+        assert!(!install_manifest::manifest_path(&dll_folder)
+            .unwrap()
+            .exists());
+        assert!(!version_dir.join(STAGING_FOLDER_NAME).exists());
+        // Removing it again has nothing to do
+        remove_target(&target).unwrap();
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_remove_target_keeps_files_it_did_not_install() {
+        let root = std::env::temp_dir().join(format!("uninstall-{}", uuid::Uuid::new_v4()));
+        let version_dir = root.join("2025");
+        let dll_folder = version_dir.join("MyAddin");
+        fs::create_dir_all(dll_folder.join("Logs")).unwrap();
+        fs::write(dll_folder.join("MyAddin.dll"), "dll").unwrap();
+        fs::write(dll_folder.join("Logs/today.log"), "log").unwrap();
+        let xml_file = version_dir.join("MyAddin.addin");
+        fs::write(&xml_file, "<RevitAddIns />").unwrap();
+
+        let target = UninstallTargetModel {
+            addin_name: "MyAddin".to_string(),
+            addin_id: "my-addin".to_string(),
+            revit_version: "2025".to_string(),
+            install_scope: InstallScope::User,
+            xml_file: xml_file.to_string_lossy().to_string(),
+            dll_folder: dll_folder.to_string_lossy().to_string(),
+            dll_files: Some(vec!["MyAddin.dll".to_string()]),
+        };
+        remove_target(&target).unwrap();
+
+        assert!(!is_installed(&target));
+        assert!(!xml_file.exists());
+        assert!(!dll_folder.join("MyAddin.dll").exists());
+        assert!(dll_folder.join("Logs/today.log").exists());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
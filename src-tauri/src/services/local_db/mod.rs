@@ -16,4 +16,32 @@ pub mod tables {
         }
         pub mod tauri_exports;
     }
+
+    pub mod bisect_sessions {
+        pub mod api;
+        pub mod entities {
+            pub mod bisect_session;
+        }
+    }
+
+    pub mod install_receipts {
+        pub mod api;
+        pub mod entities {
+            pub mod install_receipt;
+        }
+    }
+
+    pub mod pending_operations {
+        pub mod api;
+        pub mod entities {
+            pub mod pending_operation;
+        }
+    }
+
+    pub mod update_history {
+        pub mod api;
+        pub mod entities {
+            pub mod update_cycle;
+        }
+    }
 }
@@ -2,32 +2,87 @@ use std::sync::Arc;
 
 use crate::services::app_save::service::AppSaveService;
 
-use super::tables::app_kv_store::api::AppKvStoreTable;
+use super::tables::{
+    app_kv_store::api::AppKvStoreTable, bisect_sessions::api::BisectSessionsTable,
+    install_receipts::api::InstallReceiptsTable, pending_operations::api::PendingOperationsTable,
+    update_history::api::UpdateHistoryTable,
+};
 use sea_orm::DatabaseConnection;
 use sqlx::sqlite::SqlitePool;
 use tauri::AppHandle;
 
 pub struct LocalDbService {
     kv_store_table: AppKvStoreTable,
+    install_receipts_table: InstallReceiptsTable,
+    bisect_sessions_table: BisectSessionsTable,
+    pending_operations_table: PendingOperationsTable,
+    update_history_table: UpdateHistoryTable,
 }
 
 impl LocalDbService {
     // consider using a config here
-    pub async fn new_async(save_service: &AppSaveService, app_handle: AppHandle) -> Self {
+    pub async fn new_async(
+        save_service: &AppSaveService,
+        app_handle: AppHandle,
+    ) -> Result<Self, String> {
         let db_path = save_service.create_path("file_index.db");
         let db_url = format!("sqlite://{}", db_path.to_string_lossy());
 
         // Starts out as a SQLX pool, but 'into' is called to turn it into a Sea ORM database connection
-        let db: Arc<DatabaseConnection> =
-            Arc::new(SqlitePool::connect(&db_url).await.unwrap().into());
+        let pool = SqlitePool::connect(&db_url)
+            .await
+            .map_err(|e| format!("Could not open the local database {}: {}", db_url, e))?;
+        let db: Arc<DatabaseConnection> = Arc::new(pool.into());
 
-        // initialize the tables
         let kv_store_table = AppKvStoreTable::new_async(db.clone(), app_handle).await;
+        Ok(Self::with_tables(db, kv_store_table).await)
+    }
+
+    /// An empty database in memory that doesn't emit any events
+    #[cfg(test)]
+    pub async fn in_memory() -> Self {
+        let db: Arc<DatabaseConnection> = Arc::new(
+            sea_orm::Database::connect("sqlite::memory:")
+                .await
+                .expect("Could not open an in-memory database"),
+        );
+        let kv_store_table = AppKvStoreTable::without_events(db.clone()).await;
+        Self::with_tables(db, kv_store_table).await
+    }
 
-        Self { kv_store_table }
+    // initialize the tables
+    async fn with_tables(db: Arc<DatabaseConnection>, kv_store_table: AppKvStoreTable) -> Self {
+        let install_receipts_table = InstallReceiptsTable::new_async(db.clone()).await;
+        let bisect_sessions_table = BisectSessionsTable::new_async(db.clone()).await;
+        let pending_operations_table = PendingOperationsTable::new_async(db.clone()).await;
+        let update_history_table = UpdateHistoryTable::new_async(db.clone()).await;
+
+        Self {
+            kv_store_table,
+            install_receipts_table,
+            bisect_sessions_table,
+            pending_operations_table,
+            update_history_table,
+        }
     }
 
     pub fn kv_store_table(&self) -> &AppKvStoreTable {
         &self.kv_store_table
     }
+
+    pub fn install_receipts_table(&self) -> &InstallReceiptsTable {
+        &self.install_receipts_table
+    }
+
+    pub fn bisect_sessions_table(&self) -> &BisectSessionsTable {
+        &self.bisect_sessions_table
+    }
+
+    pub fn pending_operations_table(&self) -> &PendingOperationsTable {
+        &self.pending_operations_table
+    }
+
+    pub fn update_history_table(&self) -> &UpdateHistoryTable {
+        &self.update_history_table
+    }
 }
@@ -1,3 +1,4 @@
+use log::warn;
 use sea_orm::{ConnectionTrait, DatabaseConnection, EntityTrait, Schema};
 
 pub async fn generate_table<E>(
@@ -21,6 +22,6 @@ where
     E: EntityTrait,
 {
     if let Err(err) = generate_table(db, entity).await {
-        println!("Warning: Error generating table: {}", err);
+        warn!("Error generating table: {}", err);
     }
 }
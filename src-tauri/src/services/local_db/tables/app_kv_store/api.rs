@@ -7,7 +7,7 @@ use super::{
 use crate::{
     models::kv_store_value::KvStoreValue, services::local_db::table_creator::generate_table_lenient,
 };
-use print_err::print_err;
+use log::{debug, error};
 use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
 use serde::{de::DeserializeOwned, Serialize};
 use std::{fmt::Debug, sync::Arc};
@@ -35,12 +35,24 @@ impl AppKvStoreTable {
         }
     }
 
+    /// Creates a table that doesn't emit any events to the UI
+    #[cfg(test)]
+    pub async fn without_events(db: Arc<DatabaseConnection>) -> Self {
+        generate_table_lenient(&db, kv_pair::Entity).await;
+
+        Self {
+            db,
+            tauri_subscriptions: TauriSubscriptionList::without_events(),
+            subscriptions: BackendSubscriptionList::new(),
+        }
+    }
+
     /// Set a value in the key-value store
     pub async fn set<T>(&self, key: String, value: T) -> Result<(), String>
     where
         T: Serialize + Debug,
     {
-        println!("Setting key: {} with value: {:?}", key, value);
+        debug!("Setting key: {} with value: {:?}", key, value);
         let value = serde_json::to_value(value).map_err(|err| err.to_string())?;
 
         // Emit the data to Tauri subscribers, if any:
@@ -78,6 +90,19 @@ impl AppKvStoreTable {
             .map(|model| model.value))
     }
 
+    /// Every key in the store with its raw JSON value, sorted by key
+    pub async fn get_all(&self) -> Result<Vec<(String, serde_json::Value)>, String> {
+        let mut pairs: Vec<_> = kv_pair::Entity::find()
+            .all(&*self.db)
+            .await
+            .map_err(|err| err.to_string())?
+            .into_iter()
+            .map(|model| (model.key, model.value))
+            .collect();
+        pairs.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(pairs)
+    }
+
     /// Retrieve the value with the certain key in the store, given that it exists and it is in the format you want it in
     ///
     /// Not necessarily an expensive operation, as results just get cached for future requests. Though, the underlying JSON has to be deserialized every time.
@@ -150,10 +175,9 @@ impl AppKvStoreTable {
                     }
                     None => {
                         // The key does not exist in the database or the temporary storage
-                        print_err(
-                            "AppKvStore:GetOrCreate",
-                            self.set(key.to_string(), default.clone()).await,
-                        );
+                        if let Err(err) = self.set(key.to_string(), default.clone()).await {
+                            error!("AppKvStore:GetOrCreate: {}", err);
+                        }
                         serde_json::to_value(default)
                             .expect("Could not convert default value to JSON")
                     }
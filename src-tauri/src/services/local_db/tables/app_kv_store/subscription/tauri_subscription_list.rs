@@ -1,3 +1,4 @@
+use log::{debug, error};
 use std::sync::Arc;
 
 use tauri::{AppHandle, Emitter};
@@ -13,14 +14,24 @@ pub struct EmitSubscription {
 /// Freely cloneable as all of the underlying data is cheap
 pub struct TauriSubscriptionList {
     events: Arc<RwLock<Vec<EmitSubscription>>>,
-    app_handle: AppHandle,
+    /// None when running without a UI, e.g. in tests
+    app_handle: Option<AppHandle>,
 }
 
 impl TauriSubscriptionList {
     pub fn new(app_handle: AppHandle) -> Self {
         Self {
             events: Arc::new(RwLock::new(Vec::new())),
-            app_handle,
+            app_handle: Some(app_handle),
+        }
+    }
+
+    /// Creates a list that doesn't emit any events
+    #[cfg(test)]
+    pub fn without_events() -> Self {
+        Self {
+            events: Arc::new(RwLock::new(Vec::new())),
+            app_handle: None,
         }
     }
 
@@ -49,14 +60,17 @@ impl TauriSubscriptionList {
     /// "kv_subscription:{uuid}"
     /// ```
     pub async fn emit_to_subscribers(&self, key: &str, value: &serde_json::Value) {
+        let Some(app_handle) = &self.app_handle else {
+            return;
+        };
         for event in self.events.read().await.iter() {
             if event.key == key {
-                println!(
+                debug!(
                     "AppKVStore - TauriSubscriptionList: Emitting to Tauri subscribers: {}",
                     event.event_name
                 );
-                if let Err(err) = self.app_handle.emit(&event.event_name, value) {
-                    println!("AppKVStore - TauriSubscriptionList: Error emitting to Tauri subscribers: {}",err);
+                if let Err(err) = app_handle.emit(&event.event_name, value) {
+                    error!("AppKVStore - TauriSubscriptionList: Error emitting to Tauri subscribers: {}",err);
                 }
             }
         }
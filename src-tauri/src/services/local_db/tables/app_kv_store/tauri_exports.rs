@@ -1,3 +1,4 @@
+use log::debug;
 use std::sync::Arc;
 
 use tauri::State;
@@ -12,7 +13,7 @@ pub async fn kv_store_set(
     value: serde_json::Value,
     local_db: State<'_, Arc<LocalDbService>>,
 ) -> Result<(), String> {
-    println!("kv_store_set: {} = {:?}", key, value);
+    debug!("kv_store_set: {} = {:?}", key, value);
     local_db.kv_store_table().set(key, value).await?;
     Ok(())
 }
@@ -22,7 +23,7 @@ pub async fn kv_store_get(
     key: String,
     local_db: State<'_, Arc<LocalDbService>>,
 ) -> Result<Option<serde_json::Value>, String> {
-    println!("kv_store_get: {}", key);
+    debug!("kv_store_get: {}", key);
     let value = local_db.kv_store_table().get(&key).await?;
     Ok(value)
 }
@@ -40,7 +41,7 @@ pub async fn kv_store_subscribe_to_key(
     key: String,
     local_db: State<'_, Arc<LocalDbService>>,
 ) -> Result<FrontendKvSubscriptionModel, String> {
-    println!("kv_store_subscribe_to_key: {}", key);
+    debug!("kv_store_subscribe_to_key: {}", key);
     let last_data: Option<serde_json::Value> = local_db.kv_store_table().get(&key).await?;
     let identifier = local_db.kv_store_table().tauri_subscribe_to_key(&key).await;
     Ok(FrontendKvSubscriptionModel {
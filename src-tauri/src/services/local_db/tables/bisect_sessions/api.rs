@@ -0,0 +1,71 @@
+use super::entities::bisect_session;
+use crate::services::{
+    local_addins::models::bisect_session_model::BisectSessionModel,
+    local_db::table_creator::generate_table_lenient,
+};
+use sea_orm::{DatabaseConnection, EntityTrait};
+use std::sync::Arc;
+
+#[derive(Clone)]
+/// The bisect sessions in progress, so that they survive restarts between Revit launches
+pub struct BisectSessionsTable {
+    db: Arc<DatabaseConnection>,
+}
+
+impl BisectSessionsTable {
+    pub async fn new_async(db: Arc<DatabaseConnection>) -> Self {
+        generate_table_lenient(&db, bisect_session::Entity).await;
+
+        Self { db }
+    }
+
+    pub async fn save(&self, session: &BisectSessionModel) -> Result<(), String> {
+        let value = serde_json::to_value(session).map_err(|err| err.to_string())?;
+
+        let query = r#"
+            INSERT INTO bisect_sessions (revit_version, session)
+            VALUES (?, ?)
+            ON CONFLICT(revit_version) DO UPDATE SET
+                session = excluded.session
+        "#;
+
+        sqlx::query(query)
+            .bind(&session.revit_version)
+            .bind(&value)
+            .execute(self.db.get_sqlite_connection_pool())
+            .await
+            .map_err(|err| err.to_string())?;
+        Ok(())
+    }
+
+    pub async fn get(&self, revit_version: &str) -> Result<Option<BisectSessionModel>, String> {
+        bisect_session::Entity::find_by_id(revit_version.to_string())
+            .one(&*self.db)
+            .await
+            .map_err(|err| err.to_string())?
+            .map(Self::to_session)
+            .transpose()
+    }
+
+    pub async fn get_all(&self) -> Result<Vec<BisectSessionModel>, String> {
+        bisect_session::Entity::find()
+            .all(&*self.db)
+            .await
+            .map_err(|err| err.to_string())?
+            .into_iter()
+            .map(Self::to_session)
+            .collect()
+    }
+
+    pub async fn remove(&self, revit_version: &str) -> Result<(), String> {
+        bisect_session::Entity::delete_by_id(revit_version.to_string())
+            .exec(&*self.db)
+            .await
+            .map_err(|err| err.to_string())?;
+        Ok(())
+    }
+
+    fn to_session(model: bisect_session::Model) -> Result<BisectSessionModel, String> {
+        serde_json::from_value(model.session).map_err(|err| err.to_string())
+    }
+}
@@ -0,0 +1,22 @@
+use sea_orm::entity::prelude::*;
+use serde::Serialize;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize)]
+#[sea_orm(table_name = "bisect_sessions")]
+#[serde(rename_all = "camelCase")]
+pub struct Model {
+    /// There is at most one session per Revit version
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub revit_version: String,
+    /// The full `BisectSessionModel`
+    pub session: serde_json::Value,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter)]
+pub enum Relation {}
+impl RelationTrait for Relation {
+    fn def(&self) -> RelationDef {
+        panic!("No relations")
+    }
+}
+impl ActiveModelBehavior for ActiveModel {}
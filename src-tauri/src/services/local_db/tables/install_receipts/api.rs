@@ -0,0 +1,191 @@
+use super::entities::install_receipt;
+use crate::services::{
+    local_addins::models::{
+        install_receipt_model::InstallReceiptModel, install_scope_model::InstallScope,
+    },
+    local_db::table_creator::generate_table_lenient,
+};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use std::sync::Arc;
+
+#[derive(Clone)]
+/// What the app installed, where and when. There is one receipt per installed .addin file
+pub struct InstallReceiptsTable {
+    db: Arc<DatabaseConnection>,
+}
+
+impl InstallReceiptsTable {
+    pub async fn new_async(db: Arc<DatabaseConnection>) -> Self {
+        generate_table_lenient(&db, install_receipt::Entity).await;
+
+        Self { db }
+    }
+
+    /// Saves the receipt. If there already is one for the same .addin file, its install time and actor are kept
+    pub async fn record(&self, receipt: InstallReceiptModel) -> Result<(), String> {
+        let value = serde_json::to_value(&receipt).map_err(|err| err.to_string())?;
+
+        let query = r#"
+            INSERT INTO install_receipts (installed_xml_file, addin_id, receipt)
+            VALUES (?, ?, ?)
+            ON CONFLICT(installed_xml_file) DO UPDATE SET
+                addin_id = excluded.addin_id,
+                receipt = json_set(
+                    excluded.receipt,
+                    '$.installedAt', json_extract(install_receipts.receipt, '$.installedAt'),
+                    '$.installedBy', json_extract(install_receipts.receipt, '$.installedBy')
+                )
+        "#;
+
+        sqlx::query(query)
+            .bind(&receipt.installed_xml_file)
+            .bind(&receipt.addin_id)
+            .bind(&value)
+            .execute(self.db.get_sqlite_connection_pool())
+            .await
+            .map_err(|err| err.to_string())?;
+        Ok(())
+    }
+
+    pub async fn get(
+        &self,
+        installed_xml_file: &str,
+    ) -> Result<Option<InstallReceiptModel>, String> {
+        install_receipt::Entity::find_by_id(installed_xml_file.to_string())
+            .one(&*self.db)
+            .await
+            .map_err(|err| err.to_string())?
+            .map(Self::to_receipt)
+            .transpose()
+    }
+
+    pub async fn get_all(&self) -> Result<Vec<InstallReceiptModel>, String> {
+        install_receipt::Entity::find()
+            .all(&*self.db)
+            .await
+            .map_err(|err| err.to_string())?
+            .into_iter()
+            .map(Self::to_receipt)
+            .collect()
+    }
+
+    /// The receipt of the addin installed for the given Revit version and scope
+    pub async fn find(
+        &self,
+        addin_id: &str,
+        revit_version: &str,
+        install_scope: InstallScope,
+    ) -> Result<Option<InstallReceiptModel>, String> {
+        let receipts = install_receipt::Entity::find()
+            .filter(install_receipt::Column::AddinId.eq(addin_id))
+            .all(&*self.db)
+            .await
+            .map_err(|err| err.to_string())?
+            .into_iter()
+            .map(Self::to_receipt)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(receipts.into_iter().find(|receipt| {
+            receipt.revit_version == revit_version && receipt.install_scope == install_scope
+        }))
+    }
+
+    pub async fn remove(&self, installed_xml_file: &str) -> Result<(), String> {
+        install_receipt::Entity::delete_by_id(installed_xml_file.to_string())
+            .exec(&*self.db)
+            .await
+            .map_err(|err| err.to_string())?;
+        Ok(())
+    }
+
+    fn to_receipt(model: install_receipt::Model) -> Result<InstallReceiptModel, String> {
+        serde_json::from_value(model.receipt).map_err(|err| err.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::local_addins::models::install_receipt_model::{
+        InstallActor, ReceiptFileModel,
+    };
+
+    async fn table() -> InstallReceiptsTable {
+        let db = sea_orm::Database::connect("sqlite::memory:").await.unwrap();
+        InstallReceiptsTable::new_async(Arc::new(db)).await
+    }
+
+    fn receipt(revit_version: &str, version: &str) -> InstallReceiptModel {
+        let version_dir = format!("C:\\Addins\\{}", revit_version);
+        InstallReceiptModel {
+            installed_xml_file: format!("{}\\MyAddin.addin", version_dir),
+            installed_dll_folder: format!("{}\\MyAddin", version_dir),
+            source_xml_file: "R:\\Registry\\MyAddin.addin".to_string(),
+            source_dll_folder: "R:\\Registry\\MyAddin".to_string(),
+            addin_id: "my-addin".to_string(),
+            name: "MyAddin".to_string(),
+            vendor: "Vendor".to_string(),
+            version: version.to_string(),
+            revit_version: revit_version.to_string(),
+            install_scope: InstallScope::User,
+            files: vec![ReceiptFileModel {
+                relative_path: "MyAddin/MyAddin.dll".to_string(),
+                sha256: version.to_string(),
+            }],
+            installed_by: InstallActor::User,
+            installed_at: "2025-01-01T00:00:00+00:00".to_string(),
+            updated_at: "2025-01-01T00:00:00+00:00".to_string(),
+            rolled_back_from: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_record_keeps_the_install_time_and_actor() {
+        let table = table().await;
+        table.record(receipt("2025", "1.0.0")).await.unwrap();
+
+        let mut update = receipt("2025", "1.1.0");
+        update.installed_by = InstallActor::AutoUpdater;
+        update.installed_at = "2025-02-01T00:00:00+00:00".to_string();
+        update.updated_at = "2025-02-01T00:00:00+00:00".to_string();
+        table.record(update.clone()).await.unwrap();
+
+        let saved = table
+            .get(&update.installed_xml_file)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(saved.version, "1.1.0");
+        assert_eq!(saved.files, update.files);
+        assert_eq!(saved.updated_at, update.updated_at);
+        assert_eq!(saved.installed_at, "2025-01-01T00:00:00+00:00");
+        assert_eq!(saved.installed_by, InstallActor::User);
+        assert_eq!(table.get_all().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_find_and_remove() {
+        let table = table().await;
+        table.record(receipt("2024", "1.0.0")).await.unwrap();
+        table.record(receipt("2025", "1.0.0")).await.unwrap();
+
+        let found = table
+            .find("my-addin", "2025", InstallScope::User)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.revit_version, "2025");
+        assert!(table
+            .find("my-addin", "2025", InstallScope::Machine)
+            .await
+            .unwrap()
+            .is_none());
+
+        table.remove(&found.installed_xml_file).await.unwrap();
+        assert!(table
+            .find("my-addin", "2025", InstallScope::User)
+            .await
+            .unwrap()
+            .is_none());
+        assert_eq!(table.get_all().await.unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,23 @@
+use sea_orm::entity::prelude::*;
+use serde::Serialize;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize)]
+#[sea_orm(table_name = "install_receipts")]
+#[serde(rename_all = "camelCase")]
+pub struct Model {
+    /// The installed .addin file. There is one receipt per Revit version and scope the addin is installed in
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub installed_xml_file: String,
+    pub addin_id: String,
+    /// The full `InstallReceiptModel`
+    pub receipt: serde_json::Value,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter)]
+pub enum Relation {}
+impl RelationTrait for Relation {
+    fn def(&self) -> RelationDef {
+        panic!("No relations")
+    }
+}
+impl ActiveModelBehavior for ActiveModel {}
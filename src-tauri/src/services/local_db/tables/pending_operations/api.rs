@@ -0,0 +1,99 @@
+use super::entities::pending_operation;
+use crate::services::{
+    addin_updater::models::PendingOperationModel, local_db::table_creator::generate_table_lenient,
+};
+use sea_orm::{DatabaseConnection, EntityTrait};
+use std::sync::Arc;
+
+#[derive(Clone)]
+/// The updates, installs and uninstalls that wait for Revit to close or for a retry. Kept across restarts
+pub struct PendingOperationsTable {
+    db: Arc<DatabaseConnection>,
+}
+
+impl PendingOperationsTable {
+    pub async fn new_async(db: Arc<DatabaseConnection>) -> Self {
+        generate_table_lenient(&db, pending_operation::Entity).await;
+
+        Self { db }
+    }
+
+    /// Saves the operation. If one with the same key is already pending, it is replaced but keeps its creation time
+    /// and failures, so that its backoff carries on
+    pub async fn save(&self, mut operation: PendingOperationModel) -> Result<(), String> {
+        if let Some(previous) = self.get(&operation.key).await? {
+            operation.created_at = previous.created_at;
+            operation.attempts = previous.attempts;
+            operation.last_error = previous.last_error;
+            operation.next_attempt_at = previous.next_attempt_at;
+        }
+        self.write(&operation).await
+    }
+
+    /// Keeps the operation for the next try, along with why it failed. Returns it with its next attempt scheduled
+    pub async fn record_failure(
+        &self,
+        operation: PendingOperationModel,
+        error: String,
+    ) -> Result<PendingOperationModel, String> {
+        let mut operation = match self.get(&operation.key).await? {
+            Some(previous) => previous,
+            None => operation,
+        };
+        operation.fail(error, chrono::Utc::now());
+        self.write(&operation).await?;
+        Ok(operation)
+    }
+
+    pub async fn get(&self, key: &str) -> Result<Option<PendingOperationModel>, String> {
+        pending_operation::Entity::find_by_id(key.to_string())
+            .one(&*self.db)
+            .await
+            .map_err(|err| err.to_string())?
+            .map(Self::to_operation)
+            .transpose()
+    }
+
+    pub async fn get_all(&self) -> Result<Vec<PendingOperationModel>, String> {
+        let mut operations = pending_operation::Entity::find()
+            .all(&*self.db)
+            .await
+            .map_err(|err| err.to_string())?
+            .into_iter()
+            .map(Self::to_operation)
+            .collect::<Result<Vec<_>, _>>()?;
+        operations.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+        Ok(operations)
+    }
+
+    pub async fn remove(&self, key: &str) -> Result<(), String> {
+        pending_operation::Entity::delete_by_id(key.to_string())
+            .exec(&*self.db)
+            .await
+            .map_err(|err| err.to_string())?;
+        Ok(())
+    }
+
+    async fn write(&self, operation: &PendingOperationModel) -> Result<(), String> {
+        let value = serde_json::to_value(operation).map_err(|err| err.to_string())?;
+
+        let query = r#"
+            INSERT INTO pending_operations (key, operation)
+            VALUES (?, ?)
+            ON CONFLICT(key) DO UPDATE SET
+                operation = excluded.operation
+        "#;
+
+        sqlx::query(query)
+            .bind(&operation.key)
+            .bind(&value)
+            .execute(self.db.get_sqlite_connection_pool())
+            .await
+            .map_err(|err| err.to_string())?;
+        Ok(())
+    }
+
+    fn to_operation(model: pending_operation::Model) -> Result<PendingOperationModel, String> {
+        serde_json::from_value(model.operation).map_err(|err| err.to_string())
+    }
+}
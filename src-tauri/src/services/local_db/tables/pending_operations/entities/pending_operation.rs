@@ -0,0 +1,22 @@
+use sea_orm::entity::prelude::*;
+use serde::Serialize;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize)]
+#[sea_orm(table_name = "pending_operations")]
+#[serde(rename_all = "camelCase")]
+pub struct Model {
+    /// See `PendingOperationModel::key`
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub key: String,
+    /// The full `PendingOperationModel`
+    pub operation: serde_json::Value,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter)]
+pub enum Relation {}
+impl RelationTrait for Relation {
+    fn def(&self) -> RelationDef {
+        panic!("No relations")
+    }
+}
+impl ActiveModelBehavior for ActiveModel {}
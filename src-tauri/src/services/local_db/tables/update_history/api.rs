@@ -0,0 +1,96 @@
+use super::entities::update_cycle;
+use crate::services::{
+    addin_updater::models::{UpdateCycleReportModel, UpdateHistoryPageModel},
+    local_db::table_creator::generate_table_lenient,
+};
+use sea_orm::{DatabaseConnection, EntityTrait, PaginatorTrait, QueryOrder, QuerySelect};
+use std::sync::Arc;
+
+/// Only the most recent cycles are kept
+const MAX_CYCLES: u64 = 1000;
+
+#[derive(Clone)]
+/// What each update cycle applied, deferred or failed
+pub struct UpdateHistoryTable {
+    db: Arc<DatabaseConnection>,
+}
+
+impl UpdateHistoryTable {
+    pub async fn new_async(db: Arc<DatabaseConnection>) -> Self {
+        generate_table_lenient(&db, update_cycle::Entity).await;
+
+        Self { db }
+    }
+
+    /// Saves the cycle and drops the oldest ones past the limit
+    pub async fn record(&self, report: &UpdateCycleReportModel) -> Result<(), String> {
+        let value = serde_json::to_value(report).map_err(|err| err.to_string())?;
+
+        let query = r#"
+            INSERT INTO update_history (id, started_at, report)
+            VALUES (?, ?, ?)
+            ON CONFLICT(id) DO UPDATE SET
+                report = excluded.report
+        "#;
+
+        sqlx::query(query)
+            .bind(&report.id)
+            .bind(&report.started_at)
+            .bind(&value)
+            .execute(self.db.get_sqlite_connection_pool())
+            .await
+            .map_err(|err| err.to_string())?;
+
+        let prune_query = r#"
+            DELETE FROM update_history WHERE id NOT IN (
+                SELECT id FROM update_history ORDER BY started_at DESC LIMIT ?
+            )
+        "#;
+
+        sqlx::query(prune_query)
+            .bind(MAX_CYCLES as i64)
+            .execute(self.db.get_sqlite_connection_pool())
+            .await
+            .map_err(|err| err.to_string())?;
+        Ok(())
+    }
+
+    /// Newest first
+    pub async fn get_page(
+        &self,
+        offset: u64,
+        limit: u64,
+    ) -> Result<UpdateHistoryPageModel, String> {
+        let cycles = update_cycle::Entity::find()
+            .order_by_desc(update_cycle::Column::StartedAt)
+            .offset(offset)
+            .limit(limit)
+            .all(&*self.db)
+            .await
+            .map_err(|err| err.to_string())?
+            .into_iter()
+            .map(Self::to_report)
+            .collect::<Result<Vec<_>, _>>()?;
+        let total = update_cycle::Entity::find()
+            .count(&*self.db)
+            .await
+            .map_err(|err| err.to_string())?;
+        Ok(UpdateHistoryPageModel { cycles, total })
+    }
+
+    /// Newest first
+    pub async fn get_all(&self) -> Result<Vec<UpdateCycleReportModel>, String> {
+        update_cycle::Entity::find()
+            .order_by_desc(update_cycle::Column::StartedAt)
+            .all(&*self.db)
+            .await
+            .map_err(|err| err.to_string())?
+            .into_iter()
+            .map(Self::to_report)
+            .collect()
+    }
+
+    fn to_report(model: update_cycle::Model) -> Result<UpdateCycleReportModel, String> {
+        serde_json::from_value(model.report).map_err(|err| err.to_string())
+    }
+}
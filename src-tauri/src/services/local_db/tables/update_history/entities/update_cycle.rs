@@ -0,0 +1,24 @@
+use sea_orm::entity::prelude::*;
+use serde::Serialize;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize)]
+#[sea_orm(table_name = "update_history")]
+#[serde(rename_all = "camelCase")]
+pub struct Model {
+    /// See `UpdateCycleReportModel::id`
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    /// RFC 3339, kept as a column so that the history can be paged in order
+    pub started_at: String,
+    /// The full `UpdateCycleReportModel`
+    pub report: serde_json::Value,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter)]
+pub enum Relation {}
+impl RelationTrait for Relation {
+    fn def(&self) -> RelationDef {
+        panic!("No relations")
+    }
+}
+impl ActiveModelBehavior for ActiveModel {}
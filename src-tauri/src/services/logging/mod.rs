@@ -0,0 +1,91 @@
+use std::path::PathBuf;
+
+use log::LevelFilter;
+use tauri::{plugin::TauriPlugin, Runtime};
+use tauri_plugin_log::{RotationStrategy, Target, TargetKind};
+
+use crate::services::{
+    app_save::service::{AppSavePath, AppSaveService},
+    config::app_paths::AppPaths,
+};
+
+pub mod service;
+pub mod span;
+pub mod tauri_exports;
+
+/// The folder in the app save dir that holds the log files
+pub const LOG_DIR_NAME: &str = "logs";
+const LOG_FILE_NAME: &str = "app";
+/// A log file is rotated once it reaches this size
+const MAX_LOG_FILE_SIZE: u128 = 5 * 1024 * 1024;
+/// How many rotated log files are kept besides the current one
+const MAX_ROTATED_LOG_FILES: usize = 9;
+/// Dependencies that are too chatty below warnings
+const QUIET_TARGETS: [&str; 7] = [
+    "sqlx", "sea_orm", "tao", "wry", "notify", "hyper", "reqwest",
+];
+
+/// Logs to stdout and to rotating files in `log_dir`, or in the OS log folder if it is unknown
+///
+/// Everything is passed on to the targets, the level is set with `log::set_max_level` instead, so that it can be
+/// changed at runtime
+pub fn plugin<R: Runtime>(log_dir: Option<PathBuf>) -> TauriPlugin<R> {
+    let file_target = match log_dir {
+        Some(path) => TargetKind::Folder {
+            path,
+            file_name: Some(LOG_FILE_NAME.to_string()),
+        },
+        None => TargetKind::LogDir {
+            file_name: Some(LOG_FILE_NAME.to_string()),
+        },
+    };
+    let mut builder = tauri_plugin_log::Builder::new()
+        .clear_targets()
+        .targets([Target::new(TargetKind::Stdout), Target::new(file_target)])
+        .level(LevelFilter::Trace)
+        .max_file_size(MAX_LOG_FILE_SIZE)
+        .rotation_strategy(RotationStrategy::KeepSome(MAX_ROTATED_LOG_FILES))
+        .format(|out, message, record| {
+            let span = span::current()
+                .map(|span| format!("[{}]", span))
+                .unwrap_or_default();
+            out.finish(format_args!(
+                "{}[{}][{}]{} {}",
+                chrono::Local::now().format("[%Y-%m-%d][%H:%M:%S%.3f]"),
+                record.target(),
+                record.level(),
+                span,
+                message
+            ))
+        });
+    for target in QUIET_TARGETS {
+        builder = builder.level_for(target, LevelFilter::Warn);
+    }
+    builder.build()
+}
+
+/// The log folder in the app save dir of the current user. None if the app save dir can't be found
+pub fn system_log_dir() -> Option<PathBuf> {
+    let paths = AppPaths::system().ok()?;
+    let app_save_service = AppSaveService::new(AppSavePath::Other(paths.app_save_root));
+    Some(service::log_dir(&app_save_service))
+}
+
+/// Parses a level name such as "info" or "debug"
+pub fn parse_level(level: &str) -> Result<LevelFilter, String> {
+    level
+        .parse::<LevelFilter>()
+        .map_err(|_| format!("Unknown log level {}", level))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_level_ignores_case() {
+        assert_eq!(parse_level("debug"), Ok(LevelFilter::Debug));
+        assert_eq!(parse_level("WARN"), Ok(LevelFilter::Warn));
+        assert!(parse_level("verbose").is_err());
+    }
+}
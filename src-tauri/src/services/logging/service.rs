@@ -0,0 +1,145 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use log::{info, LevelFilter};
+
+use crate::{
+    models::kv_store_value::KvStoreValue,
+    services::{
+        app_save::service::AppSaveService,
+        config::keys::{DEFAULT_LOG_LEVEL, LOG_LEVEL},
+        local_db::service::LocalDbService,
+        logging::{parse_level, LOG_DIR_NAME},
+    },
+};
+
+pub struct LoggingService {
+    log_dir: PathBuf,
+    log_level: KvStoreValue<String>,
+    local_db: Arc<LocalDbService>,
+}
+
+impl LoggingService {
+    pub fn new(app_save_service: &AppSaveService, local_db: Arc<LocalDbService>) -> Self {
+        Self {
+            log_dir: log_dir(app_save_service),
+            log_level: KvStoreValue::new(
+                DEFAULT_LOG_LEVEL.to_string(),
+                LOG_LEVEL,
+                local_db.clone(),
+            ),
+            local_db,
+        }
+    }
+
+    /// Reads the logs from `log_dir` instead of the app's save folder
+    #[cfg(test)]
+    pub fn with_log_dir(log_dir: PathBuf, local_db: Arc<LocalDbService>) -> Self {
+        Self {
+            log_dir,
+            log_level: KvStoreValue::new(
+                DEFAULT_LOG_LEVEL.to_string(),
+                LOG_LEVEL,
+                local_db.clone(),
+            ),
+            local_db,
+        }
+    }
+
+    /// Applies the level stored in the config. Until then, everything at info and above is logged
+    pub async fn apply_stored_level(&self) -> Result<(), String> {
+        let level = parse_level(&self.log_level.get_data_updated().await?)?;
+        log::set_max_level(level);
+        Ok(())
+    }
+
+    pub fn get_level(&self) -> LevelFilter {
+        log::max_level()
+    }
+
+    /// Changes the level right away and keeps it for the next start
+    pub async fn set_level(&self, level: &str) -> Result<(), String> {
+        let level = parse_level(level)?;
+        self.local_db
+            .kv_store_table()
+            .set(LOG_LEVEL.to_string(), level.to_string().to_lowercase())
+            .await?;
+        log::set_max_level(level);
+        info!("Log level set to {}", level);
+        Ok(())
+    }
+
+    /// The log files written in the last `max_age`, oldest first
+    pub fn recent_log_files(&self, max_age: Duration) -> Result<Vec<PathBuf>, String> {
+        recent_log_files(&self.log_dir, SystemTime::now() - max_age)
+    }
+
+    /// Writes the log files of the last `max_age` one after another into a single file
+    pub fn bundle_recent_logs(&self, max_age: Duration, destination: &Path) -> Result<(), String> {
+        let mut bundle = String::new();
+        for log_file in self.recent_log_files(max_age)? {
+            let contents = fs::read(&log_file)
+                .map_err(|e| format!("Could not read {}: {}", log_file.display(), e))?;
+            bundle.push_str(&format!("===== {} =====\n", log_file.display()));
+            bundle.push_str(&String::from_utf8_lossy(&contents));
+            bundle.push('\n');
+        }
+        fs::write(destination, bundle)
+            .map_err(|e| format!("Could not write {}: {}", destination.display(), e))
+    }
+}
+
+/// Where the log files are written, in the app save dir
+pub fn log_dir(app_save_service: &AppSaveService) -> PathBuf {
+    app_save_service.get_save_path().join(LOG_DIR_NAME)
+}
+
+fn recent_log_files(log_dir: &Path, modified_since: SystemTime) -> Result<Vec<PathBuf>, String> {
+    if !log_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let entries = fs::read_dir(log_dir)
+        .map_err(|e| format!("Could not read {}: {}", log_dir.display(), e))?;
+    let mut log_files: Vec<(SystemTime, PathBuf)> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "log"))
+        .filter_map(|entry| {
+            let modified = entry.metadata().ok()?.modified().ok()?;
+            (modified >= modified_since).then_some((modified, entry.path()))
+        })
+        .collect();
+    log_files.sort();
+    Ok(log_files.into_iter().map(|(_, path)| path).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recent_log_files_only_lists_logs() {
+        let log_dir = std::env::temp_dir().join(format!("logs_{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&log_dir).unwrap();
+        fs::write(log_dir.join("app.log"), "current").unwrap();
+        fs::write(log_dir.join("app_2025-01-01_00-00-00.log"), "rotated").unwrap();
+        fs::write(log_dir.join("notes.txt"), "not a log").unwrap();
+
+        let since = SystemTime::now() - Duration::from_secs(60);
+        let log_files = recent_log_files(&log_dir, since).unwrap();
+        assert_eq!(log_files.len(), 2);
+        assert!(
+            recent_log_files(&log_dir, SystemTime::now() + Duration::from_secs(60))
+                .unwrap()
+                .is_empty()
+        );
+        assert!(recent_log_files(&log_dir.join("missing"), since)
+            .unwrap()
+            .is_empty());
+
+        fs::remove_dir_all(&log_dir).unwrap();
+    }
+}
@@ -0,0 +1,52 @@
+use std::{fmt::Display, future::Future, time::Instant};
+
+use log::info;
+
+tokio::task_local! {
+    /// The spans the current task is in, e.g. "update_cycle:1a2b/install:3c4d"
+    static CURRENT_SPAN: String;
+}
+
+/// Runs `future` in a span. Its start and end are logged, and every log line written while it runs is tagged
+/// with the span, so that the lines of one update cycle, install or scan can be told apart
+pub async fn in_span<F: Future>(name: &str, detail: impl Display, future: F) -> F::Output {
+    let id = uuid::Uuid::new_v4().simple().to_string();
+    let span = match current() {
+        Some(parent) => format!("{}/{}:{}", parent, name, &id[..8]),
+        None => format!("{}:{}", name, &id[..8]),
+    };
+    let started = Instant::now();
+    CURRENT_SPAN
+        .scope(span, async {
+            info!("Started {}", detail);
+            let output = future.await;
+            info!("Finished in {} ms", started.elapsed().as_millis());
+            output
+        })
+        .await
+}
+
+/// The span of the task that is logging. None outside of a span
+pub fn current() -> Option<String> {
+    CURRENT_SPAN.try_with(|span| span.clone()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_spans_nest_and_end() {
+        assert_eq!(current(), None);
+        let inner = in_span("update_cycle", "", async {
+            in_span("install", "MyAddin", async { current().unwrap() }).await
+        })
+        .await;
+
+        let parts: Vec<&str> = inner.split('/').collect();
+        assert_eq!(parts.len(), 2);
+        assert!(parts[0].starts_with("update_cycle:"));
+        assert!(parts[1].starts_with("install:"));
+        assert_eq!(current(), None);
+    }
+}
@@ -0,0 +1,34 @@
+use std::{path::Path, sync::Arc, time::Duration};
+
+use tauri::State;
+
+use crate::services::logging::service::LoggingService;
+
+/// How far back the exported logs go
+const EXPORTED_LOGS_MAX_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// The level of the messages that are logged, e.g. "info"
+#[tauri::command]
+pub async fn get_log_level(
+    logging_service: State<'_, Arc<LoggingService>>,
+) -> Result<String, String> {
+    Ok(logging_service.get_level().to_string().to_lowercase())
+}
+
+/// Changes the log level right away. One of "error", "warn", "info", "debug" and "trace"
+#[tauri::command]
+pub async fn set_log_level(
+    level: String,
+    logging_service: State<'_, Arc<LoggingService>>,
+) -> Result<(), String> {
+    logging_service.set_level(&level).await
+}
+
+/// Writes the logs of the last week into a single file, to attach to support tickets
+#[tauri::command]
+pub async fn export_recent_logs(
+    path: String,
+    logging_service: State<'_, Arc<LoggingService>>,
+) -> Result<(), String> {
+    logging_service.bundle_recent_logs(EXPORTED_LOGS_MAX_AGE, Path::new(&path))
+}
@@ -1,9 +1,17 @@
 pub mod addin_updater;
 pub mod addins_registry;
 pub mod admin;
+pub mod app_readiness;
 pub mod app_save;
+pub mod app_updater;
 pub mod config;
 pub mod dev_resources;
 pub mod local_addins;
 pub mod local_db;
+pub mod logging;
+pub mod registry_mirror;
+pub mod revit_installations;
+pub mod support_bundle;
+#[cfg(test)]
+pub mod test_fixtures;
 pub mod user_stats;
@@ -0,0 +1,83 @@
+use std::{
+    collections::HashSet,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Copies `src` to `dst` unless `dst` already has the same size and modification time
+///
+/// The modification time is carried over, so that the copy compares equal the next time
+pub fn mirror_file(src: &Path, dst: &Path) -> io::Result<()> {
+    let src_metadata = fs::metadata(src)?;
+    if let Ok(dst_metadata) = fs::metadata(dst) {
+        let is_same_size = dst_metadata.len() == src_metadata.len();
+        let is_same_time = dst_metadata.modified().ok() == src_metadata.modified().ok();
+        if is_same_size && is_same_time {
+            return Ok(());
+        }
+    }
+    if let Some(parent) = dst.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::copy(src, dst)?;
+    fs::File::options()
+        .write(true)
+        .open(dst)?
+        .set_modified(src_metadata.modified()?)?;
+    Ok(())
+}
+
+/// Makes `dst` match `src`, copying the files that changed and removing the ones that no longer exist in `src`
+pub fn mirror_dir(src: &Path, dst: &Path) -> io::Result<()> {
+    fs::create_dir_all(dst)?;
+
+    let mut src_names = HashSet::new();
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        let src_path = entry.path();
+        let dst_path = dst.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            mirror_dir(&src_path, &dst_path)?;
+        } else {
+            mirror_file(&src_path, &dst_path)?;
+        }
+        src_names.insert(entry.file_name());
+    }
+
+    for entry in fs::read_dir(dst)? {
+        let entry = entry?;
+        if src_names.contains(&entry.file_name()) {
+            continue;
+        }
+        if entry.file_type()?.is_dir() {
+            fs::remove_dir_all(entry.path())?;
+        } else {
+            fs::remove_file(entry.path())?;
+        }
+    }
+    Ok(())
+}
+
+/// Removes a mirrored file or folder, then any folders above it that became empty, up to `root`
+pub fn remove_mirrored_path(root: &Path, relative_path: &str) -> io::Result<()> {
+    let path = root.join(relative_path);
+    if path.is_dir() {
+        fs::remove_dir_all(&path)?;
+    } else if path.exists() {
+        fs::remove_file(&path)?;
+    }
+
+    let mut parent: Option<PathBuf> = path.parent().map(Path::to_path_buf);
+    while let Some(dir) = parent {
+        if dir == root || !dir.starts_with(root) {
+            break;
+        }
+        let is_empty = fs::read_dir(&dir).is_ok_and(|mut entries| entries.next().is_none());
+        if !is_empty {
+            break;
+        }
+        fs::remove_dir(&dir)?;
+        parent = dir.parent().map(Path::to_path_buf);
+    }
+    Ok(())
+}
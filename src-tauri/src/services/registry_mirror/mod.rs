@@ -0,0 +1,4 @@
+mod helpers;
+pub mod models;
+pub mod service;
+pub mod tauri_exports;
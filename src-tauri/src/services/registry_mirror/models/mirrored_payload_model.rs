@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+/// The files of an addin that are copied to the mirror, relative to the registry root
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MirroredPayloadModel {
+    pub addin_id: String,
+    /// The .addin file
+    pub relative_xml_path: String,
+    /// The folder with the DLLs
+    pub relative_dll_folder: String,
+}
@@ -0,0 +1,6 @@
+pub mod mirrored_payload_model;
+pub mod registry_mirror_status_model;
+pub mod registry_snapshot_model;
+pub use mirrored_payload_model::*;
+pub use registry_mirror_status_model::*;
+pub use registry_snapshot_model::*;
@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegistryMirrorStatusModel {
+    /// Whether the library is showing the snapshot because the registry can't be reached
+    pub is_browsing_snapshot: bool,
+    /// When the registry was last read with the listing in the snapshot, in RFC 3339 format. None if the registry has
+    /// never been read
+    pub snapshot_taken_at: Option<String>,
+    pub snapshot_age_secs: Option<u64>,
+    /// The addins whose files are mirrored, so that they can be installed or repaired offline
+    pub mirrored_addin_ids: Vec<String>,
+}
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+use crate::services::addins_registry::models::addin_model::AddinModel;
+
+/// The registry listing as it was the last time the registry could be read
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegistrySnapshotModel {
+    /// The registry root that the addin paths are inside of
+    pub registry_path: String,
+    /// When the registry was read with this listing, in RFC 3339 format. Not updated while the listing stays the same
+    pub taken_at: String,
+    /// The addins with their paths in the registry
+    pub addins: Vec<AddinModel>,
+}
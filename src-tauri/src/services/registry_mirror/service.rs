@@ -0,0 +1,439 @@
+use log::{error, warn};
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+};
+
+use serde::{de::DeserializeOwned, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use crate::services::{
+    addins_registry::models::addin_model::AddinModel,
+    app_save::service::AppSaveService,
+    registry_mirror::{
+        helpers,
+        models::{MirroredPayloadModel, RegistryMirrorStatusModel, RegistrySnapshotModel},
+    },
+};
+
+pub const REGISTRY_MIRROR_STATUS_CHANGED_EVENT: &str = "registry_mirror_status_changed";
+
+const MIRROR_DIR_NAME: &str = "RegistryMirror";
+const SNAPSHOT_FILE_NAME: &str = "snapshot.json";
+const PAYLOADS_FILE_NAME: &str = "payloads.json";
+/// The mirrored files keep the folder structure they have in the registry below this folder
+const PAYLOADS_DIR_NAME: &str = "Payloads";
+
+/// A local copy of the addin registry, so that the library can be browsed while the registry share can't be reached
+///
+/// The mirror holds a snapshot of the whole registry listing, which is rewritten whenever a read of the registry
+/// finds it changed, and the files of the addins the user is entitled to, which the update checker keeps in sync.
+/// While offline, the snapshot is listed with the paths of the mirrored addins pointing into the mirror, so those
+/// addins can still be installed or repaired.
+pub struct RegistryMirrorService {
+    /// Used to emit `REGISTRY_MIRROR_STATUS_CHANGED_EVENT`. None when running without a UI, e.g. in tests
+    app_handle: Option<AppHandle>,
+    mirror_dir: PathBuf,
+    is_browsing_snapshot: AtomicBool,
+    /// What the snapshot file holds. None until the snapshot is first saved
+    saved_snapshot: Mutex<Option<SavedSnapshot>>,
+    payloads_lock: Mutex<()>,
+}
+
+/// The listing in the snapshot file, so that reading an unchanged registry doesn't write the file again
+struct SavedSnapshot {
+    /// The registry path and its addins as JSON
+    listing: String,
+    /// When the registry was last read with this listing, in RFC 3339 format
+    confirmed_at: String,
+}
+
+impl RegistryMirrorService {
+    pub fn new(app_handle: AppHandle, app_save_service: &AppSaveService) -> Self {
+        Self::with_mirror_dir(
+            Some(app_handle),
+            app_save_service.get_save_path().join(MIRROR_DIR_NAME),
+        )
+    }
+
+    /// Creates a service that doesn't emit any events
+    #[cfg(test)]
+    pub fn without_events(mirror_dir: PathBuf) -> Self {
+        Self::with_mirror_dir(None, mirror_dir)
+    }
+
+    fn with_mirror_dir(app_handle: Option<AppHandle>, mirror_dir: PathBuf) -> Self {
+        Self {
+            app_handle,
+            mirror_dir,
+            is_browsing_snapshot: AtomicBool::new(false),
+            saved_snapshot: Mutex::new(None),
+            payloads_lock: Mutex::new(()),
+        }
+    }
+
+    /// Replaces the snapshot with a listing that was just read from the registry. The file is only written if the
+    /// listing changed
+    pub fn save_snapshot(&self, registry_path: &str, addins: &[AddinModel]) -> Result<(), String> {
+        let listing = serde_json::to_string(&(registry_path, addins)).map_err(|e| e.to_string())?;
+        let confirmed_at = chrono::Utc::now().to_rfc3339();
+        {
+            let mut saved_snapshot = self.saved_snapshot.lock().map_err(|e| e.to_string())?;
+            if saved_snapshot.is_none() {
+                *saved_snapshot = self.read_saved_snapshot();
+            }
+            let is_unchanged = saved_snapshot
+                .as_ref()
+                .is_some_and(|saved_snapshot| saved_snapshot.listing == listing);
+            if !is_unchanged {
+                let snapshot = RegistrySnapshotModel {
+                    registry_path: registry_path.to_string(),
+                    taken_at: confirmed_at.clone(),
+                    addins: addins.to_vec(),
+                };
+                self.write_json(SNAPSHOT_FILE_NAME, &snapshot)?;
+            }
+            *saved_snapshot = Some(SavedSnapshot {
+                listing,
+                confirmed_at,
+            });
+        }
+        self.set_browsing_snapshot(false);
+        Ok(())
+    }
+
+    /// Lists the snapshot, with the paths of the mirrored addins pointing into the mirror. None if the registry has
+    /// never been read
+    ///
+    /// The addins that aren't in `get_status().mirrored_addin_ids` keep their paths in the registry
+    pub fn snapshot_addins(&self) -> Result<Option<Vec<AddinModel>>, String> {
+        let Some(snapshot) = self.read_json::<RegistrySnapshotModel>(SNAPSHOT_FILE_NAME)? else {
+            return Ok(None);
+        };
+        let registry_root = Path::new(&snapshot.registry_path);
+        let payloads_dir = self.payloads_dir();
+        let addins = snapshot
+            .addins
+            .into_iter()
+            .map(|mut addin| {
+                if let Some(path) =
+                    mirrored_path(registry_root, &payloads_dir, &addin.path_to_addin_xml_file)
+                {
+                    addin.path_to_addin_xml_file = path;
+                }
+                if let Some(path) = mirrored_path(
+                    registry_root,
+                    &payloads_dir,
+                    &addin.path_to_addin_dll_folder,
+                ) {
+                    addin.path_to_addin_dll_folder = path;
+                }
+                addin
+            })
+            .collect();
+        self.set_browsing_snapshot(true);
+        Ok(Some(addins))
+    }
+
+    /// How many addins the snapshot lists. Unlike `snapshot_addins`, the library keeps showing the registry.
+    /// None if the registry has never been read
+    pub fn snapshot_addin_count(&self) -> Result<Option<usize>, String> {
+        Ok(self
+            .read_json::<RegistrySnapshotModel>(SNAPSHOT_FILE_NAME)?
+            .map(|snapshot| snapshot.addins.len()))
+    }
+
+    /// Copies the files of the `entitled` registry addins that changed, and removes the ones of other addins
+    ///
+    /// If `is_complete` is false, `entitled` may be missing some addins (e.g. the allowed addins couldn't be read), so
+    /// files are only removed for addins that are no longer in the registry
+    pub fn sync_payloads(&self, entitled: &[AddinModel], is_complete: bool) -> Result<(), String> {
+        let _guard = self.payloads_lock.lock().map_err(|e| e.to_string())?;
+        let Some(snapshot) = self.read_json::<RegistrySnapshotModel>(SNAPSHOT_FILE_NAME)? else {
+            return Ok(());
+        };
+        let registry_root = Path::new(&snapshot.registry_path);
+        let payloads_dir = self.payloads_dir();
+        let previous_payloads = self
+            .read_json::<Vec<MirroredPayloadModel>>(PAYLOADS_FILE_NAME)?
+            .unwrap_or_default();
+
+        let mut payloads: Vec<MirroredPayloadModel> = Vec::new();
+        for addin in entitled {
+            if payloads
+                .iter()
+                .any(|payload| payload.addin_id == addin.addin_id)
+            {
+                continue;
+            }
+            let Some(payload) = payload_for(registry_root, addin) else {
+                error!(
+                    "Not mirroring {}, it is outside of the registry {}",
+                    addin.name, snapshot.registry_path
+                );
+                continue;
+            };
+            if let Err(e) = mirror_payload(registry_root, &payloads_dir, &payload) {
+                error!("Failed to mirror {}: {}", addin.name, e);
+                // Whatever was mirrored before is still better than nothing
+                if let Some(previous) = previous_payloads.iter().find(|p| *p == &payload) {
+                    payloads.push(previous.clone());
+                }
+                continue;
+            }
+            payloads.push(payload);
+        }
+
+        if !is_complete {
+            let registry_ids: HashSet<&str> = snapshot
+                .addins
+                .iter()
+                .map(|addin| addin.addin_id.as_str())
+                .collect();
+            for previous in &previous_payloads {
+                let is_kept = payloads.iter().any(|p| p.addin_id == previous.addin_id);
+                if !is_kept && registry_ids.contains(previous.addin_id.as_str()) {
+                    payloads.push(previous.clone());
+                }
+            }
+        }
+
+        // Several addins can share a .addin file and DLL folder, so only remove paths nothing uses anymore
+        let used_paths: HashSet<&str> = payloads
+            .iter()
+            .flat_map(|p| [p.relative_xml_path.as_str(), p.relative_dll_folder.as_str()])
+            .collect();
+        for stale in previous_payloads.iter().filter(|p| !payloads.contains(p)) {
+            for relative_path in [&stale.relative_xml_path, &stale.relative_dll_folder] {
+                if used_paths.contains(relative_path.as_str()) {
+                    continue;
+                }
+                helpers::remove_mirrored_path(&payloads_dir, relative_path).map_err(|e| {
+                    format!(
+                        "Failed to remove the stale mirror of {}: {}",
+                        relative_path, e
+                    )
+                })?;
+            }
+        }
+
+        self.write_json(PAYLOADS_FILE_NAME, &payloads)
+    }
+
+    pub fn get_status(&self) -> Result<RegistryMirrorStatusModel, String> {
+        let snapshot = self.read_json::<RegistrySnapshotModel>(SNAPSHOT_FILE_NAME)?;
+        let payloads = self
+            .read_json::<Vec<MirroredPayloadModel>>(PAYLOADS_FILE_NAME)?
+            .unwrap_or_default();
+        // The file is only rewritten when the listing changes, so it can be older than the last read of the registry
+        let confirmed_at = self.saved_snapshot.lock().ok().and_then(|saved_snapshot| {
+            saved_snapshot
+                .as_ref()
+                .map(|saved_snapshot| saved_snapshot.confirmed_at.clone())
+        });
+        let snapshot_taken_at = confirmed_at.or_else(|| snapshot.map(|snapshot| snapshot.taken_at));
+        let snapshot_age_secs = snapshot_taken_at
+            .as_ref()
+            .and_then(|taken_at| chrono::DateTime::parse_from_rfc3339(taken_at).ok())
+            .map(|taken_at| {
+                (chrono::Utc::now() - taken_at.with_timezone(&chrono::Utc))
+                    .num_seconds()
+                    .max(0) as u64
+            });
+        Ok(RegistryMirrorStatusModel {
+            is_browsing_snapshot: self.is_browsing_snapshot.load(Ordering::SeqCst),
+            snapshot_taken_at,
+            snapshot_age_secs,
+            mirrored_addin_ids: payloads.into_iter().map(|p| p.addin_id).collect(),
+        })
+    }
+
+    fn set_browsing_snapshot(&self, is_browsing_snapshot: bool) {
+        let was_browsing_snapshot = self
+            .is_browsing_snapshot
+            .swap(is_browsing_snapshot, Ordering::SeqCst);
+        if was_browsing_snapshot == is_browsing_snapshot {
+            return;
+        }
+        let Some(app_handle) = &self.app_handle else {
+            return;
+        };
+        match self.get_status() {
+            Ok(status) => {
+                if let Err(e) = app_handle.emit(REGISTRY_MIRROR_STATUS_CHANGED_EVENT, status) {
+                    warn!("Error emitting registry mirror status: {}", e);
+                }
+            }
+            Err(e) => warn!("Could not read the registry mirror status: {}", e),
+        }
+    }
+
+    /// The listing in the snapshot file. None if there is none or it can't be read, so that it is written again
+    fn read_saved_snapshot(&self) -> Option<SavedSnapshot> {
+        let snapshot = self
+            .read_json::<RegistrySnapshotModel>(SNAPSHOT_FILE_NAME)
+            .ok()??;
+        let listing =
+            serde_json::to_string(&(snapshot.registry_path.as_str(), &snapshot.addins)).ok()?;
+        Some(SavedSnapshot {
+            listing,
+            confirmed_at: snapshot.taken_at,
+        })
+    }
+
+    fn payloads_dir(&self) -> PathBuf {
+        self.mirror_dir.join(PAYLOADS_DIR_NAME)
+    }
+
+    fn read_json<T: DeserializeOwned>(&self, file_name: &str) -> Result<Option<T>, String> {
+        let path = self.mirror_dir.join(file_name);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let contents = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let value = serde_json::from_str(&contents)
+            .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?;
+        Ok(Some(value))
+    }
+
+    /// Writes to a temporary file first, so that a crash never leaves a half written file behind
+    fn write_json<T: Serialize>(&self, file_name: &str, value: &T) -> Result<(), String> {
+        fs::create_dir_all(&self.mirror_dir).map_err(|e| e.to_string())?;
+        let path = self.mirror_dir.join(file_name);
+        let temp_path = path.with_extension("json.tmp");
+        let contents = serde_json::to_string(value).map_err(|e| e.to_string())?;
+        fs::write(&temp_path, contents)
+            .map_err(|e| format!("Failed to write {}: {}", temp_path.display(), e))?;
+        fs::rename(&temp_path, &path)
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+}
+
+/// The addin's files relative to the registry root. None if they are not inside of it
+fn payload_for(registry_root: &Path, addin: &AddinModel) -> Option<MirroredPayloadModel> {
+    let relative = |path: &str| {
+        Path::new(path)
+            .strip_prefix(registry_root)
+            .ok()
+            .map(|relative_path| relative_path.to_string_lossy().to_string())
+    };
+    Some(MirroredPayloadModel {
+        addin_id: addin.addin_id.clone(),
+        relative_xml_path: relative(&addin.path_to_addin_xml_file)?,
+        relative_dll_folder: relative(&addin.path_to_addin_dll_folder)?,
+    })
+}
+
+fn mirror_payload(
+    registry_root: &Path,
+    payloads_dir: &Path,
+    payload: &MirroredPayloadModel,
+) -> std::io::Result<()> {
+    helpers::mirror_file(
+        &registry_root.join(&payload.relative_xml_path),
+        &payloads_dir.join(&payload.relative_xml_path),
+    )?;
+    let dll_folder = registry_root.join(&payload.relative_dll_folder);
+    if dll_folder.is_dir() {
+        helpers::mirror_dir(
+            &dll_folder,
+            &payloads_dir.join(&payload.relative_dll_folder),
+        )?;
+    }
+    Ok(())
+}
+
+/// Where a path in the registry is in the mirror. None if the path is not inside the registry or was never mirrored
+fn mirrored_path(registry_root: &Path, payloads_dir: &Path, path: &str) -> Option<String> {
+    let relative_path = Path::new(path).strip_prefix(registry_root).ok()?;
+    let mirrored_path = payloads_dir.join(relative_path);
+    mirrored_path
+        .exists()
+        .then(|| mirrored_path.to_string_lossy().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::test_fixtures::publish_registry_addin;
+
+    #[test]
+    fn test_snapshot_points_into_the_mirror_and_stale_payloads_are_removed() {
+        let root = std::env::temp_dir().join(format!("registry_mirror_{}", uuid::Uuid::new_v4()));
+        let registry_root = root.join("Registry");
+        let mirror = RegistryMirrorService::without_events(root.join("Mirror"));
+        let addin = publish_registry_addin(&registry_root, "v1");
+        assert!(mirror.snapshot_addins().unwrap().is_none());
+
+        let registry_path = registry_root.to_string_lossy().to_string();
+        mirror
+            .save_snapshot(&registry_path, std::slice::from_ref(&addin))
+            .unwrap();
+        mirror
+            .sync_payloads(std::slice::from_ref(&addin), true)
+            .unwrap();
+
+        // The registry goes offline
+        fs::remove_dir_all(&registry_root).unwrap();
+        let snapshot = mirror.snapshot_addins().unwrap().unwrap();
+        let dll = Path::new(&snapshot[0].path_to_addin_dll_folder).join("MyAddin.dll");
+        assert_eq!(fs::read_to_string(dll).unwrap(), "v1");
+        assert!(Path::new(&snapshot[0].path_to_addin_xml_file).exists());
+        let status = mirror.get_status().unwrap();
+        assert!(status.is_browsing_snapshot);
+        assert_eq!(status.mirrored_addin_ids, vec![addin.addin_id.clone()]);
+
+        // It comes back without the addin
+        fs::create_dir_all(&registry_root).unwrap();
+        mirror.save_snapshot(&registry_path, &[]).unwrap();
+        mirror.sync_payloads(&[], true).unwrap();
+        assert!(!mirror.get_status().unwrap().is_browsing_snapshot);
+        assert!(mirror.get_status().unwrap().mirrored_addin_ids.is_empty());
+        assert!(!root
+            .join("Mirror")
+            .join(PAYLOADS_DIR_NAME)
+            .join("Tools")
+            .exists());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_unchanged_listing_is_not_written_and_unmirrored_paths_are_kept() {
+        let root = std::env::temp_dir().join(format!("registry_mirror_{}", uuid::Uuid::new_v4()));
+        let registry_root = root.join("Registry");
+        let mirror = RegistryMirrorService::without_events(root.join("Mirror"));
+        let addin = publish_registry_addin(&registry_root, "v1");
+        let registry_path = registry_root.to_string_lossy().to_string();
+        let snapshot_file = root.join("Mirror").join(SNAPSHOT_FILE_NAME);
+
+        mirror
+            .save_snapshot(&registry_path, std::slice::from_ref(&addin))
+            .unwrap();
+        fs::remove_file(&snapshot_file).unwrap();
+        mirror
+            .save_snapshot(&registry_path, std::slice::from_ref(&addin))
+            .unwrap();
+        assert!(!snapshot_file.exists());
+        mirror.save_snapshot(&registry_path, &[]).unwrap();
+        assert!(snapshot_file.exists());
+
+        // Listed, but its files were never mirrored
+        mirror
+            .save_snapshot(&registry_path, std::slice::from_ref(&addin))
+            .unwrap();
+        let snapshot = mirror.snapshot_addins().unwrap().unwrap();
+        assert_eq!(
+            snapshot[0].path_to_addin_dll_folder,
+            addin.path_to_addin_dll_folder
+        );
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
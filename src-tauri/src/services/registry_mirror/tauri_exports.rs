@@ -0,0 +1,15 @@
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::services::registry_mirror::{
+    models::RegistryMirrorStatusModel, service::RegistryMirrorService,
+};
+
+/// Returns whether the library is showing the offline snapshot of the registry, and how old it is
+#[tauri::command]
+pub async fn get_registry_mirror_status(
+    registry_mirror_service: State<'_, Arc<RegistryMirrorService>>,
+) -> Result<RegistryMirrorStatusModel, String> {
+    registry_mirror_service.get_status()
+}
@@ -0,0 +1,4 @@
+pub mod models;
+pub mod providers;
+pub mod service;
+pub mod tauri_exports;
@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+/// A DLL that an addin ships which Revit already ships itself. Revit will load its own copy first,
+/// so the addin may end up running against a different version of the DLL than it was built with
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DllConflictModel {
+    pub dll_name: String,
+    pub revit_version: String,
+    /// The folder of the Revit installation that also contains the DLL
+    pub revit_install_dir: String,
+}
@@ -0,0 +1,4 @@
+mod dll_conflict_model;
+pub use dll_conflict_model::*;
+mod revit_installation_model;
+pub use revit_installation_model::*;
@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RevitInstallationModel {
+    /// The name of the installed product, for example "Autodesk Revit 2025"
+    pub product: String,
+    /// The Revit version, for example "2025"
+    pub version: String,
+    /// The folder that contains Revit.exe. Will be None if the installation was only inferred from an Addins folder
+    pub install_dir: Option<String>,
+    /// Typically C:\Users\<username>\AppData\Roaming\Autodesk\Revit\Addins\2025
+    pub user_addins_folder: Option<String>,
+    /// Typically C:\ProgramData\Autodesk\Revit\Addins\2025
+    pub machine_addins_folder: Option<String>,
+}
@@ -0,0 +1,81 @@
+use std::path::PathBuf;
+
+/// Tells the `RevitInstallationsService` where to look for Revit on this machine.
+///
+/// The service only ever touches the file system below these roots, so tests can point it at a fake layout.
+/// The app uses `AppPaths`, which is built from the standard locations of the current platform
+pub trait RevitInstallationsProvider: Send + Sync {
+    /// The Program Files folders. Revit is installed to `<Program Files>\Autodesk\Revit <version>`
+    fn program_files_dirs(&self) -> Vec<PathBuf>;
+    /// Typically C:\Users\<username>\AppData\Roaming\Autodesk\Revit\Addins
+    fn user_addins_root(&self) -> Option<PathBuf>;
+    /// Typically C:\ProgramData\Autodesk\Revit\Addins
+    fn machine_addins_root(&self) -> Option<PathBuf>;
+}
+
+/// Mirrors the Windows folder layout below a single root folder, so that discovery can be tested on any platform
+#[cfg(test)]
+pub struct FakeRevitInstallationsProvider {
+    pub root: PathBuf,
+}
+
+#[cfg(test)]
+impl FakeRevitInstallationsProvider {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    /// Creates `Program Files\Autodesk\Revit <version>\Revit.exe`
+    pub fn add_installation(&self, version: &str) -> PathBuf {
+        let install_dir = self
+            .root
+            .join("Program Files")
+            .join("Autodesk")
+            .join(format!("Revit {}", version));
+        std::fs::create_dir_all(&install_dir).unwrap();
+        std::fs::write(install_dir.join("Revit.exe"), "").unwrap();
+        install_dir
+    }
+
+    /// Creates the per-user Addins folder for the version
+    pub fn add_user_addins_folder(&self, version: &str) -> PathBuf {
+        let folder = self.user_addins_root().unwrap().join(version);
+        std::fs::create_dir_all(&folder).unwrap();
+        folder
+    }
+
+    /// Creates the machine-wide Addins folder for the version
+    pub fn add_machine_addins_folder(&self, version: &str) -> PathBuf {
+        let folder = self.machine_addins_root().unwrap().join(version);
+        std::fs::create_dir_all(&folder).unwrap();
+        folder
+    }
+}
+
+#[cfg(test)]
+impl RevitInstallationsProvider for FakeRevitInstallationsProvider {
+    fn program_files_dirs(&self) -> Vec<PathBuf> {
+        vec![self.root.join("Program Files")]
+    }
+
+    fn user_addins_root(&self) -> Option<PathBuf> {
+        Some(
+            self.root
+                .join("AppData")
+                .join("Roaming")
+                .join("Autodesk")
+                .join("Revit")
+                .join("Addins"),
+        )
+    }
+
+    fn machine_addins_root(&self) -> Option<PathBuf> {
+        Some(
+            self.root
+                .join("ProgramData")
+                .join("Autodesk")
+                .join("Revit")
+                .join("Addins"),
+        )
+    }
+}
@@ -0,0 +1,243 @@
+use log::warn;
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use revit_check::revit_version_from_path;
+
+use crate::services::revit_installations::{
+    models::{DllConflictModel, RevitInstallationModel},
+    providers::RevitInstallationsProvider,
+};
+
+/// Discovers the Revit installations on this machine
+pub struct RevitInstallationsService {
+    provider: Arc<dyn RevitInstallationsProvider>,
+}
+
+impl RevitInstallationsService {
+    pub fn new(provider: Arc<dyn RevitInstallationsProvider>) -> Self {
+        Self { provider }
+    }
+
+    /// Returns the Revit installations on this machine, oldest first
+    ///
+    /// Installations are discovered from folders like `<Program Files>\Autodesk\Revit 2025` that contain a `Revit.exe`.
+    /// If none are found there (for example, Revit was installed somewhere else), the version folders of the
+    /// per-user and machine-wide Addins folders are used instead, without an install dir.
+    pub fn get_installations(&self) -> Vec<RevitInstallationModel> {
+        let mut installations = Vec::new();
+        for program_files in self.provider.program_files_dirs() {
+            self.discover_installations_in(&program_files.join("Autodesk"), &mut installations);
+        }
+
+        if installations.is_empty() {
+            for version in self.versions_from_addins_folders() {
+                installations.push(self.installation_model(version, None));
+            }
+        }
+
+        installations.sort_by(|a, b| a.version.cmp(&b.version));
+        installations.dedup_by(|a, b| a.version == b.version);
+        installations
+    }
+
+    /// Returns the Revit versions that are installed on this machine, oldest first
+    ///
+    /// Example: ["2024", "2025", "2026"]
+    pub fn installed_versions(&self) -> Vec<String> {
+        self.get_installations()
+            .into_iter()
+            .map(|installation| installation.version)
+            .collect()
+    }
+
+    /// Returns the DLLs in the folder that also ship with an installed Revit.
+    ///
+    /// If `revit_version` is given, only that installation is checked
+    pub fn find_dll_conflicts(
+        &self,
+        dll_folder: &Path,
+        revit_version: Option<&str>,
+    ) -> Result<Vec<DllConflictModel>, String> {
+        let addin_dlls = get_all_dlls_in_dir(dll_folder)
+            .map_err(|e| format!("Could not read dlls in {}: {}", dll_folder.display(), e))?;
+
+        let mut conflicts = Vec::new();
+        for installation in self.get_installations() {
+            if revit_version.is_some_and(|version| version != installation.version) {
+                continue;
+            }
+            let Some(install_dir) = installation.install_dir else {
+                continue;
+            };
+            let revit_dlls = match get_all_dlls_in_dir(Path::new(&install_dir)) {
+                Ok(dlls) => dlls,
+                Err(e) => {
+                    warn!("Could not read Revit dlls in {}: {}", install_dir, e);
+                    continue;
+                }
+            };
+            for dll in addin_dlls.iter().filter(|dll| revit_dlls.contains(*dll)) {
+                conflicts.push(DllConflictModel {
+                    dll_name: dll.clone(),
+                    revit_version: installation.version.clone(),
+                    revit_install_dir: install_dir.clone(),
+                });
+            }
+        }
+        Ok(conflicts)
+    }
+
+    /// Looks for folders like `Revit 2025` that contain a `Revit.exe`
+    fn discover_installations_in(
+        &self,
+        autodesk_dir: &Path,
+        installations: &mut Vec<RevitInstallationModel>,
+    ) {
+        let Ok(entries) = fs::read_dir(autodesk_dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Some(folder_name) = path.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            if !folder_name.starts_with("Revit ") || !path.join("Revit.exe").exists() {
+                continue;
+            }
+            if let Some(version) = revit_version_from_path(folder_name) {
+                installations.push(self.installation_model(version, Some(path)));
+            }
+        }
+    }
+
+    fn versions_from_addins_folders(&self) -> Vec<String> {
+        [
+            self.provider.user_addins_root(),
+            self.provider.machine_addins_root(),
+        ]
+        .into_iter()
+        .flatten()
+        .filter_map(|addins_root| fs::read_dir(addins_root).ok())
+        .flat_map(|entries| entries.flatten())
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| revit_version_from_path(&entry.file_name().to_string_lossy()))
+        .collect()
+    }
+
+    fn installation_model(
+        &self,
+        version: String,
+        install_dir: Option<PathBuf>,
+    ) -> RevitInstallationModel {
+        let to_string = |path: PathBuf| path.to_string_lossy().to_string();
+        RevitInstallationModel {
+            product: format!("Autodesk Revit {}", version),
+            user_addins_folder: self
+                .provider
+                .user_addins_root()
+                .map(|root| to_string(root.join(&version))),
+            machine_addins_folder: self
+                .provider
+                .machine_addins_root()
+                .map(|root| to_string(root.join(&version))),
+            install_dir: install_dir.map(to_string),
+            version,
+        }
+    }
+}
+
+fn get_all_dlls_in_dir(dir: &Path) -> Result<HashSet<String>, std::io::Error> {
+    let mut dlls = HashSet::new();
+    for entry in dir.read_dir()? {
+        let file_name = entry?.file_name().to_string_lossy().to_string();
+        if file_name.to_lowercase().ends_with(".dll") {
+            dlls.insert(file_name);
+        }
+    }
+    Ok(dlls)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::revit_installations::providers::FakeRevitInstallationsProvider;
+
+    fn fake_service() -> (
+        RevitInstallationsService,
+        Arc<FakeRevitInstallationsProvider>,
+    ) {
+        let root =
+            std::env::temp_dir().join(format!("revit_installations_{}", uuid::Uuid::new_v4()));
+        let provider = Arc::new(FakeRevitInstallationsProvider::new(root));
+        (RevitInstallationsService::new(provider.clone()), provider)
+    }
+
+    #[test]
+    fn test_discovers_installations() {
+        let (service, provider) = fake_service();
+        provider.add_installation("2026");
+        provider.add_installation("2024");
+        // Not a Revit installation, since there is no Revit.exe
+        fs::create_dir_all(
+            provider
+                .root
+                .join("Program Files")
+                .join("Autodesk")
+                .join("Revit 2025"),
+        )
+        .unwrap();
+
+        assert_eq!(service.installed_versions(), vec!["2024", "2026"]);
+        let installation = service.get_installations().pop().unwrap();
+        assert!(installation.install_dir.unwrap().ends_with("Revit 2026"));
+        assert!(installation
+            .machine_addins_folder
+            .unwrap()
+            .ends_with("2026"));
+
+        fs::remove_dir_all(&provider.root).unwrap();
+    }
+
+    #[test]
+    fn test_falls_back_to_addins_folders() {
+        let (service, provider) = fake_service();
+        provider.add_user_addins_folder("2025");
+        provider.add_machine_addins_folder("2025");
+        provider.add_machine_addins_folder("2026");
+
+        let installations = service.get_installations();
+        assert_eq!(installations.len(), 2);
+        assert_eq!(installations[0].version, "2025");
+        assert_eq!(installations[1].version, "2026");
+        assert!(installations[0].install_dir.is_none());
+
+        fs::remove_dir_all(&provider.root).unwrap();
+    }
+
+    #[test]
+    fn test_finds_dll_conflicts() {
+        let (service, provider) = fake_service();
+        let install_dir = provider.add_installation("2025");
+        fs::write(install_dir.join("Newtonsoft.Json.dll"), "").unwrap();
+        let addin_dir = provider.root.join("MyAddin");
+        fs::create_dir_all(&addin_dir).unwrap();
+        fs::write(addin_dir.join("Newtonsoft.Json.dll"), "").unwrap();
+        fs::write(addin_dir.join("MyAddin.dll"), "").unwrap();
+
+        let conflicts = service.find_dll_conflicts(&addin_dir, None).unwrap();
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].dll_name, "Newtonsoft.Json.dll");
+        assert_eq!(conflicts[0].revit_version, "2025");
+        assert!(service
+            .find_dll_conflicts(&addin_dir, Some("2024"))
+            .unwrap()
+            .is_empty());
+
+        fs::remove_dir_all(&provider.root).unwrap();
+    }
+}
@@ -0,0 +1,32 @@
+use std::{path::Path, sync::Arc};
+
+use tauri::State;
+
+use crate::services::{
+    addins_registry::models::addin_model::AddinModel,
+    revit_installations::{
+        models::{DllConflictModel, RevitInstallationModel},
+        service::RevitInstallationsService,
+    },
+};
+
+#[tauri::command]
+pub async fn get_revit_installations(
+    revit_installations_service: State<'_, Arc<RevitInstallationsService>>,
+) -> Result<Vec<RevitInstallationModel>, String> {
+    Ok(revit_installations_service.get_installations())
+}
+
+/// Returns the DLLs of the addin that Revit already ships itself.
+///
+/// If the addin is installed locally, only the Revit version it is installed in is checked
+#[tauri::command]
+pub async fn get_addin_dll_conflicts(
+    revit_installations_service: State<'_, Arc<RevitInstallationsService>>,
+    addin: AddinModel,
+) -> Result<Vec<DllConflictModel>, String> {
+    revit_installations_service.find_dll_conflicts(
+        Path::new(&addin.path_to_addin_dll_folder),
+        addin.revit_version.as_deref(),
+    )
+}
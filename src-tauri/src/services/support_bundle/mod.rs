@@ -0,0 +1,4 @@
+pub mod models;
+mod redaction;
+pub mod service;
+pub mod tauri_exports;
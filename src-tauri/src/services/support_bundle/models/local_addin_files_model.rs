@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+use crate::services::addins_registry::models::addin_model::AddinModel;
+
+/// A local addin and the files it is made of
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalAddinFilesModel {
+    pub addin: AddinModel,
+    /// The .addin file first, then the files in the DLL folder
+    pub files: Vec<FileHashModel>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileHashModel {
+    pub path: String,
+    pub size: Option<u64>,
+    /// None if the file couldn't be read, e.g. because Revit locks it
+    pub sha256: Option<String>,
+    pub error: Option<String>,
+}
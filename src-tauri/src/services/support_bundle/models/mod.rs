@@ -0,0 +1,6 @@
+pub mod local_addin_files_model;
+pub mod registry_diagnostics_model;
+pub mod support_bundle_model;
+pub use local_addin_files_model::*;
+pub use registry_diagnostics_model::*;
+pub use support_bundle_model::*;
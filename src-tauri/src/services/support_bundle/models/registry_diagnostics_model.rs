@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+use crate::services::registry_mirror::models::RegistryMirrorStatusModel;
+
+/// Whether the registry could be reached when the support bundle was made, and what was known about it from before.
+/// The registry isn't scanned for the bundle, as that can take long on a slow share
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegistryDiagnosticsModel {
+    /// Empty if no registry is configured
+    pub registry_path: String,
+    pub is_reachable: bool,
+    /// The registry's change token. None if it has none or can't be reached
+    pub change_token: Option<String>,
+    /// How many addins the registry listed the last time it was read. None if it has never been read
+    pub snapshot_addin_count: Option<usize>,
+    pub mirror_status: Option<RegistryMirrorStatusModel>,
+}
@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+/// What was written to a support bundle
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SupportBundleModel {
+    /// The zip file
+    pub path: String,
+    pub created_at: String,
+    pub app_version: String,
+    pub os: String,
+    /// The entries in the zip, e.g. "logs/app.log"
+    pub entries: Vec<String>,
+    /// The parts that couldn't be collected. The rest of the bundle is still written
+    pub problems: Vec<String>,
+}
@@ -0,0 +1,19 @@
+use std::{path::Path, sync::Arc};
+
+use tauri::{AppHandle, State};
+
+use crate::services::support_bundle::{models::SupportBundleModel, service::SupportBundleService};
+
+/// Writes a zip with the recent logs, the local addins, the config, the registry diagnostics, the pending
+/// operations, the update history and the running Revit processes, to attach to support tickets
+#[tauri::command]
+pub async fn create_support_bundle(
+    path: String,
+    app: AppHandle,
+    support_bundle_service: State<'_, Arc<SupportBundleService>>,
+) -> Result<SupportBundleModel, String> {
+    let app_version = app.package_info().version.to_string();
+    support_bundle_service
+        .create_bundle(Path::new(&path), &app_version)
+        .await
+}
@@ -0,0 +1,56 @@
+use std::{fs, path::Path};
+
+use crate::services::addins_registry::models::{
+    addin_model::AddinModel,
+    addin_xml_model::{AddIn, RevitAddIns},
+};
+
+/// The .addin file of `MyAddin`, whose DLL is in the `MyAddin` folder next to it
+pub const ADDIN_XML: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<RevitAddIns>
+  <AddIn Type="Application">
+    <Name>MyAddin</Name>
+    <Assembly>MyAddin\MyAddin.dll</Assembly>
+    <AddInId>8d83c886-b739-4acd-a9db-1bc78f315b2b</AddInId>
+    <FullClassName>MyAddin.App</FullClassName>
+    <VendorId>EMA</VendorId>
+    <VendorDescription>EMA</VendorDescription>
+    <VendorEmail>dev@example.com</VendorEmail>
+  </AddIn>
+</RevitAddIns>"#;
+
+/// Writes the .addin file and DLL folder of `MyAddin` to `dir` and reads it back
+pub fn write_addin(dir: &Path, dll_contents: &str) -> AddinModel {
+    let dll_folder = dir.join("MyAddin");
+    fs::create_dir_all(&dll_folder).unwrap();
+    let xml_file = dir.join("MyAddin.addin");
+    fs::write(&xml_file, ADDIN_XML).unwrap();
+    fs::write(dll_folder.join("MyAddin.dll"), dll_contents).unwrap();
+
+    let revit_addins = RevitAddIns::from_file(&xml_file).unwrap();
+    AddinModel::from_xml_data(
+        xml_file.to_string_lossy().to_string(),
+        dll_folder.to_string_lossy().to_string(),
+        &revit_addins.add_in[0],
+    )
+    .unwrap()
+}
+
+/// Publishes `MyAddin` to `<registry>\Tools` and returns it the way the registry reads it
+pub fn publish_registry_addin(registry_root: &Path, dll_contents: &str) -> AddinModel {
+    write_addin(&registry_root.join("Tools"), dll_contents)
+}
+
+/// An `<AddIn>` entry with only a name and an ID, for tests that don't read the addin's files
+pub fn xml_addin(name: &str, addin_id: &str) -> AddIn {
+    AddIn {
+        name: Some(name.to_string()),
+        assembly: None,
+        addin_id: Some(addin_id.to_string()),
+        full_class_name: None,
+        vendor_id: None,
+        vendor_description: None,
+        vendor_email: None,
+        addin_type: None,
+    }
+}
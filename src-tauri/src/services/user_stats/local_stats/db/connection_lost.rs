@@ -0,0 +1,67 @@
+use log::warn;
+use sea_orm::{DbErr, RuntimeErr};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+/// The primary SQLite result codes that mean the database file can't be reached anymore
+const SQLITE_IOERR: i32 = 10;
+const SQLITE_CANTOPEN: i32 = 14;
+
+/// Shared by the tables of one connection. Set when a query fails because the database can't be reached anymore,
+/// for example when the network share went away, so that the handler drops the connection and connects again
+#[derive(Clone, Default)]
+pub struct ConnectionLost(Arc<AtomicBool>);
+
+impl ConnectionLost {
+    /// Passes the error on, after noting whether it means that the connection is lost
+    pub fn check(&self, e: DbErr) -> DbErr {
+        if is_connection_error(&e) && !self.0.swap(true, Ordering::Relaxed) {
+            warn!("Lost the connection to the stats database: {}", e);
+        }
+        e
+    }
+
+    pub fn is_lost(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+fn is_connection_error(e: &DbErr) -> bool {
+    match e {
+        DbErr::Conn(_) | DbErr::ConnectionAcquire(_) => true,
+        DbErr::Exec(RuntimeErr::SqlxError(e)) | DbErr::Query(RuntimeErr::SqlxError(e)) => match e {
+            sqlx::Error::Io(_)
+            | sqlx::Error::PoolTimedOut
+            | sqlx::Error::PoolClosed
+            | sqlx::Error::WorkerCrashed => true,
+            // SQLite reports extended codes, the primary code is in the lowest byte
+            sqlx::Error::Database(e) => e
+                .code()
+                .and_then(|code| code.parse::<i32>().ok())
+                .is_some_and(|code| matches!(code & 0xff, SQLITE_IOERR | SQLITE_CANTOPEN)),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_connection_errors_mark_the_connection_lost() {
+        let connection_lost = ConnectionLost::default();
+        connection_lost.check(DbErr::RecordNotFound("user".to_string()));
+        assert!(!connection_lost.is_lost());
+
+        let io_error = std::io::Error::new(std::io::ErrorKind::NotFound, "share is gone");
+        connection_lost.check(DbErr::Query(RuntimeErr::SqlxError(sqlx::Error::Io(
+            io_error,
+        ))));
+        assert!(connection_lost.is_lost());
+        assert!(connection_lost.clone().is_lost());
+    }
+}
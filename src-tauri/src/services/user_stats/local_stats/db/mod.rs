@@ -1,43 +1,247 @@
-use std::{fs, path::Path, sync::Arc};
+use log::warn;
+use std::{
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use sea_orm::DatabaseConnection;
 use sqlx::sqlite::SqlitePool;
+use tokio::sync::Mutex;
+mod connection_lost;
 pub mod user_addins;
 pub mod user_stats;
+use connection_lost::ConnectionLost;
 use user_addins::UserAddinsTable;
 use user_stats::UserStatsTable;
 
+use crate::{
+    services::{config::keys, local_db::service::LocalDbService, user_stats::StatsDbStatusModel},
+    utils,
+};
+
+const DB_FILE_NAME: &str = "UserStats.db";
+/// The wait before retrying after the first failed attempt. Every further failure doubles it
+const INITIAL_RETRY_DELAY: Duration = Duration::from_secs(5);
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(5 * 60);
+/// An unreachable network share can otherwise block for a long time
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The shared stats database, which typically lives on a network share.
+///
+/// The connection is made lazily on first use, so the app starts even when the share is not mapped (for example,
+/// on a laptop that is off the VPN). While it can't be reached the handler is "offline": the tables return an
+/// error and the connection is retried with exponential backoff. A query that fails because the share went away
+/// drops the connection, so that the next use connects again.
 pub struct LocalStatsDbHandler {
+    local_db: Arc<LocalDbService>,
+    /// Used when no location is configured under `keys::STATS_DB_PATH`
+    default_dir: PathBuf,
+    state: Mutex<ConnectionState>,
+    /// Held while connecting, so that only one attempt runs at a time. The state isn't locked meanwhile
+    connecting: Mutex<()>,
+}
+
+#[derive(Default)]
+struct ConnectionState {
+    connection: Option<StatsDbConnection>,
+    failed_attempts: u32,
+    last_error: Option<String>,
+    next_attempt_at: Option<Instant>,
+}
+
+struct StatsDbConnection {
+    dir: PathBuf,
     user_stats_table: UserStatsTable,
     user_addins_table: UserAddinsTable,
+    connection_lost: ConnectionLost,
 }
 
 impl LocalStatsDbHandler {
-    /// Creates a new instance of the local stats database handler.
+    /// Creates a new instance of the local stats database handler. Does not connect yet.
     ///
-    /// `dir` is the directory where the database will be stored.
+    /// The database is stored in the directory configured under `keys::STATS_DB_PATH`, or `default_dir`.
     /// It will NOT create the directory if it doesn't exist.
     /// It will NOT create the database file if it doesn't exist.
-    pub async fn new_async(dir: &Path) -> Self {
-        // Removed code that created DB if the file didn't exist
-        let path_to_db = dir.join("UserStats.db");
-
-        let db_url = format!("sqlite://{}", path_to_db.to_string_lossy());
-        let db: Arc<DatabaseConnection> =
-            Arc::new(SqlitePool::connect(&db_url).await.unwrap().into());
-        let user_stats_table = UserStatsTable::new_async(db.clone()).await;
-        let user_addins_table = UserAddinsTable::new_async(db.clone()).await;
+    pub fn new(local_db: Arc<LocalDbService>, default_dir: PathBuf) -> Self {
         Self {
-            user_stats_table,
-            user_addins_table,
+            local_db,
+            default_dir,
+            state: Mutex::new(ConnectionState::default()),
+            connecting: Mutex::new(()),
+        }
+    }
+
+    pub async fn user_stats_table(&self) -> Result<UserStatsTable, String> {
+        let state = self.connected_state(false).await;
+        match &state.connection {
+            Some(connection) => Ok(connection.user_stats_table.clone()),
+            None => Err(offline_error(&state)),
+        }
+    }
+
+    pub async fn user_addins_table(&self) -> Result<UserAddinsTable, String> {
+        let state = self.connected_state(false).await;
+        match &state.connection {
+            Some(connection) => Ok(connection.user_addins_table.clone()),
+            None => Err(offline_error(&state)),
+        }
+    }
+
+    /// Returns whether the database can be reached. Connects first if a connection attempt is due
+    pub async fn status(&self) -> StatsDbStatusModel {
+        let state = self.connected_state(false).await;
+        self.status_of(&state).await
+    }
+
+    /// Tries to connect right away, ignoring the backoff
+    pub async fn reconnect(&self) -> StatsDbStatusModel {
+        let state = self.connected_state(true).await;
+        self.status_of(&state).await
+    }
+
+    /// Locks the state, connecting first if there is no connection and an attempt is due
+    ///
+    /// The state isn't locked while connecting, since an unreachable share only fails after `CONNECT_TIMEOUT`.
+    /// Meanwhile, other callers see the database as offline instead of waiting
+    async fn connected_state(&self, force: bool) -> tokio::sync::MutexGuard<'_, ConnectionState> {
+        let dir = self.configured_dir().await;
+        let mut state = self.state.lock().await;
+
+        // The location was changed in the settings
+        if state
+            .connection
+            .as_ref()
+            .is_some_and(|connection| connection.dir != dir)
+        {
+            *state = ConnectionState::default();
         }
+
+        if state
+            .connection
+            .as_ref()
+            .is_some_and(|connection| connection.connection_lost.is_lost())
+        {
+            // Connect again right away. If that fails, the backoff starts over
+            *state = ConnectionState {
+                last_error: Some(format!("Lost the connection to {}", dir.display())),
+                ..Default::default()
+            };
+        }
+
+        let attempt_is_due = state
+            .next_attempt_at
+            .map_or(true, |next_attempt_at| Instant::now() >= next_attempt_at);
+        if state.connection.is_some() || !(force || attempt_is_due) {
+            return state;
+        }
+        // Another caller is connecting already
+        let Ok(_connecting) = self.connecting.try_lock() else {
+            return state;
+        };
+        drop(state);
+
+        let connection = connect(&dir).await;
+        let mut state = self.state.lock().await;
+        match connection {
+            Ok(connection) => {
+                *state = ConnectionState {
+                    connection: Some(connection),
+                    ..Default::default()
+                };
+            }
+            Err(e) => {
+                warn!("Stats database at {} is offline: {}", dir.display(), e);
+                state.failed_attempts += 1;
+                state.next_attempt_at = Some(Instant::now() + retry_delay(state.failed_attempts));
+                state.last_error = Some(e);
+            }
+        }
+        state
     }
 
-    pub fn user_stats_table(&self) -> &UserStatsTable {
-        &self.user_stats_table
+    async fn configured_dir(&self) -> PathBuf {
+        match keys::get_stats_db_path(self.local_db.clone()).await {
+            Ok(path) if !path.trim().is_empty() => PathBuf::from(path.trim()),
+            Ok(_) => self.default_dir.clone(),
+            Err(e) => {
+                warn!(
+                    "Could not read the stats database path, using the default: {}",
+                    e
+                );
+                self.default_dir.clone()
+            }
+        }
+    }
+
+    async fn status_of(&self, state: &ConnectionState) -> StatsDbStatusModel {
+        let path = match &state.connection {
+            Some(connection) => connection.dir.clone(),
+            None => self.configured_dir().await,
+        };
+        StatsDbStatusModel {
+            is_online: state.connection.is_some(),
+            path: path.to_string_lossy().to_string(),
+            failed_attempts: state.failed_attempts,
+            last_error: state.last_error.clone(),
+            retry_in_secs: state
+                .next_attempt_at
+                .filter(|_| state.connection.is_none())
+                .map(|next_attempt_at| {
+                    next_attempt_at
+                        .saturating_duration_since(Instant::now())
+                        .as_secs()
+                }),
+        }
+    }
+}
+
+async fn connect(dir: &std::path::Path) -> Result<StatsDbConnection, String> {
+    let path_to_db = dir.join(DB_FILE_NAME);
+    let db_url = format!("sqlite://{}", path_to_db.to_string_lossy());
+    let pool = tokio::time::timeout(CONNECT_TIMEOUT, SqlitePool::connect(&db_url))
+        .await
+        .map_err(|_| format!("Timed out connecting to {}", path_to_db.display()))?
+        .map_err(|e| format!("Could not open {}: {}", path_to_db.display(), e))?;
+    let db: Arc<DatabaseConnection> = Arc::new(pool.into());
+    let connection_lost = ConnectionLost::default();
+    Ok(StatsDbConnection {
+        dir: dir.to_path_buf(),
+        user_stats_table: UserStatsTable::new_async(db.clone(), connection_lost.clone()).await,
+        user_addins_table: UserAddinsTable::new_async(db, connection_lost.clone()).await,
+        connection_lost,
+    })
+}
+
+fn offline_error(state: &ConnectionState) -> String {
+    match &state.last_error {
+        Some(e) => format!("The stats database is offline: {}", e),
+        None => "The stats database is offline".to_string(),
+    }
+}
+
+/// 5s, 10s, 20s, ... up to 5 minutes
+fn retry_delay(failed_attempts: u32) -> Duration {
+    utils::backoff_delay(INITIAL_RETRY_DELAY, MAX_RETRY_DELAY, failed_attempts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_delay_backs_off_up_to_the_max() {
+        assert_eq!(retry_delay(1), Duration::from_secs(5));
+        assert_eq!(retry_delay(2), Duration::from_secs(10));
+        assert_eq!(retry_delay(4), Duration::from_secs(40));
+        assert_eq!(retry_delay(7), MAX_RETRY_DELAY);
+        assert_eq!(retry_delay(u32::MAX), MAX_RETRY_DELAY);
     }
 
-    pub fn user_addins_table(&self) -> &UserAddinsTable {
-        &self.user_addins_table
+    #[tokio::test]
+    async fn test_connect_fails_when_share_is_missing() {
+        let dir = std::env::temp_dir().join(format!("stats_db_{}", uuid::Uuid::new_v4()));
+        let error = connect(&dir).await.err().unwrap();
+        assert!(error.contains(DB_FILE_NAME));
     }
 }
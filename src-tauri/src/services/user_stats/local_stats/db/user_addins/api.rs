@@ -1,9 +1,14 @@
 use db_manager::db::user_addins_table::*;
+use log::info;
 use sea_orm::{prelude::*, ActiveValue::Set, IntoActiveModel};
 use std::{fmt::Display, sync::Arc};
 
+use super::super::ConnectionLost;
+
+#[derive(Clone)]
 pub struct UserAddinsTable {
     db: Arc<DatabaseConnection>,
+    connection_lost: ConnectionLost,
 }
 
 #[derive(Debug)]
@@ -19,8 +24,11 @@ impl Display for UserAddinsError {
 }
 
 impl UserAddinsTable {
-    pub async fn new_async(db: Arc<DatabaseConnection>) -> Self {
-        Self { db }
+    pub async fn new_async(db: Arc<DatabaseConnection>, connection_lost: ConnectionLost) -> Self {
+        Self {
+            db,
+            connection_lost,
+        }
     }
 
     pub async fn create_user(
@@ -42,7 +50,7 @@ impl UserAddinsTable {
         let user = user
             .insert(self.db.as_ref())
             .await
-            .map_err(UserAddinsError::DbError)?;
+            .map_err(|e| UserAddinsError::DbError(self.connection_lost.check(e)))?;
         Ok(user)
     }
 
@@ -54,7 +62,7 @@ impl UserAddinsTable {
             .filter(user::Column::UserEmail.eq(user_email))
             .one(self.db.as_ref())
             .await
-            .map_err(UserAddinsError::DbError)?;
+            .map_err(|e| UserAddinsError::DbError(self.connection_lost.check(e)))?;
         Ok(user)
     }
 
@@ -81,7 +89,7 @@ impl UserAddinsTable {
                 active_user
                     .update(self.db.as_ref())
                     .await
-                    .map_err(UserAddinsError::DbError)?;
+                    .map_err(|e| UserAddinsError::DbError(self.connection_lost.check(e)))?;
 
                 Ok(())
             }
@@ -94,21 +102,21 @@ impl UserAddinsTable {
         user_email: String,
         new_user_email: String,
     ) -> Result<(), UserAddinsError> {
-        println!("Changing email from {} to {}", user_email, new_user_email);
+        info!("Changing email from {} to {}", user_email, new_user_email);
 
         // First, find the existing user
         let user = user::Entity::find()
             .filter(user::Column::UserEmail.eq(&user_email))
             .one(self.db.as_ref())
             .await
-            .map_err(UserAddinsError::DbError)?;
+            .map_err(|e| UserAddinsError::DbError(self.connection_lost.check(e)))?;
 
         if let Some(user) = user {
             // Delete the old record
             user::Entity::delete_by_id(&user_email)
                 .exec(self.db.as_ref())
                 .await
-                .map_err(UserAddinsError::DbError)?;
+                .map_err(|e| UserAddinsError::DbError(self.connection_lost.check(e)))?;
 
             // Insert a new record with the new email
             let new_user = user::ActiveModel {
@@ -121,7 +129,7 @@ impl UserAddinsTable {
             new_user
                 .insert(self.db.as_ref())
                 .await
-                .map_err(UserAddinsError::DbError)?;
+                .map_err(|e| UserAddinsError::DbError(self.connection_lost.check(e)))?;
         }
 
         Ok(())
@@ -131,7 +139,7 @@ impl UserAddinsTable {
         user::Entity::delete_by_id(user_email)
             .exec(self.db.as_ref())
             .await
-            .map_err(|e| e.to_string())?;
+            .map_err(|e| self.connection_lost.check(e).to_string())?;
         Ok(())
     }
 }
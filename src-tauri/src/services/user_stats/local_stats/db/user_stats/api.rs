@@ -1,16 +1,23 @@
 use db_manager::db::user_stats_table::*;
+use log::info;
 use sea_orm::{prelude::*, ActiveValue::Set};
 use std::sync::Arc;
 
+use super::super::ConnectionLost;
 use crate::services::user_stats::*;
 
+#[derive(Clone)]
 pub struct UserStatsTable {
     db: Arc<DatabaseConnection>,
+    connection_lost: ConnectionLost,
 }
 
 impl UserStatsTable {
-    pub async fn new_async(db: Arc<DatabaseConnection>) -> Self {
-        Self { db }
+    pub async fn new_async(db: Arc<DatabaseConnection>, connection_lost: ConnectionLost) -> Self {
+        Self {
+            db,
+            connection_lost,
+        }
     }
 
     /// Creates a new user with the given email and name
@@ -38,7 +45,7 @@ impl UserStatsTable {
         let user = user
             .insert(self.db.as_ref())
             .await
-            .map_err(|e| e.to_string())?;
+            .map_err(|e| self.db_error(e))?;
         Ok(user)
     }
 
@@ -48,7 +55,7 @@ impl UserStatsTable {
             .filter(user::Column::UserEmail.eq(user_email))
             .one(self.db.as_ref())
             .await
-            .map_err(|e| e.to_string())?;
+            .map_err(|e| self.db_error(e))?;
         Ok(user)
     }
 
@@ -57,21 +64,21 @@ impl UserStatsTable {
         user_email: String,
         new_user_email: String,
     ) -> Result<(), String> {
-        println!("Changing email from {} to {}", user_email, new_user_email);
+        info!("Changing email from {} to {}", user_email, new_user_email);
 
         // First, find the existing user
         let user = user::Entity::find()
             .filter(user::Column::UserEmail.eq(&user_email))
             .one(self.db.as_ref())
             .await
-            .map_err(|e| e.to_string())?;
+            .map_err(|e| self.db_error(e))?;
 
         if let Some(user) = user {
             // Delete the old record
             user::Entity::delete_by_id(&user_email)
                 .exec(self.db.as_ref())
                 .await
-                .map_err(|e| e.to_string())?;
+                .map_err(|e| self.db_error(e))?;
 
             // Insert a new record with the new email
             let new_user = user::ActiveModel {
@@ -85,7 +92,7 @@ impl UserStatsTable {
             new_user
                 .insert(self.db.as_ref())
                 .await
-                .map_err(|e| e.to_string())?;
+                .map_err(|e| self.db_error(e))?;
         }
 
         Ok(())
@@ -100,7 +107,7 @@ impl UserStatsTable {
             .filter(user::Column::UserEmail.eq(user_email))
             .one(self.db.as_ref())
             .await
-            .map_err(|e| e.to_string())?;
+            .map_err(|e| self.db_error(e))?;
         if let Some(user) = user {
             let user = user::ActiveModel {
                 user_name: Set(new_user_name),
@@ -108,7 +115,7 @@ impl UserStatsTable {
             };
             user.update(self.db.as_ref())
                 .await
-                .map_err(|e| e.to_string())?;
+                .map_err(|e| self.db_error(e))?;
         }
         Ok(())
     }
@@ -122,7 +129,7 @@ impl UserStatsTable {
             .filter(user::Column::UserEmail.eq(user_email))
             .one(self.db.as_ref())
             .await
-            .map_err(|e| e.to_string())?;
+            .map_err(|e| self.db_error(e))?;
         if let Some(user) = user {
             let user = user::ActiveModel {
                 published_addins: Set(serde_json::to_value(published_addins).unwrap()),
@@ -130,7 +137,7 @@ impl UserStatsTable {
             };
             user.update(self.db.as_ref())
                 .await
-                .map_err(|e| e.to_string())?;
+                .map_err(|e| self.db_error(e))?;
         }
         Ok(())
     }
@@ -144,7 +151,7 @@ impl UserStatsTable {
             .filter(user::Column::UserEmail.eq(user_email))
             .one(self.db.as_ref())
             .await
-            .map_err(|e| e.to_string())?;
+            .map_err(|e| self.db_error(e))?;
         if let Some(user) = user {
             let user = user::ActiveModel {
                 installed_addins: Set(serde_json::to_value(installed_addins).unwrap()),
@@ -152,7 +159,7 @@ impl UserStatsTable {
             };
             user.update(self.db.as_ref())
                 .await
-                .map_err(|e| e.to_string())?;
+                .map_err(|e| self.db_error(e))?;
         }
         Ok(())
     }
@@ -166,7 +173,7 @@ impl UserStatsTable {
             .filter(user::Column::UserEmail.eq(user_email))
             .one(self.db.as_ref())
             .await
-            .map_err(|e| e.to_string())?;
+            .map_err(|e| self.db_error(e))?;
         if let Some(user) = user {
             let user = user::ActiveModel {
                 disciplines: Set(serde_json::to_value(disciplines).unwrap()),
@@ -174,7 +181,7 @@ impl UserStatsTable {
             };
             user.update(self.db.as_ref())
                 .await
-                .map_err(|e| e.to_string())?;
+                .map_err(|e| self.db_error(e))?;
         }
         Ok(())
     }
@@ -183,7 +190,7 @@ impl UserStatsTable {
         let users = user::Entity::find()
             .all(self.db.as_ref())
             .await
-            .map_err(|e| e.to_string())?;
+            .map_err(|e| self.db_error(e))?;
         let user_stats = users.into_iter().map(UserStatsModel::from).collect();
         Ok(user_stats)
     }
@@ -192,11 +199,15 @@ impl UserStatsTable {
         user::Entity::delete_by_id(user_email)
             .exec(self.db.as_ref())
             .await
-            .map_err(|e| e.to_string())?;
+            .map_err(|e| self.db_error(e))?;
         Ok(())
     }
 
     // Util:
+    fn db_error(&self, e: DbErr) -> String {
+        self.connection_lost.check(e).to_string()
+    }
+
     fn err_if_str_is_empty(str: &str, value_name: &str) -> Result<(), String> {
         if str.is_empty() {
             return Err(format!(
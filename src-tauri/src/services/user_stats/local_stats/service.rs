@@ -2,35 +2,40 @@ use crate::services::addins_registry::models::addin_model::AddinModel;
 use crate::services::addins_registry::services::local_registry::LocalAddinsRegistryService;
 use crate::services::addins_registry::services::AddinsRegistry;
 use crate::services::config::keys;
+use crate::services::local_addins::disabling;
 use crate::services::local_addins::service::LocalAddinsService;
 use crate::services::local_db::service::LocalDbService;
 use crate::services::user_stats::db::LocalStatsDbHandler;
 use crate::services::user_stats::*;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 pub struct LocalUserStatsService {
     local_db: Arc<LocalDbService>,
     addins_registry: Arc<LocalAddinsRegistryService>,
+    local_addins: Arc<LocalAddinsService>,
     pub stats_db: LocalStatsDbHandler,
 }
 
 impl LocalUserStatsService {
-    pub async fn new_async(
+    /// `default_stats_db_dir` is used until a stats database location is configured
+    pub fn new(
         db: Arc<LocalDbService>,
         addins_registry: Arc<LocalAddinsRegistryService>,
-        path_to_stats_db: &Path,
+        local_addins: Arc<LocalAddinsService>,
+        default_stats_db_dir: PathBuf,
     ) -> Self {
-        let stats_db = LocalStatsDbHandler::new_async(path_to_stats_db).await;
+        let stats_db = LocalStatsDbHandler::new(db.clone(), default_stats_db_dir);
         Self {
             local_db: db,
             addins_registry,
+            local_addins,
             stats_db,
         }
     }
 
     pub async fn get_all_user_stats(&self) -> Result<Vec<UserStatsModel>, String> {
-        let table = self.stats_db.user_stats_table();
+        let table = self.stats_db.user_stats_table().await?;
         table.get_all_user_stats().await.map_err(|e| e.to_string())
     }
 
@@ -38,13 +43,14 @@ impl LocalUserStatsService {
     ///
     /// Returns the user stats of the user that is currently using the app, or None if the user does not exist
     pub async fn refresh_user_stats(&self) -> Result<Option<UserStatsModel>, String> {
+        // Bail out before scanning the addins if the stats database is offline
+        let table = self.stats_db.user_stats_table().await?;
+
         let user_email = keys::get_user_email(self.local_db.clone()).await?;
         let disciplines = keys::get_user_disciplines(self.local_db.clone()).await?;
 
         let published_addins = self.get_published_addins().await?;
-        let installed_addins = self.get_installed_addins()?;
-
-        let table = self.stats_db.user_stats_table();
+        let installed_addins = self.get_installed_addins().await?;
 
         // Refresh the user stats:
         table
@@ -62,11 +68,21 @@ impl LocalUserStatsService {
     }
 
     pub async fn does_user_exist(&self, user_email: String) -> Result<bool, String> {
-        let table = self.stats_db.user_stats_table();
+        let table = self.stats_db.user_stats_table().await?;
         let user_stats = table.get_user(user_email).await?;
         Ok(user_stats.is_some())
     }
 
+    /// Whether the stats database can currently be reached
+    pub async fn get_stats_db_status(&self) -> StatsDbStatusModel {
+        self.stats_db.status().await
+    }
+
+    /// Tries to reconnect to the stats database right away
+    pub async fn reconnect_stats_db(&self) -> StatsDbStatusModel {
+        self.stats_db.reconnect().await
+    }
+
     async fn get_published_addins(&self) -> Result<Vec<PublishedAddinModel>, String> {
         let user_email = keys::get_user_email(self.local_db.clone()).await?;
 
@@ -89,12 +105,27 @@ impl LocalUserStatsService {
         Ok(published_addins)
     }
 
-    fn get_installed_addins(&self) -> Result<Vec<InstalledAddinModel>, String> {
-        let local_addins = LocalAddinsService::get_local_addins().map_err(|e| e.to_string())?;
+    /// The install date comes from the install receipt, if the addin was installed by the app
+    async fn get_installed_addins(&self) -> Result<Vec<InstalledAddinModel>, String> {
+        let local_addins = self
+            .local_addins
+            .get_local_addins()
+            .map_err(|e| e.to_string())?;
+        let receipts = self.local_db.install_receipts_table().get_all().await?;
 
         let installed_addins = local_addins
             .into_iter()
-            .map(|addin| InstalledAddinModel::from(addin.clone()))
+            .map(|addin| {
+                let xml_file =
+                    disabling::enabled_xml_path(Path::new(&addin.path_to_addin_xml_file));
+                match receipts
+                    .iter()
+                    .find(|receipt| Path::new(&receipt.installed_xml_file) == xml_file)
+                {
+                    Some(receipt) => InstalledAddinModel::from_receipt(addin, receipt),
+                    None => InstalledAddinModel::from(addin),
+                }
+            })
             .collect::<Vec<InstalledAddinModel>>();
 
         Ok(installed_addins)
@@ -5,7 +5,10 @@ use tauri::State;
 use crate::services::{
     config::keys,
     local_db::service::LocalDbService,
-    user_stats::{models::UserStatsModel, *},
+    user_stats::{
+        models::{StatsDbStatusModel, UserStatsModel},
+        *,
+    },
 };
 
 /// Creates a new user with the given email and name
@@ -19,7 +22,7 @@ pub async fn create_user_stats(
 ) -> Result<UserStatsModel, String> {
     let user_email = keys::get_user_email(local_db_service.inner().clone()).await?;
     let user_name = keys::get_user_name(local_db_service.inner().clone()).await?;
-    let user_stats_table = user_stats_service.stats_db.user_stats_table();
+    let user_stats_table = user_stats_service.stats_db.user_stats_table().await?;
     let user_stats = user_stats_table
         .create_user(user_email, user_name)
         .await
@@ -69,8 +72,8 @@ pub async fn change_user_stats_email(
     local_db_service: State<'_, Arc<LocalDbService>>,
 ) -> Result<(), String> {
     let user_email = keys::get_user_email(local_db_service.inner().clone()).await?;
-    let user_stats_table = user_stats_service.stats_db.user_stats_table();
-    let user_addins_table = user_stats_service.stats_db.user_addins_table();
+    let user_stats_table = user_stats_service.stats_db.user_stats_table().await?;
+    let user_addins_table = user_stats_service.stats_db.user_addins_table().await?;
     user_stats_table
         .change_email(user_email.clone(), new_user_email.clone())
         .await
@@ -90,10 +93,28 @@ pub async fn change_user_stats_name(
     local_db_service: State<'_, Arc<LocalDbService>>,
 ) -> Result<(), String> {
     let user_email = keys::get_user_email(local_db_service.inner().clone()).await?;
-    let user_stats_table = user_stats_service.stats_db.user_stats_table();
+    let user_stats_table = user_stats_service.stats_db.user_stats_table().await?;
     user_stats_table
         .change_name(user_email, new_user_name)
         .await
         .map_err(|e| e.to_string())?;
     Ok(())
 }
+
+/// Returns whether the stats database can currently be reached.
+///
+/// While it is offline, stats, permissions and automatic installs are unavailable
+#[tauri::command]
+pub async fn get_stats_db_status(
+    user_stats_service: State<'_, Arc<LocalUserStatsService>>,
+) -> Result<StatsDbStatusModel, String> {
+    Ok(user_stats_service.get_stats_db_status().await)
+}
+
+/// Tries to reconnect to the stats database right away, e.g. after the location was changed
+#[tauri::command]
+pub async fn reconnect_stats_db(
+    user_stats_service: State<'_, Arc<LocalUserStatsService>>,
+) -> Result<StatsDbStatusModel, String> {
+    Ok(user_stats_service.reconnect_stats_db().await)
+}
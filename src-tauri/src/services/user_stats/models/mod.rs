@@ -1,2 +1,4 @@
+pub mod stats_db_status_model;
 pub mod user_stats_model;
+pub use stats_db_status_model::*;
 pub use user_stats_model::*;
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+/// Whether the shared stats database can currently be reached
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatsDbStatusModel {
+    pub is_online: bool,
+    /// The folder that holds the stats database
+    pub path: String,
+    /// The number of failed connection attempts since the last successful one
+    pub failed_attempts: u32,
+    pub last_error: Option<String>,
+    /// When offline, the number of seconds until the next connection attempt
+    pub retry_in_secs: Option<u64>,
+}
@@ -1,6 +1,7 @@
 use std::path::Path;
 
 use crate::services::addins_registry::models::addin_model::AddinModel;
+use crate::services::local_addins::models::install_receipt_model::InstallReceiptModel;
 use db_manager::db::user_stats_table::user;
 use serde::{Deserialize, Serialize};
 
@@ -42,6 +43,20 @@ pub struct InstalledAddinModel {
     pub date_installed: String,
 }
 
+impl InstalledAddinModel {
+    /// Uses the install time recorded in the receipt, which unlike the file's modification time isn't changed by updates
+    pub fn from_receipt(addin: AddinModel, receipt: &InstallReceiptModel) -> Self {
+        let date_installed = chrono::DateTime::parse_from_rfc3339(&receipt.installed_at)
+            .map(|installed_at| installed_at.format("%Y-%m-%d").to_string())
+            .unwrap_or_else(|_| "Unknown".to_string());
+        Self {
+            addin,
+            date_installed,
+        }
+    }
+}
+
+/// Guesses the install date from the modification time of the .addin file, for addins without an install receipt
 impl From<AddinModel> for InstalledAddinModel {
     fn from(value: AddinModel) -> Self {
         let addin = value.clone();
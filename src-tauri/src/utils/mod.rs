@@ -1,6 +1,7 @@
 use rayon::prelude::*;
 use std::fs;
 use std::path::Path;
+use std::time::Duration;
 
 pub fn copy_dir_all(src: &Path, dst: &Path) -> std::io::Result<()> {
     fs::create_dir_all(dst)?;
@@ -36,3 +37,29 @@ pub fn copy_dir_all(src: &Path, dst: &Path) -> std::io::Result<()> {
 pub fn double_backslash_to_single_forward_slash(path: &str) -> String {
     path.replace("\\", "/")
 }
+
+/// Exponential backoff: waits `initial` after the first failed attempt and twice as long after every further one,
+/// up to `max`
+pub fn backoff_delay(initial: Duration, max: Duration, failed_attempts: u32) -> Duration {
+    let doublings = failed_attempts.saturating_sub(1).min(16);
+    initial.saturating_mul(1 << doublings).min(max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_delay_doubles_up_to_the_max() {
+        let initial = Duration::from_secs(1);
+        let max = Duration::from_secs(10);
+        assert_eq!(backoff_delay(initial, max, 0), initial);
+        assert_eq!(backoff_delay(initial, max, 1), initial);
+        assert_eq!(backoff_delay(initial, max, 3), Duration::from_secs(4));
+        assert_eq!(backoff_delay(initial, max, 5), max);
+        assert_eq!(
+            backoff_delay(Duration::MAX, Duration::MAX, u32::MAX),
+            Duration::MAX
+        );
+    }
+}
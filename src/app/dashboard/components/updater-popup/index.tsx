@@ -1,109 +1,85 @@
 import { useEffect, useState } from "react";
-import { check, Update } from "@tauri-apps/plugin-updater";
-import { relaunch } from "@tauri-apps/plugin-process";
+import { listen } from "@tauri-apps/api/event";
 import {
   Dialog,
   DialogContent,
-  DialogDescription,
   DialogFooter,
   DialogHeader,
   DialogTitle,
 } from "@/components/ui/dialog";
-import { PackageOpen, Download, CheckCircle, Loader2 } from "lucide-react";
+import {
+  PackageOpen,
+  Download,
+  CheckCircle,
+  Loader2,
+  Clock,
+} from "lucide-react";
 import { Button } from "@/components/ui/button";
 import { Progress } from "@/components/ui/progress";
+import getTauriCommands from "@/lib/commands/getTauriCommands";
+import { AppUpdateStateModel } from "@/lib/models/app-update.model";
 
-type UpdateStatus =
-  | "idle"
-  | "downloading"
-  | "installing"
-  | "completed"
-  | "error";
+const toMegabytes = (bytes: number) =>
+  Math.round((bytes / 1024 / 1024) * 100) / 100;
 
+// The backend checks for app updates on startup. This pops up once it found one
 export default function UpdaterPopup() {
   const [isVisible, setIsVisible] = useState(false);
-  const [update, setUpdate] = useState<Update | undefined>(undefined);
-  const [status, setStatus] = useState<UpdateStatus>("idle");
-  const [progress, setProgress] = useState(0);
-  const [downloaded, setDownloaded] = useState(0);
-  const [contentLength, setContentLength] = useState(0);
-  const [error, setError] = useState<string | null>(null);
+  const [state, setState] = useState<AppUpdateStateModel>({ stage: "idle" });
 
   useEffect(() => {
-    const checkForUpdates = async () => {
-      try {
-        const update = await check();
-        if (update) {
-          setIsVisible(true);
-          setUpdate(update);
-        }
-      } catch (err) {
-        console.error("Failed to check for updates:", err);
+    const showState = (newState: AppUpdateStateModel) => {
+      setState(newState);
+      if (newState.stage === "available" || newState.stage === "downloaded") {
+        setIsVisible(true);
       }
     };
-    checkForUpdates();
-  }, []);
-  
 
-  const handleUpdate = async () => {
-    if (!update) return;
+    getTauriCommands()
+      .getAppUpdateState()
+      .then(showState)
+      .catch((error) =>
+        console.error("Failed to get the app update state:", error)
+      );
 
-    try {
-      setStatus("downloading");
-      setProgress(0);
-      setDownloaded(0);
-      setError(null);
-
-      await update.downloadAndInstall((event) => {
-        switch (event.event) {
-          case "Started":
-            setContentLength(event.data.contentLength || 0);
-            console.log(
-              `started downloading ${event.data.contentLength} bytes`
-            );
-            break;
-          case "Progress":
-            setDownloaded((prev) => prev + event.data.chunkLength);
-            if (contentLength > 0) {
-              const progressPercent = Math.round(
-                ((downloaded + event.data.chunkLength) / contentLength) * 100
-              );
-              setProgress(progressPercent);
-            }
-            console.log(
-              `downloaded ${
-                downloaded + event.data.chunkLength
-              } from ${contentLength}`
-            );
-            break;
-          case "Finished":
-            setStatus("installing");
-            console.log("download finished");
-            break;
-        }
-      });
+    const unlisten = listen<AppUpdateStateModel>(
+      "app_update_state_changed",
+      (event) => showState(event.payload)
+    );
 
-      setStatus("completed");
-      console.log("update installed");
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
 
-      // Give user a moment to see completion status before relaunching
-      setTimeout(async () => {
-        await relaunch();
-      }, 2000);
-    } catch (err) {
-      setStatus("error");
-      setError(err instanceof Error ? err.message : "Update failed");
-      console.error("Update failed:", err);
+  const handleUpdate = async () => {
+    try {
+      if (state.stage !== "downloaded") {
+        await getTauriCommands().downloadAppUpdate();
+      }
+      // The app restarts once the update is installed
+      await getTauriCommands().applyAppUpdate();
+    } catch (error) {
+      // The backend sends the failed state with the reason
+      console.error("Update failed:", error);
     }
   };
 
+  const update = "update" in state ? state.update : undefined;
+  const isUpdateInProgress =
+    state.stage === "downloading" ||
+    state.stage === "waitingForInstalls" ||
+    state.stage === "installing";
+
   const getStatusIcon = () => {
-    switch (status) {
+    switch (state.stage) {
       case "downloading":
         return <Download className="w-4 h-4 animate-pulse" />;
+      case "waitingForInstalls":
+        return <Clock className="w-4 h-4" />;
       case "installing":
         return <Loader2 className="w-4 h-4 animate-spin" />;
-      case "completed":
+      case "downloaded":
         return <CheckCircle className="w-4 h-4 text-green-500" />;
       default:
         return <PackageOpen className="w-4 h-4" />;
@@ -111,54 +87,52 @@ export default function UpdaterPopup() {
   };
 
   const getStatusText = () => {
-    switch (status) {
+    switch (state.stage) {
       case "downloading":
-        return `Downloading update... ${progress}%`;
+        return state.total
+          ? `Downloading update... ${Math.round(
+              (state.downloaded / state.total) * 100
+            )}%`
+          : "Downloading update...";
+      case "waitingForInstalls":
+        return "Waiting for addin installs to finish...";
       case "installing":
         return "Installing update...";
-      case "completed":
-        return "Update completed! Restarting app...";
-      case "error":
-        return `Update failed: ${error}`;
+      case "downloaded":
+        return "Update ready";
+      case "failed":
+        return "Update failed";
       default:
         return "Update Available";
     }
   };
 
   const getDescription = () => {
-    if (status === "error") {
-      return error;
-    }
-
-    if (status === "downloading") {
-      return `Downloading version ${update?.version}...`;
-    }
-
-    if (status === "installing") {
-      return "Installing the update...";
-    }
-
-    if (status === "completed") {
-      return "Update installed successfully. The app will restart in a few seconds.";
+    switch (state.stage) {
+      case "failed":
+        return state.error;
+      case "downloading":
+        return `Downloading version ${update?.version}...`;
+      case "waitingForInstalls":
+        return "The app restarts as soon as no addin is being installed, updated or uninstalled.";
+      case "installing":
+        return "Installing the update. The app restarts when it is done.";
     }
 
     return (
       <div>
         A new version of the app is available: <b>{update?.version}</b>
-        {update?.body && (
+        {update?.channel === "beta" && " (beta)"}
+        {update?.notes && (
           <div className="mt-2 text-sm text-muted-foreground">
             <strong>What&apos;s new:</strong>
-            <div className="mt-1 whitespace-pre-wrap">{update.body}</div>
+            <div className="mt-1 whitespace-pre-wrap">{update.notes}</div>
           </div>
         )}
       </div>
     );
   };
 
-  const isUpdateInProgress =
-    status === "downloading" || status === "installing";
-  const isCompleted = status === "completed";
-
   return (
     <Dialog open={isVisible} onOpenChange={setIsVisible}>
       <DialogContent>
@@ -171,12 +145,15 @@ export default function UpdaterPopup() {
           </DialogTitle>
         </DialogHeader>
         <div className="mt-4">{getDescription()}</div>
-        {status === "downloading" && contentLength > 0 && (
+        {state.stage === "downloading" && state.total && (
           <div className="mt-4">
-            <Progress value={progress} className="w-full" />
+            <Progress
+              value={Math.round((state.downloaded / state.total) * 100)}
+              className="w-full"
+            />
             <div className="text-xs text-muted-foreground mt-1">
-              {Math.round((downloaded / 1024 / 1024) * 100) / 100} MB /{" "}
-              {Math.round((contentLength / 1024 / 1024) * 100) / 100} MB
+              {toMegabytes(state.downloaded)} MB / {toMegabytes(state.total)}{" "}
+              MB
             </div>
           </div>
         )}
@@ -184,22 +161,19 @@ export default function UpdaterPopup() {
           <Button
             className="flex w-full"
             onClick={handleUpdate}
-            disabled={isUpdateInProgress || isCompleted}
+            disabled={isUpdateInProgress}
           >
             {isUpdateInProgress ? (
               <>
                 <Loader2 className="w-4 h-4 mr-2 animate-spin" />
-                {status === "downloading" ? "Downloading..." : "Installing..."}
-              </>
-            ) : isCompleted ? (
-              <>
-                <CheckCircle className="w-4 h-4 mr-2" />
-                Restarting...
+                {state.stage === "downloading"
+                  ? "Downloading..."
+                  : "Installing..."}
               </>
-            ) : status === "error" ? (
+            ) : state.stage === "failed" ? (
               "Retry Update"
             ) : (
-              "Update"
+              "Update and restart"
             )}
           </Button>
         </DialogFooter>
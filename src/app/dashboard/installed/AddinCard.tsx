@@ -1,16 +1,34 @@
+import { useEffect, useState } from "react";
 import { Badge } from "@/components/ui/badge";
 import { AddinModel } from "@/lib/models/addin.model";
 import { Button } from "@/components/ui/button";
+import getTauriCommands from "@/lib/commands/getTauriCommands";
+import { DllConflictModel } from "@/lib/models/dll-conflict.model";
 
 interface AddinCardProps {
   addin: AddinModel;
   onUninstallClicked: () => void;
+  onAdoptClicked: () => void;
+  onToggleDisabledClicked: () => void;
+  onVerifyClicked: () => void;
 }
 
 export default function AddinCard({
   addin,
   onUninstallClicked,
+  onAdoptClicked,
+  onToggleDisabledClicked,
+  onVerifyClicked,
 }: AddinCardProps) {
+  const [dllConflicts, setDllConflicts] = useState<DllConflictModel[]>([]);
+
+  useEffect(() => {
+    getTauriCommands()
+      .getAddinDllConflicts(addin)
+      .then(setDllConflicts)
+      .catch(() => setDllConflicts([]));
+  }, [addin]);
+
   return (
     <div className="border rounded-lg p-4">
       <div className="flex items-start justify-between">
@@ -24,6 +42,24 @@ export default function AddinCard({
         <div className="flex flex-col justify-between gap-2 items-end">
           <div className="flex flex-col items-end gap-2">
             <Badge variant="outline">{addin.addinType}</Badge>
+            {addin.installScope === "machine" && (
+              <Badge variant="secondary">All users</Badge>
+            )}
+            {addin.isDisabled && <Badge variant="secondary">Disabled</Badge>}
+            {dllConflicts.length > 0 && (
+              <Badge
+                variant="destructive"
+                title={`Revit ${dllConflicts[0].revitVersion} already ships ${dllConflicts
+                  .map((conflict) => conflict.dllName)
+                  .join(", ")}`}
+              >
+                {dllConflicts.length} DLL conflict
+                {dllConflicts.length > 1 && "s"}
+              </Badge>
+            )}
+            {addin.management === "foreign" && (
+              <Badge variant="secondary">Not from the registry</Badge>
+            )}
             {addin.email && (
               <span className="text-xs text-muted-foreground">
                 {addin.email}
@@ -31,13 +67,41 @@ export default function AddinCard({
             )}
           </div>
 
-          <Button
-            className="text-sm text-destructive"
-            onClick={onUninstallClicked}
-            variant="link"
-          >
-            Uninstall
-          </Button>
+          {/* Foreign addins are only shown, the app never changes them */}
+          {addin.management !== "foreign" && (
+            <div className="flex gap-2">
+              {addin.management === "unmanaged" && (
+                <Button
+                  className="text-sm"
+                  onClick={onAdoptClicked}
+                  variant="link"
+                >
+                  Manage
+                </Button>
+              )}
+              <Button
+                className="text-sm"
+                onClick={onVerifyClicked}
+                variant="link"
+              >
+                Verify
+              </Button>
+              <Button
+                className="text-sm"
+                onClick={onToggleDisabledClicked}
+                variant="link"
+              >
+                {addin.isDisabled ? "Enable" : "Disable"}
+              </Button>
+              <Button
+                className="text-sm text-destructive"
+                onClick={onUninstallClicked}
+                variant="link"
+              >
+                Uninstall
+              </Button>
+            </div>
+          )}
         </div>
       </div>
     </div>
@@ -5,6 +5,7 @@ import { Badge } from "@/components/ui/badge";
 import useLocalAddins from "@/lib/addins/local-addins/useLocalAddins";
 import AddinCard from "./AddinCard";
 import { useInstalledAddinsStore } from "./store";
+import { toast } from "sonner";
 
 interface AddinGroupCardProps {
   group: AddinGroup;
@@ -15,7 +16,14 @@ export default function AddinGroupCard({
   group,
   refreshAddins,
 }: AddinGroupCardProps) {
-  const { uninstallAddins } = useLocalAddins();
+  const {
+    uninstallAddins,
+    adoptLocalAddins,
+    disableLocalAddins,
+    enableLocalAddins,
+    verifyLocalAddins,
+    repairLocalAddins,
+  } = useLocalAddins();
   const { setFailedToUninstallAddin } = useInstalledAddinsStore();
   const formatRevitVersions = (versions: string[]) => {
     if (versions.length === 1) {
@@ -44,7 +52,15 @@ export default function AddinGroupCard({
 
   const onUninstallClicked = async (addin: AddinModel) => {
     try {
-      await uninstallAddins([{ addin, forRevitVersions: group.revitVersions }]);
+      const results = await uninstallAddins([
+        { addin, forRevitVersions: group.revitVersions },
+      ]);
+      if (results.some((result) => result.status === "failed")) {
+        setFailedToUninstallAddin(true);
+      }
+      if (results.some((result) => result.status === "pending")) {
+        toast.info(`${addin.name} will be uninstalled once Revit is closed`);
+      }
     } catch (error) {
       console.warn(error);
       setFailedToUninstallAddin(true);
@@ -53,6 +69,87 @@ export default function AddinGroupCard({
     }
   };
 
+  const onAdoptClicked = async (addin: AddinModel) => {
+    try {
+      await adoptLocalAddins(group.copies.get(addin) ?? [addin]);
+    } catch (error) {
+      console.warn(error);
+    } finally {
+      await refreshAddins();
+    }
+  };
+
+  const onToggleDisabledClicked = async (addin: AddinModel) => {
+    try {
+      const copies = group.copies.get(addin) ?? [addin];
+      if (addin.isDisabled) {
+        await enableLocalAddins(copies);
+      } else {
+        await disableLocalAddins(copies);
+      }
+    } catch (error) {
+      console.warn(error);
+    } finally {
+      await refreshAddins();
+    }
+  };
+
+  const repair = async (addins: AddinModel[]) => {
+    try {
+      const repairs = await repairLocalAddins(addins);
+      const failed = repairs.filter(
+        (repair) => repair.error || !repair.verification.isHealthy
+      );
+      if (failed.length === 0) {
+        toast.success(`${addins[0].name} was repaired`);
+      } else {
+        toast.error(
+          `Could not repair every copy of ${addins[0].name}: ${failed
+            .map((repair) => repair.error ?? repair.verification.error)
+            .filter(Boolean)
+            .join("; ")}`
+        );
+      }
+    } catch (error) {
+      toast.error(`Failed to repair ${addins[0].name}: ${error}`);
+    } finally {
+      await refreshAddins();
+    }
+  };
+
+  const onVerifyClicked = async (addin: AddinModel) => {
+    const copies = group.copies.get(addin) ?? [addin];
+    try {
+      const verifications = await verifyLocalAddins(copies);
+      const broken = verifications.filter(
+        (verification) => !verification.isHealthy
+      );
+      if (broken.length === 0) {
+        toast.success(`${addin.name} matches the registry`);
+        return;
+      }
+      const problems = broken.flatMap((verification) => [
+        ...verification.discrepancies.map(
+          (discrepancy) => `${discrepancy.relativePath} is ${discrepancy.kind}`
+        ),
+        ...verification.unresolvedAssemblies.map(
+          (assembly) => `${assembly} can't be found`
+        ),
+        ...(verification.error ? [verification.error] : []),
+      ]);
+      toast.warning(`${addin.name} differs from the registry`, {
+        description: problems.join("; "),
+        action: {
+          label: "Repair",
+          onClick: () =>
+            repair(broken.map((verification) => verification.addin)),
+        },
+      });
+    } catch (error) {
+      toast.error(`Failed to verify ${addin.name}: ${error}`);
+    }
+  };
+
   return (
     <Card className="mb-6">
       <CardHeader>
@@ -70,6 +167,9 @@ export default function AddinGroupCard({
               key={index}
               addin={addin}
               onUninstallClicked={() => onUninstallClicked(addin)}
+              onAdoptClicked={() => onAdoptClicked(addin)}
+              onToggleDisabledClicked={() => onToggleDisabledClicked(addin)}
+              onVerifyClicked={() => onVerifyClicked(addin)}
             />
           ))}
         </div>
@@ -0,0 +1,145 @@
+import { useEffect, useState } from "react";
+import { toast } from "sonner";
+import { Badge } from "@/components/ui/badge";
+import { Button } from "@/components/ui/button";
+import { Card, CardContent, CardHeader, CardTitle } from "@/components/ui/card";
+import useTauriCommands from "@/lib/commands/getTauriCommands";
+import { BisectSessionModel } from "@/lib/models/bisect-session.model";
+
+interface BisectSessionCardProps {
+  revitVersions: string[];
+  refreshAddins: () => Promise<void>;
+}
+
+/** Guides the user through finding the addin that causes a problem in a Revit version */
+export default function BisectSessionCard({
+  revitVersions,
+  refreshAddins,
+}: BisectSessionCardProps) {
+  const {
+    getBisectSessions,
+    startBisectSession,
+    answerBisectStep,
+    endBisectSession,
+  } = useTauriCommands();
+  const [sessions, setSessions] = useState<BisectSessionModel[]>([]);
+  const [isBusy, setIsBusy] = useState(false);
+
+  const refreshSessions = async () => {
+    try {
+      setSessions(await getBisectSessions());
+    } catch (error) {
+      console.warn(error);
+    }
+  };
+
+  useEffect(() => {
+    refreshSessions();
+  }, []);
+
+  const run = async (action: () => Promise<unknown>) => {
+    try {
+      setIsBusy(true);
+      await action();
+    } catch (error) {
+      toast.error(String(error));
+    } finally {
+      await refreshSessions();
+      await refreshAddins();
+      setIsBusy(false);
+    }
+  };
+
+  const nameOf = (session: BisectSessionModel, xmlFile: string) =>
+    session.candidates.find((candidate) => candidate.xmlFile === xmlFile)
+      ?.name ?? xmlFile;
+
+  const versionsWithoutSession = revitVersions.filter(
+    (version) => !sessions.some((session) => session.revitVersion === version)
+  );
+
+  return (
+    <Card className="mb-6">
+      <CardHeader>
+        <CardTitle>Troubleshoot Revit</CardTitle>
+        <p className="text-sm text-muted-foreground">
+          Find the addin that causes a problem by turning half of them off at a
+          time.
+        </p>
+      </CardHeader>
+      <CardContent>
+        <div className="space-y-4">
+          {sessions.map((session) => (
+            <div key={session.revitVersion} className="border rounded-lg p-4">
+              <div className="flex items-center gap-2">
+                <h3 className="font-semibold">Revit {session.revitVersion}</h3>
+                <Badge variant="secondary">
+                  {session.suspects.length} suspect
+                  {session.suspects.length !== 1 ? "s" : ""}
+                </Badge>
+              </div>
+              {session.culprit ? (
+                <p className="text-sm mt-2">
+                  The problem is caused by{" "}
+                  <span className="font-semibold">{session.culprit.name}</span>.
+                  All addins have been turned back on.
+                </p>
+              ) : (
+                <>
+                  <p className="text-sm mt-2">
+                    Start Revit {session.revitVersion} and try to reproduce the
+                    problem. Only these suspects are turned on:{" "}
+                    {session.testing
+                      .map((xmlFile) => nameOf(session, xmlFile))
+                      .join(", ")}
+                  </p>
+                  <div className="flex gap-2 mt-2">
+                    <Button
+                      disabled={isBusy}
+                      onClick={() =>
+                        run(() => answerBisectStep(session.revitVersion, true))
+                      }
+                    >
+                      The problem happened
+                    </Button>
+                    <Button
+                      disabled={isBusy}
+                      variant="secondary"
+                      onClick={() =>
+                        run(() => answerBisectStep(session.revitVersion, false))
+                      }
+                    >
+                      It worked fine
+                    </Button>
+                  </div>
+                </>
+              )}
+              <Button
+                className="text-sm px-0"
+                disabled={isBusy}
+                variant="link"
+                onClick={() => run(() => endBisectSession(session.revitVersion))}
+              >
+                {session.culprit ? "Done" : "Stop and turn all addins back on"}
+              </Button>
+            </div>
+          ))}
+          {versionsWithoutSession.length > 0 && (
+            <div className="flex flex-wrap gap-2">
+              {versionsWithoutSession.map((version) => (
+                <Button
+                  key={version}
+                  disabled={isBusy}
+                  variant="outline"
+                  onClick={() => run(() => startBisectSession(version))}
+                >
+                  Troubleshoot Revit {version}
+                </Button>
+              ))}
+            </div>
+          )}
+        </div>
+      </CardContent>
+    </Card>
+  );
+}
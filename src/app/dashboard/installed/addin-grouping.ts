@@ -3,39 +3,47 @@ import { AddinModel } from "../../../lib/models/addin.model";
 export interface AddinGroup {
   revitVersions: string[];
   addins: AddinModel[];
+  // The installed copy of each addin for every Revit version in the group, keyed by the addin shown
+  copies: Map<AddinModel, AddinModel[]>;
 }
 
 export function groupAddinsByRevitVersions(addins: AddinModel[]): AddinGroup[] {
   // Step 1: Group by addin identity, collect all versions for each addin
   const addinIdentityMap = new Map<
     string,
-    { addin: AddinModel; versions: Set<string> }
+    { addin: AddinModel; versions: Set<string>; copies: AddinModel[] }
   >();
 
   for (const addin of addins) {
-    const key = `${addin.name}::${addin.vendor}::${addin.addinType}`;
+    // The same addin can be installed for the user and for the machine, which are uninstalled separately.
+    // It can also be disabled or unmanaged for some Revit versions only
+    const key = `${addin.name}::${addin.vendor}::${addin.addinType}::${addin.installScope}::${addin.management}::${addin.isDisabled}`;
     if (!addinIdentityMap.has(key)) {
-      addinIdentityMap.set(key, { addin, versions: new Set() });
+      addinIdentityMap.set(key, { addin, versions: new Set(), copies: [] });
     }
-    addinIdentityMap.get(key)!.versions.add(addin.revitVersion!);
+    const identity = addinIdentityMap.get(key)!;
+    identity.versions.add(addin.revitVersion!);
+    identity.copies.push(addin);
   }
 
   // Step 2: Group addins by their set of versions
   const versionSetMap = new Map<
     string, // key: sorted, joined versions
-    { revitVersions: string[]; addins: AddinModel[] }
+    AddinGroup
   >();
 
-  for (const { addin, versions } of addinIdentityMap.values()) {
+  for (const { addin, versions, copies } of addinIdentityMap.values()) {
     const sortedVersions = Array.from(versions).sort();
     const versionKey = sortedVersions.join(",");
     if (!versionSetMap.has(versionKey)) {
       versionSetMap.set(versionKey, {
         revitVersions: sortedVersions,
         addins: [],
+        copies: new Map(),
       });
     }
     versionSetMap.get(versionKey)!.addins.push(addin);
+    versionSetMap.get(versionKey)!.copies.set(addin, copies);
   }
 
   // Convert to AddinGroup[]
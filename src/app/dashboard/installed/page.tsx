@@ -4,6 +4,7 @@ import { useMemo } from "react";
 import useLocalAddins from "@/lib/addins/local-addins/useLocalAddins";
 import { groupAddinsByRevitVersions } from "@/app/dashboard/installed/addin-grouping";
 import AddinGroupCard from "./AddinGroupCard";
+import BisectSessionCard from "./BisectSessionCard";
 import FailedToInstallAddinDialog from "@/app/shared/FailedToUninstallAddinDialog";
 import { useInstalledAddinsStore } from "./store";
 import PageWrapper from "@/components/PageWrapper";
@@ -14,7 +15,7 @@ import PageWrapper from "@/components/PageWrapper";
  * Should also have a category for addins that are installed in every version of Revit (all)
  */
 export default function InstalledPage() {
-  const { addins, error, refreshAddins } = useLocalAddins();
+  const { addins, revitVersions, error, refreshAddins } = useLocalAddins();
   const { failedToUninstallAddin, setFailedToUninstallAddin } =
     useInstalledAddinsStore();
 
@@ -72,6 +73,10 @@ export default function InstalledPage() {
               </div>
             ) : (
               <div className="space-y-6">
+                <BisectSessionCard
+                  revitVersions={revitVersions}
+                  refreshAddins={refreshAddins}
+                />
                 {groupedAddins.map((group, index) => (
                   <AddinGroupCard
                     key={index}
@@ -13,6 +13,7 @@ import useConfig from "@/lib/persistence/config/useConfig";
 import { useAddinUpdater } from "@/lib/addins/addin-updater/useAddinUpdater";
 import { toast, Toaster } from "sonner";
 import useUserStatsUpdater from "@/lib/user-stats/useUserStatsUpdater";
+import { useAppReadiness } from "@/lib/app-readiness/useAppReadiness";
 
 export default function DashboardLayout({
   children,
@@ -24,6 +25,19 @@ export default function DashboardLayout({
   const updater = useUserStatsUpdater();
 
   const config = useConfig();
+  const readiness = useAppReadiness();
+
+  useEffect(() => {
+    if (readiness.state === "degraded") {
+      readiness.reasons.forEach((reason) => toast.warning(reason));
+    }
+    if (readiness.state === "failed") {
+      toast.error(`The app failed to start: ${readiness.error}`, {
+        duration: Infinity,
+      });
+    }
+  }, [readiness]);
+
   useAddinUpdater({
     onNewNotifications: (addinUpdateNotifications) => {
       addinUpdateNotifications.forEach((notification) => {
@@ -37,6 +51,9 @@ export default function DashboardLayout({
         if (notification.notificationType == "warning") {
           toast.warning(toastContent);
         }
+        if (notification.notificationType == "error") {
+          toast.error(toastContent);
+        }
       });
     },
   });
@@ -9,6 +9,7 @@ import useLocalAddins from "@/lib/addins/local-addins/useLocalAddins";
 import useAddinPackage from "./hooks/useAddinPackage";
 import { useCreatePackageDialogStore } from "./dialogs/create-package-dialog/store";
 import CreatePackageDialog from "./dialogs/create-package-dialog";
+import { toast } from "sonner";
 
 interface AddinPreviewProps {
   onInstallClicked?: () => void;
@@ -67,9 +68,17 @@ export default function AddinPreview({
       return;
     }
     try {
-      await uninstallAddins([
+      const results = await uninstallAddins([
         { addin: selectedAddin, forRevitVersions: revitVersions },
       ]);
+      if (results.some((result) => result.status === "failed")) {
+        setFailedToUninstallAddin(true);
+      }
+      if (results.some((result) => result.status === "pending")) {
+        toast.info(
+          `${selectedAddin.name} will be uninstalled once Revit is closed`
+        );
+      }
     } catch (error) {
       console.warn(error);
       setFailedToUninstallAddin(true);
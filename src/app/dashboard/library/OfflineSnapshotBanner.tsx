@@ -0,0 +1,67 @@
+import { useEffect, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
+import { CloudOff } from "lucide-react";
+import getTauriCommands from "@/lib/commands/getTauriCommands";
+import { RegistryMirrorStatusModel } from "@/lib/models/registry-mirror-status.model";
+
+const describeAge = (takenAt: string) => {
+  const minutes = Math.floor((Date.now() - Date.parse(takenAt)) / 60000);
+  if (minutes < 1) {
+    return "less than a minute ago";
+  }
+  if (minutes < 60) {
+    return `${minutes} minute${minutes === 1 ? "" : "s"} ago`;
+  }
+  const hours = Math.floor(minutes / 60);
+  if (hours < 24) {
+    return `${hours} hour${hours === 1 ? "" : "s"} ago`;
+  }
+  const days = Math.floor(hours / 24);
+  return `${days} day${days === 1 ? "" : "s"} ago`;
+};
+
+// Shown while the registry can't be reached and the library lists the mirrored snapshot instead
+export default function OfflineSnapshotBanner() {
+  const [status, setStatus] = useState<RegistryMirrorStatusModel | null>(
+    null
+  );
+
+  useEffect(() => {
+    getTauriCommands()
+      .getRegistryMirrorStatus()
+      .then(setStatus)
+      .catch((error) =>
+        console.error("Failed to get the registry mirror status:", error)
+      );
+
+    const unlisten = listen<RegistryMirrorStatusModel>(
+      "registry_mirror_status_changed",
+      (event) => setStatus(event.payload)
+    );
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  if (!status?.isBrowsingSnapshot) {
+    return null;
+  }
+
+  return (
+    <div className="flex items-start gap-3 rounded-lg border border-yellow-500/50 bg-yellow-500/10 p-3 mb-4">
+      <CloudOff className="w-4 h-4 mt-0.5 text-yellow-600 flex-shrink-0" />
+      <div className="text-sm">
+        <p className="font-medium">The registry can&apos;t be reached</p>
+        <p className="text-muted-foreground">
+          You are browsing a saved copy
+          {status.snapshotTakenAt &&
+            ` from ${describeAge(status.snapshotTakenAt)}`}
+          . Only the {status.mirroredAddinIds.length} addin
+          {status.mirroredAddinIds.length === 1 ? "" : "s"} you are allowed to
+          use can be installed until it is back.
+        </p>
+      </div>
+    </div>
+  );
+}
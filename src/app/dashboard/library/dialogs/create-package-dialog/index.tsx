@@ -43,6 +43,8 @@ export default function CreatePackageDialog({
     displayName: addin.name,
     disciplinePackage: null,
     emoji: null,
+    minRevitVersion: null,
+    maxRevitVersion: null,
   });
 
   const [selectedDiscipline, setSelectedDiscipline] = useState<string>("");
@@ -62,6 +64,8 @@ export default function CreatePackageDialog({
         displayName: existingPackage.displayName,
         disciplinePackage: existingPackage.disciplinePackage || null,
         emoji: existingPackage.emoji || null,
+        minRevitVersion: existingPackage.minRevitVersion || null,
+        maxRevitVersion: existingPackage.maxRevitVersion || null,
       });
       setSelectedDiscipline(existingPackage.disciplinePackage || "");
     } else {
@@ -73,6 +77,8 @@ export default function CreatePackageDialog({
         displayName: addin.name,
         disciplinePackage: null,
         emoji: null,
+        minRevitVersion: null,
+        maxRevitVersion: null,
       });
       setSelectedDiscipline("");
     }
@@ -368,6 +374,43 @@ export default function CreatePackageDialog({
             )}
           </div>
 
+          {/* Supported Revit Versions */}
+          <div className="space-y-2">
+            <Label>Supported Revit Versions (Optional)</Label>
+            <p className="text-xs text-muted-foreground">
+              Automatic installs will only target installed Revit versions in
+              this range. Leave a field empty for no limit.
+            </p>
+            <div className="flex gap-2">
+              <Input
+                id="minRevitVersion"
+                type="text"
+                value={formData.minRevitVersion || ""}
+                onChange={(e) =>
+                  setFormData((prev) => ({
+                    ...prev,
+                    minRevitVersion: e.target.value || null,
+                  }))
+                }
+                placeholder="From, e.g. 2022"
+                maxLength={4}
+              />
+              <Input
+                id="maxRevitVersion"
+                type="text"
+                value={formData.maxRevitVersion || ""}
+                onChange={(e) =>
+                  setFormData((prev) => ({
+                    ...prev,
+                    maxRevitVersion: e.target.value || null,
+                  }))
+                }
+                placeholder="To, e.g. 2026"
+                maxLength={4}
+              />
+            </div>
+          </div>
+
           {/* Discipline Selection */}
           <div className="space-y-2">
             <Label htmlFor="discipline">Discipline Package (Optional)</Label>
@@ -15,8 +15,10 @@ import FailedToDelistAddinDialog from "@/app/shared/FailedToDelistAddinDialog";
 import PageWrapper from "@/components/PageWrapper";
 import MessageDialog from "@/components/dialogs/MessageDialog";
 import ConfirmDelistAddinDialog from "./dialogs/ConfirmDelistAddinDialog";
+import OfflineSnapshotBanner from "./OfflineSnapshotBanner";
 import { useAuthStore } from "@/lib/auth/useAuthStore";
 import { useAddinRegistryStore } from "@/lib/addins/addin-registry/useAddinRegistryStore";
+import { getConfigValueWithFallback } from "@/lib/persistence/config/getConfigValue";
 
 // Type-safe interface for addins with file tree path
 interface AddinWithTreePath extends AddinModel {
@@ -72,6 +74,10 @@ export default function LibraryPage() {
     const request = {
       addin: selectedAddin,
       forRevitVersions: await determineRevitVersions(selectedAddin),
+      installScope: await getConfigValueWithFallback(
+        "defaultInstallScope",
+        "user"
+      ),
     };
     console.log("reuqest", request);
     const result = await installAddins([request]);
@@ -126,6 +132,7 @@ export default function LibraryPage() {
               Browse and preview available addins. Click a folder to navigate,
               or select an addin to see more details.
             </p>
+            <OfflineSnapshotBanner />
           </div>
           <div className="flex flex-1 min-h-0 px-8 pb-8 gap-8">
             {/* Left: Tree View */}
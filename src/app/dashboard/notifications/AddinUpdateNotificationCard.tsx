@@ -7,7 +7,14 @@ import {
 } from "@/components/ui/card";
 import { Button } from "@/components/ui/button";
 import { UpdateNotificationModel } from "@/lib/models/update-notification.model";
-import { X, Download, Clock, CheckCircle, Info } from "lucide-react";
+import {
+  X,
+  Download,
+  Clock,
+  CheckCircle,
+  Info,
+  AlertCircle,
+} from "lucide-react";
 import { UpdateNotificationWithTime } from "@/lib/addins/addin-updater/update-notification-with-time.model";
 
 interface AddinUpdateNotificationCardProps {
@@ -22,9 +29,12 @@ export default function AddinUpdateNotificationCard({
   // Determine if this is a pending update (Revit is running) or completed update
   const isPendingUpdate = notification.notificationType === "warning";
   const isCompletedUpdate = notification.notificationType === "install";
+  const isFailedUpdate = notification.notificationType === "error";
 
   const getIcon = () => {
-    if (isPendingUpdate) {
+    if (isFailedUpdate) {
+      return <AlertCircle className="w-5 h-5 text-destructive" />;
+    } else if (isPendingUpdate) {
       return <Clock className="w-5 h-5 text-chart-3" />;
     } else if (isCompletedUpdate) {
       return <Download className="w-5 h-5 text-primary" />;
@@ -34,7 +44,9 @@ export default function AddinUpdateNotificationCard({
   };
 
   const getCardStyle = () => {
-    if (isPendingUpdate) {
+    if (isFailedUpdate) {
+      return "border-destructive/20 bg-destructive/10 hover:bg-destructive/20 transition-colors";
+    } else if (isPendingUpdate) {
       return "border-chart-3/20 bg-chart-3/10 hover:bg-chart-3/20 transition-colors";
     } else if (isCompletedUpdate) {
       return "border-primary/20 bg-primary/10 hover:bg-primary/20 transition-colors";
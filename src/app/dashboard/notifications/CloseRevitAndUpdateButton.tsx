@@ -0,0 +1,125 @@
+import { useEffect, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
+import { Loader2, RefreshCw } from "lucide-react";
+import { toast } from "sonner";
+import { Button } from "@/components/ui/button";
+import MessageDialog from "@/components/dialogs/MessageDialog";
+import getTauriCommands from "@/lib/commands/getTauriCommands";
+import { RevitProcessChangeModel } from "@/lib/models/revit-process-change.model";
+import {
+  RevitRestartProgressModel,
+  RevitRestartResultModel,
+} from "@/lib/models/revit-restart.model";
+
+// Only shown while Revit is running and there are operations waiting for it to close
+export function CloseRevitAndUpdateButton() {
+  const [isRevitRunning, setIsRevitRunning] = useState(false);
+  const [pendingCount, setPendingCount] = useState(0);
+  const [isConfirmOpen, setIsConfirmOpen] = useState(false);
+  const [progress, setProgress] = useState<string | null>(null);
+
+  const refresh = async () => {
+    try {
+      const [running, pendingOperations] = await Promise.all([
+        getTauriCommands().isRevitRunning(),
+        getTauriCommands().getPendingOperations(),
+      ]);
+      setIsRevitRunning(running);
+      setPendingCount(pendingOperations.length);
+    } catch (error) {
+      console.error("Failed to check the pending operations:", error);
+    }
+  };
+
+  useEffect(() => {
+    refresh();
+
+    const unlistenProcesses = listen<RevitProcessChangeModel>(
+      "revit_processes_changed",
+      () => refresh()
+    );
+    const unlistenProgress = listen<RevitRestartProgressModel>(
+      "revit_restart_progress",
+      (event) => setProgress(event.payload.message)
+    );
+
+    return () => {
+      unlistenProcesses.then((fn) => fn());
+      unlistenProgress.then((fn) => fn());
+    };
+  }, []);
+
+  const reportResult = (result: RevitRestartResultModel) => {
+    if (!result.revitClosed) {
+      toast.warning("Revit did not close, nothing was updated");
+    } else if (result.error) {
+      toast.error(`Updating failed: ${result.error}`);
+    } else if (result.notClosedCount > 0) {
+      toast.warning(
+        `${result.notClosedCount} Revit process${
+          result.notClosedCount === 1 ? "" : "es"
+        } could not be closed, the remaining updates are applied once Revit exits`
+      );
+    } else if (result.remainingOperations > 0) {
+      toast.warning(
+        `${result.remainingOperations} operation${
+          result.remainingOperations === 1 ? "" : "s"
+        } could not be applied and will be retried`
+      );
+    } else {
+      toast.success("Addins updated");
+    }
+  };
+
+  const closeRevitAndUpdate = async () => {
+    try {
+      setProgress("Asking Revit to close");
+      const result = await getTauriCommands().closeRevitAndUpdate();
+      reportResult(result);
+    } catch (error) {
+      console.error("Failed to close Revit and update:", error);
+      toast.error(`Failed to close Revit and update: ${error}`);
+    } finally {
+      setProgress(null);
+      refresh();
+    }
+  };
+
+  if (!progress && (!isRevitRunning || pendingCount === 0)) {
+    return null;
+  }
+
+  return (
+    <>
+      <Button
+        variant="destructive"
+        onClick={() => setIsConfirmOpen(true)}
+        disabled={progress !== null}
+      >
+        {progress ? (
+          <>
+            <Loader2 className="w-4 h-4 mr-2 animate-spin" />
+            {progress}
+          </>
+        ) : (
+          <>
+            <RefreshCw className="w-4 h-4 mr-2" />
+            Close Revit and update
+          </>
+        )}
+      </Button>
+      <MessageDialog
+        isOpen={isConfirmOpen}
+        setIsOpen={setIsConfirmOpen}
+        title="Close Revit and update?"
+        message={`Revit will be asked to close so that ${pendingCount} pending operation${
+          pendingCount === 1 ? "" : "s"
+        } can be applied. Save your work when Revit asks. Revit is reopened with the same documents afterwards.`}
+        okButtonText="Close Revit and update"
+        okButtonVariant="destructive"
+        cancelButtonText="Cancel"
+        onOk={closeRevitAndUpdate}
+      />
+    </>
+  );
+}
@@ -0,0 +1,73 @@
+import { useEffect, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
+import { AlertCircle } from "lucide-react";
+import { Card, CardContent, CardTitle } from "@/components/ui/card";
+import getTauriCommands from "@/lib/commands/getTauriCommands";
+import {
+  OperationOutcome,
+  UpdateCycleReportModel,
+} from "@/lib/models/update-cycle-report.model";
+
+const countByStatus = (
+  report: UpdateCycleReportModel,
+  status: OperationOutcome["status"]
+) => report.operations.filter((op) => op.outcome.status === status).length;
+
+// Summary of what the last update cycle applied, deferred or failed
+export function LastUpdateCycleReport() {
+  const [report, setReport] = useState<UpdateCycleReportModel | undefined>();
+
+  useEffect(() => {
+    getTauriCommands()
+      .getLastUpdateCycleReport()
+      .then(setReport)
+      .catch((error) =>
+        console.error("Failed to get the last update cycle report:", error)
+      );
+
+    const unlisten = listen<UpdateCycleReportModel>(
+      "update_cycle_report",
+      (event) => setReport(event.payload)
+    );
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  if (!report) {
+    return null;
+  }
+
+  const failures = report.operations.filter(
+    (op) => op.outcome.status === "failed"
+  );
+
+  return (
+    <Card>
+      <CardContent className="p-4 flex flex-col gap-2">
+        <CardTitle className="text-sm font-semibold">
+          Last update cycle,{" "}
+          {new Date(report.finishedAt ?? report.startedAt).toLocaleTimeString(
+            [],
+            { hour: "numeric", minute: "2-digit", hour12: true }
+          )}
+        </CardTitle>
+        <p className="text-sm text-muted-foreground">
+          {countByStatus(report, "succeeded")} applied,{" "}
+          {countByStatus(report, "deferred")} waiting for Revit,{" "}
+          {countByStatus(report, "skipped")} skipped, {failures.length} failed
+        </p>
+        {failures.map((failure, index) => (
+          <div key={index} className="flex items-start gap-2 text-sm">
+            <AlertCircle className="w-4 h-4 mt-0.5 text-destructive flex-shrink-0" />
+            <span>
+              {failure.addinName} ({failure.operationType}):{" "}
+              {failure.outcome.status === "failed" && failure.outcome.cause}
+            </span>
+          </div>
+        ))}
+      </CardContent>
+    </Card>
+  );
+}
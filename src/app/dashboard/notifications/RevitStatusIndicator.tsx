@@ -1,7 +1,9 @@
 import { useEffect, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
 import { Monitor, MonitorOff } from "lucide-react";
 import { Badge } from "@/components/ui/badge";
 import getTauriCommands from "@/lib/commands/getTauriCommands";
+import { RevitProcessChangeModel } from "@/lib/models/revit-process-change.model";
 
 export function RevitStatusIndicator() {
   const [isRevitRunning, setIsRevitRunning] = useState<boolean | null>(null);
@@ -23,10 +25,18 @@ export function RevitStatusIndicator() {
   useEffect(() => {
     checkRevitStatus();
 
-    // Check status every 30 seconds
-    const interval = setInterval(checkRevitStatus, 30000);
+    // The backend reports whenever Revit starts or exits
+    const unlisten = listen<RevitProcessChangeModel>(
+      "revit_processes_changed",
+      (event) => {
+        const { versions, hasUnknownVersion } = event.payload.running;
+        setIsRevitRunning(versions.length > 0 || hasUnknownVersion);
+      }
+    );
 
-    return () => clearInterval(interval);
+    return () => {
+      unlisten.then((fn) => fn());
+    };
   }, []);
 
   if (isLoading) {
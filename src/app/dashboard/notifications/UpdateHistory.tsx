@@ -0,0 +1,131 @@
+import { useEffect, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
+import { save } from "@tauri-apps/plugin-dialog";
+import { Download } from "lucide-react";
+import { toast } from "sonner";
+import { Button } from "@/components/ui/button";
+import { Card, CardContent, CardTitle } from "@/components/ui/card";
+import getTauriCommands from "@/lib/commands/getTauriCommands";
+import {
+  AddinVersionModel,
+  OperationReportModel,
+  UpdateCycleReportModel,
+} from "@/lib/models/update-cycle-report.model";
+
+const PAGE_SIZE = 20;
+
+const formatVersion = (version?: AddinVersionModel | null) =>
+  version
+    ? `${version.version} (${new Date(version.modifiedAt).toLocaleString()})`
+    : "none";
+
+const describeOutcome = (operation: OperationReportModel) => {
+  switch (operation.outcome.status) {
+    case "succeeded":
+      return "succeeded";
+    case "deferred":
+      return "waiting for Revit to close";
+    case "skipped":
+      return `skipped, ${operation.outcome.reason}`;
+    case "failed":
+      return `failed, ${operation.outcome.cause}`;
+  }
+};
+
+// What the background updater did, newest cycle first
+export function UpdateHistory() {
+  const [cycles, setCycles] = useState<UpdateCycleReportModel[]>([]);
+  const [total, setTotal] = useState(0);
+
+  const loadPage = async (offset: number) => {
+    try {
+      const page = await getTauriCommands().getUpdateHistory(
+        offset,
+        PAGE_SIZE
+      );
+      setCycles((previous) =>
+        offset === 0 ? page.cycles : [...previous, ...page.cycles]
+      );
+      setTotal(page.total);
+    } catch (error) {
+      console.error("Failed to get the update history:", error);
+    }
+  };
+
+  useEffect(() => {
+    loadPage(0);
+
+    const unlisten = listen<UpdateCycleReportModel>(
+      "update_cycle_report",
+      () => loadPage(0)
+    );
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  const exportHistory = async () => {
+    const path = await save({
+      defaultPath: "update-history.json",
+      filters: [{ name: "JSON", extensions: ["json"] }],
+    });
+    if (!path) {
+      return;
+    }
+    try {
+      await getTauriCommands().exportUpdateHistory(path);
+      toast.success("Update history exported");
+    } catch (error) {
+      toast.error(`Failed to export the update history: ${error}`);
+    }
+  };
+
+  return (
+    <div className="flex flex-col gap-3">
+      <div className="flex items-center justify-between">
+        <h2 className="text-lg font-semibold">Update history</h2>
+        <Button variant="outline" size="sm" onClick={exportHistory}>
+          <Download className="w-4 h-4 mr-2" />
+          Export
+        </Button>
+      </div>
+      {cycles.length === 0 ? (
+        <p className="text-sm text-muted-foreground">
+          The updater hasn't run yet
+        </p>
+      ) : (
+        cycles.map((cycle) => (
+          <Card key={cycle.id}>
+            <CardContent className="p-4 flex flex-col gap-1">
+              <CardTitle className="text-sm font-semibold">
+                {new Date(cycle.startedAt).toLocaleString()}
+                {cycle.registryReachable === false &&
+                  ", registry unreachable"}
+              </CardTitle>
+              {cycle.operations.length === 0 ? (
+                <p className="text-sm text-muted-foreground">
+                  Nothing to update
+                </p>
+              ) : (
+                cycle.operations.map((operation, index) => (
+                  <p key={index} className="text-sm text-muted-foreground">
+                    {operation.operationType} {operation.addinName}:{" "}
+                    {describeOutcome(operation)}.{" "}
+                    {formatVersion(operation.versionBefore)} →{" "}
+                    {formatVersion(operation.versionAfter)}
+                  </p>
+                ))
+              )}
+            </CardContent>
+          </Card>
+        ))
+      )}
+      {cycles.length < total && (
+        <Button variant="ghost" onClick={() => loadPage(cycles.length)}>
+          Load more
+        </Button>
+      )}
+    </div>
+  );
+}
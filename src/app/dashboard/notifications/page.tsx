@@ -5,6 +5,9 @@ import AddinUpdateNotificationCard from "./AddinUpdateNotificationCard";
 import PageWrapper from "@/components/PageWrapper";
 import { CheckForUpdatesButton } from "./CheckForUpdatesButton";
 import { RevitStatusIndicator } from "./RevitStatusIndicator";
+import { CloseRevitAndUpdateButton } from "./CloseRevitAndUpdateButton";
+import { LastUpdateCycleReport } from "./LastUpdateCycleReport";
+import { UpdateHistory } from "./UpdateHistory";
 import { UpdateNotificationModel } from "@/lib/models/update-notification.model";
 import { Info } from "lucide-react";
 import { usePathname } from "next/navigation";
@@ -20,6 +23,7 @@ export default function NotificationsPage() {
             <h1 className="text-2xl font-bold">Notifications</h1>
             <div className="flex items-center gap-3">
               <RevitStatusIndicator />
+              <CloseRevitAndUpdateButton />
               <CheckForUpdatesButton />
               {updateNotifications.length > 0 && (
                 <button
@@ -32,6 +36,8 @@ export default function NotificationsPage() {
             </div>
           </div>
 
+          <LastUpdateCycleReport />
+
           <div className="overflow-y-auto thin-scrollbar">
             {updateNotifications.length === 0 ? (
               <div className="flex flex-col items-center justify-center py-16 text-center">
@@ -64,6 +70,8 @@ export default function NotificationsPage() {
               </div>
             )}
           </div>
+
+          <UpdateHistory />
         </div>
       </div>
     </PageWrapper>
@@ -0,0 +1,91 @@
+"use client";
+
+import { useEffect, useState } from "react";
+import { toast } from "sonner";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { Label } from "@/components/ui/label";
+import { Switch } from "@/components/ui/switch";
+import getTauriCommands from "@/lib/commands/getTauriCommands";
+import useConfig from "@/lib/persistence/config/useConfig";
+import { useConfigValueWithFallback } from "@/lib/persistence/config/useConfigValue";
+import { Loader2, Rocket } from "lucide-react";
+
+export default function ChangeAppUpdateChannel() {
+  const { update } = useConfig();
+  const { data: channel } = useConfigValueWithFallback(
+    "appUpdateChannel",
+    "stable"
+  );
+  const { data: endpoint } = useConfigValueWithFallback(
+    "appUpdateEndpoint",
+    ""
+  );
+  const [endpointValue, setEndpointValue] = useState(endpoint);
+  const [isChecking, setIsChecking] = useState(false);
+
+  useEffect(() => {
+    setEndpointValue(endpoint);
+  }, [endpoint]);
+
+  const checkForUpdates = async () => {
+    setIsChecking(true);
+    try {
+      // An update shows up in the update popup
+      const appUpdate = await getTauriCommands().checkForAppUpdate();
+      if (!appUpdate) {
+        toast.success("The app is up to date");
+      }
+    } catch (error) {
+      toast.error(`${error}`);
+    } finally {
+      setIsChecking(false);
+    }
+  };
+
+  return (
+    <div className="space-y-4">
+      <div className="flex items-center space-x-2">
+        <Rocket className="h-4 w-4 text-muted-foreground" />
+        <Label className="text-base font-medium">App updates</Label>
+      </div>
+
+      <div className="pl-6 space-y-4">
+        <div className="flex items-center justify-between">
+          <p className="text-xs text-muted-foreground">
+            Get beta versions of the app before everyone else. Beta versions
+            may have more bugs.
+          </p>
+          <Switch
+            checked={channel === "beta"}
+            onCheckedChange={(beta) =>
+              update("appUpdateChannel", beta ? "beta" : "stable")
+            }
+          />
+        </div>
+        <div className="space-y-2">
+          <p className="text-xs text-muted-foreground">
+            Where updates are downloaded from, e.g. a manifest on a local
+            server. <code>{"{{channel}}"}</code> is replaced with the channel.
+            Leave empty to use the default.
+          </p>
+          <Input
+            placeholder="https://updates.example.com/{{channel}}/latest.json"
+            value={endpointValue}
+            onChange={(e) => setEndpointValue(e.target.value)}
+            onBlur={() => update("appUpdateEndpoint", endpointValue.trim())}
+          />
+        </div>
+        <Button
+          variant="outline"
+          size="sm"
+          onClick={checkForUpdates}
+          disabled={isChecking}
+        >
+          {isChecking && <Loader2 className="w-4 h-4 mr-2 animate-spin" />}
+          Check for app updates
+        </Button>
+      </div>
+    </div>
+  );
+}
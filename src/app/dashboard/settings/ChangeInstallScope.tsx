@@ -0,0 +1,44 @@
+"use client";
+
+import { Label } from "@/components/ui/label";
+import { Switch } from "@/components/ui/switch";
+import useConfig from "@/lib/persistence/config/useConfig";
+import { useConfigValueWithFallback } from "@/lib/persistence/config/useConfigValue";
+import { Monitor } from "lucide-react";
+
+export default function ChangeInstallScope() {
+  const { update } = useConfig();
+  const { data: installScope } = useConfigValueWithFallback(
+    "defaultInstallScope",
+    "user"
+  );
+
+  const handleToggle = async (installForAllUsers: boolean) => {
+    await update("defaultInstallScope", installForAllUsers ? "machine" : "user");
+  };
+
+  return (
+    <div className="space-y-4">
+      <div className="flex items-center space-x-2">
+        <Monitor className="h-4 w-4 text-muted-foreground" />
+        <Label className="text-base font-medium">Install for all users</Label>
+      </div>
+
+      <div className="pl-6">
+        <div className="flex items-center justify-between">
+          <div className="space-y-1">
+            <p className="text-xs text-muted-foreground">
+              Installs new addins to the machine-wide Revit Addins folder
+              (ProgramData) instead of your own. Requires write access to that
+              folder.
+            </p>
+          </div>
+          <Switch
+            checked={installScope === "machine"}
+            onCheckedChange={handleToggle}
+          />
+        </div>
+      </div>
+    </div>
+  );
+}
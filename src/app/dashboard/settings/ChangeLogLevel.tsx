@@ -0,0 +1,80 @@
+"use client";
+
+import { useEffect, useState } from "react";
+import { save } from "@tauri-apps/plugin-dialog";
+import { Download, ScrollText } from "lucide-react";
+import { toast } from "sonner";
+import { Button } from "@/components/ui/button";
+import { Label } from "@/components/ui/label";
+import getTauriCommands from "@/lib/commands/getTauriCommands";
+import { LOG_LEVELS, LogLevel } from "@/lib/models/log-level.model";
+
+export default function ChangeLogLevel() {
+  const [level, setLevel] = useState<LogLevel>("info");
+
+  useEffect(() => {
+    getTauriCommands()
+      .getLogLevel()
+      .then(setLevel)
+      .catch((error) => console.error("Failed to get the log level:", error));
+  }, []);
+
+  const handleChange = async (newLevel: LogLevel) => {
+    try {
+      await getTauriCommands().setLogLevel(newLevel);
+      setLevel(newLevel);
+    } catch (error) {
+      toast.error(`Failed to change the log level: ${error}`);
+    }
+  };
+
+  const exportLogs = async () => {
+    const path = await save({
+      defaultPath: "addin-manager-logs.txt",
+      filters: [{ name: "Log", extensions: ["txt", "log"] }],
+    });
+    if (!path) {
+      return;
+    }
+    try {
+      await getTauriCommands().exportRecentLogs(path);
+      toast.success("Logs exported");
+    } catch (error) {
+      toast.error(`Failed to export the logs: ${error}`);
+    }
+  };
+
+  return (
+    <div className="space-y-4">
+      <div className="flex items-center space-x-2">
+        <ScrollText className="h-4 w-4 text-muted-foreground" />
+        <Label className="text-base font-medium">Logging</Label>
+      </div>
+
+      <div className="pl-6 space-y-2">
+        <p className="text-xs text-muted-foreground">
+          How much is written to the log files. Use &quot;debug&quot; or
+          &quot;trace&quot; while tracking down a problem, it applies right
+          away.
+        </p>
+        <div className="flex items-center gap-2">
+          <select
+            className="h-9 w-32 rounded-md border border-input bg-transparent px-3 text-sm"
+            value={level}
+            onChange={(e) => handleChange(e.target.value as LogLevel)}
+          >
+            {LOG_LEVELS.map((logLevel) => (
+              <option key={logLevel} value={logLevel}>
+                {logLevel}
+              </option>
+            ))}
+          </select>
+          <Button variant="outline" size="sm" onClick={exportLogs}>
+            <Download className="w-4 h-4 mr-2" />
+            Export last week&apos;s logs
+          </Button>
+        </div>
+      </div>
+    </div>
+  );
+}
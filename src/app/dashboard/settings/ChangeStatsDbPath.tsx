@@ -0,0 +1,83 @@
+"use client";
+
+import { open } from "@tauri-apps/plugin-dialog";
+import { useCallback, useEffect, useState } from "react";
+import { Badge } from "@/components/ui/badge";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { Label } from "@/components/ui/label";
+import getTauriCommands from "@/lib/commands/getTauriCommands";
+import { StatsDbStatusModel } from "@/lib/models/stats-db-status.model";
+import useConfig from "@/lib/persistence/config/useConfig";
+import { Database } from "lucide-react";
+
+export default function ChangeStatsDbPath() {
+  const { update } = useConfig();
+  const { getStatsDbStatus, reconnectStatsDb } = getTauriCommands();
+  const [status, setStatus] = useState<StatsDbStatusModel>();
+  const [isReconnecting, setIsReconnecting] = useState(false);
+
+  useEffect(() => {
+    getStatsDbStatus().then(setStatus).catch(console.error);
+  }, []);
+
+  const reconnect = useCallback(async () => {
+    setIsReconnecting(true);
+    try {
+      setStatus(await reconnectStatsDb());
+    } finally {
+      setIsReconnecting(false);
+    }
+  }, [reconnectStatsDb]);
+
+  const handlePathClicked = async () => {
+    const selected = await open({
+      directory: true,
+      multiple: false,
+      title: "Select the folder that contains UserStats.db",
+    });
+    if (selected) {
+      await update("statsDbPath", selected as string);
+      await reconnect();
+    }
+  };
+
+  return (
+    <div className="space-y-4">
+      <div className="flex items-center space-x-2">
+        <Database className="h-4 w-4 text-muted-foreground" />
+        <Label className="text-base font-medium">Stats database</Label>
+        {status && (
+          <Badge variant={status.isOnline ? "secondary" : "destructive"}>
+            {status.isOnline ? "Online" : "Offline"}
+          </Badge>
+        )}
+      </div>
+
+      <div className="pl-6 space-y-2">
+        <p className="text-xs text-muted-foreground">
+          Stats, permissions and automatic installs need this shared folder.
+          While it can&apos;t be reached, the app keeps working offline and
+          retries in the background.
+        </p>
+        <div className="flex items-center space-x-2">
+          <Input
+            value={status?.path ?? ""}
+            readOnly={true}
+            onClick={handlePathClicked}
+          />
+          <Button
+            variant="outline"
+            disabled={isReconnecting}
+            onClick={reconnect}
+          >
+            {isReconnecting ? "Connecting..." : "Retry"}
+          </Button>
+        </div>
+        {status && !status.isOnline && status.lastError && (
+          <p className="text-xs text-destructive">{status.lastError}</p>
+        )}
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,63 @@
+"use client";
+
+import { useEffect, useState } from "react";
+import { Input } from "@/components/ui/input";
+import { Label } from "@/components/ui/label";
+import useConfig from "@/lib/persistence/config/useConfig";
+import { useConfigValueWithFallback } from "@/lib/persistence/config/useConfigValue";
+import { RefreshCw } from "lucide-react";
+
+const DEFAULT_INTERVAL_MINUTES = 30;
+const MIN_INTERVAL_MINUTES = 1;
+const MAX_INTERVAL_MINUTES = 1440;
+
+export default function ChangeUpdateCheckInterval() {
+  const { update } = useConfig();
+  const { data: intervalMinutes } = useConfigValueWithFallback(
+    "updateCheckIntervalMinutes",
+    DEFAULT_INTERVAL_MINUTES
+  );
+  const [value, setValue] = useState(String(intervalMinutes));
+
+  useEffect(() => {
+    setValue(String(intervalMinutes));
+  }, [intervalMinutes]);
+
+  const handleBlur = async () => {
+    const minutes = Number.parseInt(value, 10);
+    if (
+      Number.isNaN(minutes) ||
+      minutes < MIN_INTERVAL_MINUTES ||
+      minutes > MAX_INTERVAL_MINUTES
+    ) {
+      setValue(String(intervalMinutes));
+      return;
+    }
+    await update("updateCheckIntervalMinutes", minutes);
+  };
+
+  return (
+    <div className="space-y-4">
+      <div className="flex items-center space-x-2">
+        <RefreshCw className="h-4 w-4 text-muted-foreground" />
+        <Label className="text-base font-medium">Full update check</Label>
+      </div>
+
+      <div className="pl-6 space-y-2">
+        <p className="text-xs text-muted-foreground">
+          Updates are picked up as soon as the registry changes. The whole
+          registry is also checked at least this often, in minutes.
+        </p>
+        <Input
+          type="number"
+          min={MIN_INTERVAL_MINUTES}
+          max={MAX_INTERVAL_MINUTES}
+          className="w-32"
+          value={value}
+          onChange={(e) => setValue(e.target.value)}
+          onBlur={handleBlur}
+        />
+      </div>
+    </div>
+  );
+}
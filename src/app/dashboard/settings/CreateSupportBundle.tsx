@@ -0,0 +1,71 @@
+"use client";
+
+import { useState } from "react";
+import { save } from "@tauri-apps/plugin-dialog";
+import { LifeBuoy, Loader2, PackageOpen } from "lucide-react";
+import { toast } from "sonner";
+import { Button } from "@/components/ui/button";
+import { Label } from "@/components/ui/label";
+import getTauriCommands from "@/lib/commands/getTauriCommands";
+
+export default function CreateSupportBundle() {
+  const [isCreating, setIsCreating] = useState(false);
+
+  const createBundle = async () => {
+    const date = new Date().toISOString().slice(0, 10);
+    const path = await save({
+      defaultPath: `support-bundle-${date}.zip`,
+      filters: [{ name: "Zip", extensions: ["zip"] }],
+    });
+    if (!path) {
+      return;
+    }
+    setIsCreating(true);
+    try {
+      const bundle = await getTauriCommands().createSupportBundle(path);
+      if (bundle.problems.length > 0) {
+        toast.warning(
+          `Support bundle created, but some parts are missing: ${bundle.problems.join(
+            "; "
+          )}`
+        );
+      } else {
+        toast.success("Support bundle created, attach it to your ticket");
+      }
+    } catch (error) {
+      toast.error(`Failed to create the support bundle: ${error}`);
+    } finally {
+      setIsCreating(false);
+    }
+  };
+
+  return (
+    <div className="space-y-4">
+      <div className="flex items-center space-x-2">
+        <LifeBuoy className="h-4 w-4 text-muted-foreground" />
+        <Label className="text-base font-medium">Support bundle</Label>
+      </div>
+
+      <div className="pl-6 space-y-2">
+        <p className="text-xs text-muted-foreground">
+          A zip with the recent logs, your installed addins, the app settings
+          without secrets, the registry status, the pending and past updates
+          and the running Revit windows. Attach it when reporting a problem.
+        </p>
+        <Button
+          variant="outline"
+          size="sm"
+          onClick={createBundle}
+          disabled={isCreating}
+        >
+          {isCreating ? (
+            <Loader2 className="w-4 h-4 mr-2 animate-spin" />
+          ) : (
+            <PackageOpen className="w-4 h-4 mr-2" />
+          )}
+          Create support bundle
+        </Button>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,127 @@
+"use client";
+
+import { useCallback, useEffect, useState } from "react";
+import { History, Loader2, Undo2 } from "lucide-react";
+import { toast } from "sonner";
+import { Button } from "@/components/ui/button";
+import { Label } from "@/components/ui/label";
+import getTauriCommands from "@/lib/commands/getTauriCommands";
+import { InstallReceiptModel } from "@/lib/models/install-receipt.model";
+
+export default function InstalledByApp() {
+  const [receipts, setReceipts] = useState<InstallReceiptModel[] | undefined>(
+    undefined
+  );
+  // The earlier installs, by the .addin file they were installed to
+  const [rollbacks, setRollbacks] = useState<Map<string, InstallReceiptModel>>(
+    new Map()
+  );
+  const [rollingBack, setRollingBack] = useState<string | undefined>(undefined);
+
+  const load = useCallback(async () => {
+    const commands = getTauriCommands();
+    try {
+      const [receipts, rollbacks] = await Promise.all([
+        commands.getInstallReceipts(),
+        commands.getRollbackReceipts(),
+      ]);
+      setReceipts(
+        receipts.sort(
+          (a, b) =>
+            a.name.localeCompare(b.name) ||
+            a.revitVersion.localeCompare(b.revitVersion)
+        )
+      );
+      setRollbacks(
+        new Map(rollbacks.map((receipt) => [receipt.installedXmlFile, receipt]))
+      );
+    } catch {
+      setReceipts([]);
+    }
+  }, []);
+
+  useEffect(() => {
+    load();
+  }, [load]);
+
+  const rollBack = async (receipt: InstallReceiptModel) => {
+    setRollingBack(receipt.installedXmlFile);
+    try {
+      const restored = await getTauriCommands().rollBackAddin(
+        receipt.installedXmlFile
+      );
+      toast.success(
+        `Rolled ${receipt.name} for Revit ${receipt.revitVersion} back to version ${restored.version}`
+      );
+    } catch (error) {
+      toast.error(`${error}`);
+    } finally {
+      setRollingBack(undefined);
+      load();
+    }
+  };
+
+  return (
+    <div className="space-y-4">
+      <div className="flex items-center space-x-2">
+        <History className="h-4 w-4 text-muted-foreground" />
+        <Label className="text-base font-medium">Installed by the app</Label>
+      </div>
+
+      <div className="pl-6 space-y-3">
+        <p className="text-xs text-muted-foreground">
+          The addins the app installed, when and by whom. An update can be
+          rolled back to the version it replaced, which is then kept until
+          the registry has another version.
+        </p>
+        {receipts?.length === 0 && (
+          <p className="text-sm">The app hasn&apos;t installed any addins.</p>
+        )}
+        {receipts?.map((receipt) => {
+          const rollback = rollbacks.get(receipt.installedXmlFile);
+          return (
+            <div
+              key={receipt.installedXmlFile}
+              className="flex items-center justify-between gap-4"
+            >
+              <div className="space-y-1">
+                <p className="text-sm font-medium">
+                  {receipt.name} {receipt.version} for Revit{" "}
+                  {receipt.revitVersion}
+                </p>
+                <p className="text-xs text-muted-foreground">
+                  Installed{" "}
+                  {receipt.installedBy === "user"
+                    ? "from the library"
+                    : "automatically"}{" "}
+                  on {new Date(receipt.installedAt).toLocaleDateString()}
+                  {receipt.updatedAt !== receipt.installedAt &&
+                    `, updated on ${new Date(
+                      receipt.updatedAt
+                    ).toLocaleDateString()}`}
+                  {receipt.rolledBackFrom &&
+                    `, rolled back from version ${receipt.rolledBackFrom}`}
+                </p>
+              </div>
+              {rollback && (
+                <Button
+                  variant="outline"
+                  size="sm"
+                  onClick={() => rollBack(receipt)}
+                  disabled={rollingBack !== undefined}
+                >
+                  {rollingBack === receipt.installedXmlFile ? (
+                    <Loader2 className="w-4 h-4 mr-2 animate-spin" />
+                  ) : (
+                    <Undo2 className="w-4 h-4 mr-2" />
+                  )}
+                  Roll back to {rollback.version}
+                </Button>
+              )}
+            </div>
+          );
+        })}
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,48 @@
+"use client";
+
+import { useEffect, useState } from "react";
+import { Building2 } from "lucide-react";
+import { Label } from "@/components/ui/label";
+import getTauriCommands from "@/lib/commands/getTauriCommands";
+import { RevitInstallationModel } from "@/lib/models/revit-installation.model";
+
+export default function RevitInstallations() {
+  const [installations, setInstallations] = useState<
+    RevitInstallationModel[] | undefined
+  >(undefined);
+
+  useEffect(() => {
+    getTauriCommands()
+      .getRevitInstallations()
+      .then(setInstallations)
+      .catch(() => setInstallations([]));
+  }, []);
+
+  return (
+    <div className="space-y-4">
+      <div className="flex items-center space-x-2">
+        <Building2 className="h-4 w-4 text-muted-foreground" />
+        <Label className="text-base font-medium">Installed Revit versions</Label>
+      </div>
+
+      <div className="pl-6 space-y-3">
+        <p className="text-xs text-muted-foreground">
+          New addins are installed for these versions, as far as the addin
+          supports them.
+        </p>
+        {installations?.length === 0 && (
+          <p className="text-sm">No Revit installation was found.</p>
+        )}
+        {installations?.map((installation) => (
+          <div key={installation.version} className="space-y-1">
+            <p className="text-sm font-medium">{installation.product}</p>
+            <p className="text-xs text-muted-foreground break-all">
+              {installation.installDir ??
+                "Found from its Addins folder, the install folder is unknown"}
+            </p>
+          </div>
+        ))}
+      </div>
+    </div>
+  );
+}
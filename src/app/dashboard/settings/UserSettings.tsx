@@ -24,6 +24,14 @@ import getTauriCommands from "@/lib/commands/getTauriCommands";
 import ChangeEmail from "./ChangeEmail";
 import ChangeName from "./ChangeName";
 import ChangeDisciplines from "./ChangeDisciplines";
+import ChangeInstallScope from "./ChangeInstallScope";
+import RevitInstallations from "./RevitInstallations";
+import InstalledByApp from "./InstalledByApp";
+import ChangeStatsDbPath from "./ChangeStatsDbPath";
+import ChangeUpdateCheckInterval from "./ChangeUpdateCheckInterval";
+import ChangeAppUpdateChannel from "./ChangeAppUpdateChannel";
+import ChangeLogLevel from "./ChangeLogLevel";
+import CreateSupportBundle from "./CreateSupportBundle";
 
 export function UserSettings() {
   return (
@@ -57,6 +65,38 @@ export function UserSettings() {
 
         </div>
 
+        {/* Installation Section */}
+        <div className="space-y-6">
+          <div className="flex items-center space-x-3">
+            <Settings className="h-5 w-5 text-primary" />
+            <h3 className="text-xl font-semibold">Installation</h3>
+          </div>
+
+          <div className="bg-card border rounded-lg p-6 space-y-6">
+            <RevitInstallations />
+          </div>
+
+          <div className="bg-card border rounded-lg p-6 space-y-6">
+            <InstalledByApp />
+          </div>
+
+          <div className="bg-card border rounded-lg p-6 space-y-6">
+            <ChangeInstallScope />
+          </div>
+
+          <div className="bg-card border rounded-lg p-6 space-y-6">
+            <ChangeStatsDbPath />
+          </div>
+
+          <div className="bg-card border rounded-lg p-6 space-y-6">
+            <ChangeUpdateCheckInterval />
+          </div>
+
+          <div className="bg-card border rounded-lg p-6 space-y-6">
+            <ChangeAppUpdateChannel />
+          </div>
+        </div>
+
         {/* Help Section */}
         <div className="space-y-6">
           <div className="flex items-center space-x-3">
@@ -64,6 +104,14 @@ export function UserSettings() {
             <h3 className="text-xl font-semibold">Help</h3>
           </div>
 
+          <div className="bg-card border rounded-lg p-6 space-y-6">
+            <ChangeLogLevel />
+          </div>
+
+          <div className="bg-card border rounded-lg p-6 space-y-6">
+            <CreateSupportBundle />
+          </div>
+
           <div className="bg-card border rounded-lg p-6 space-y-6">
             <p className="text-sm font-sans">
               Encountered a bug? Please contact the maintainer at{" "}
@@ -3,8 +3,17 @@ import useTauriCommands from "../../commands/getTauriCommands";
 import { AddinModel } from "../../models/addin.model";
 
 export default function useLocalAddins() {
-  const { getLocalAddins, getRevitVersions, uninstallAddins, getCategories } =
-    useTauriCommands();
+  const {
+    getLocalAddins,
+    getRevitVersions,
+    uninstallAddins,
+    adoptLocalAddins,
+    disableLocalAddins,
+    enableLocalAddins,
+    verifyLocalAddins,
+    repairLocalAddins,
+    getCategories,
+  } = useTauriCommands();
   const [addins, setAddins] = useState<AddinModel[]>([]);
   const [revitVersions, setRevitVersions] = useState<string[]>([]);
   const [loading, setLoading] = useState(true);
@@ -51,5 +60,10 @@ export default function useLocalAddins() {
     refreshAddins,
     refreshRevitVersions,
     uninstallAddins,
+    adoptLocalAddins,
+    disableLocalAddins,
+    enableLocalAddins,
+    verifyLocalAddins,
+    repairLocalAddins,
   };
 }
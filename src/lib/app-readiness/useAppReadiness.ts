@@ -0,0 +1,31 @@
+import { useEffect, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
+import getTauriCommands from "@/lib/commands/getTauriCommands";
+import { AppReadinessModel } from "@/lib/models/app-readiness.model";
+
+/**
+ * Observes where the backend service container is in its startup
+ */
+export function useAppReadiness() {
+  const [readiness, setReadiness] = useState<AppReadinessModel>({
+    state: "initializing",
+  });
+
+  useEffect(() => {
+    const unlisten = listen<AppReadinessModel>(
+      "app_readiness_changed",
+      (event) => setReadiness(event.payload)
+    );
+    // The services may have finished before the listener was set up
+    getTauriCommands()
+      .getAppReadiness()
+      .then(setReadiness)
+      .catch(console.error);
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  return readiness;
+}
@@ -1,5 +1,5 @@
 import { useCallback } from "react";
-import { invoke } from "@tauri-apps/api/core";
+import { invokeWhenReady as invoke } from "./invokeWhenReady";
 import { KvSubscriptionModel } from "../models/kv-subscription-model";
 import { AddinModel } from "@/lib/models/addin.model";
 import { InstallAddinRequestModel } from "../models/install-addin-request.model";
@@ -8,26 +8,80 @@ import { DllModel } from "../models/dll.model";
 import { CategoryModel } from "../models/category.model";
 import { ErrorList } from "@/types/error-list";
 import { UninstallAddinRequestModel } from "../models/uninstall-addin-request.model";
+import { UninstallResultModel } from "../models/uninstall-result.model";
 import { UserStatsModel } from "../models/user-stats.model";
 import { UpdateNotificationModel } from "../models/update-notification.model";
 import { UserModel } from "../models/user.model";
 import { CreateAddinPackageRequestModel } from "../models/create-addin-package-request.model";
 import { AddinPackageInfoModel } from "../models/addin-package-info.model";
 import { VsTemplateModel } from "../models/vs-template.model";
+import { RevitInstallationModel } from "../models/revit-installation.model";
+import { DllConflictModel } from "../models/dll-conflict.model";
+import { StatsDbStatusModel } from "../models/stats-db-status.model";
+import { RegistryMirrorStatusModel } from "../models/registry-mirror-status.model";
+import { AppReadinessModel } from "../models/app-readiness.model";
+import {
+  AddinRepairModel,
+  AddinVerificationModel,
+} from "../models/addin-verification.model";
+import { InstallReceiptModel } from "../models/install-receipt.model";
+import { BisectSessionModel } from "../models/bisect-session.model";
+import { PendingOperationModel } from "../models/pending-operation.model";
+import { RevitRestartResultModel } from "../models/revit-restart.model";
+import {
+  UpdateCycleReportModel,
+  UpdateHistoryPageModel,
+} from "../models/update-cycle-report.model";
+import { LogLevel } from "../models/log-level.model";
+import { SupportBundleModel } from "../models/support-bundle.model";
+import {
+  AppUpdateInfoModel,
+  AppUpdateStateModel,
+} from "../models/app-update.model";
 
 interface TauriCommands {
+  getAppReadiness: () => Promise<AppReadinessModel>;
+  /** Rejects with a `NotReadyError` if the app failed to initialize */
+  waitForAppReady: () => Promise<AppReadinessModel>;
   kvStoreSet: (key: string, value: any) => Promise<void>;
   kvStoreGet: <T>(key: string) => Promise<T | undefined>;
   kvStoreSubscribeToKey<T>(key: string): Promise<KvSubscriptionModel<T>>;
   getAddins: (path: string) => Promise<AddinModel[]>;
   getLocalAddins: () => Promise<AddinModel[]>;
   getRevitVersions: () => Promise<string[]>;
+  getRevitInstallations: () => Promise<RevitInstallationModel[]>;
+  getAddinDllConflicts: (addin: AddinModel) => Promise<DllConflictModel[]>;
   installAddins: (installRequests: InstallAddinRequestModel[]) => Promise<void>;
   delistAddin: (addin: AddinModel, registryPath: string) => Promise<void>;
   getCategories: (path: string) => Promise<CategoryModel[]>;
+  /** One result per addin, Revit version and scope */
   uninstallAddins: (
     uninstallRequests: UninstallAddinRequestModel[]
-  ) => Promise<void>;
+  ) => Promise<UninstallResultModel[]>;
+  /** Brings unmanaged copies of registry addins under management */
+  adoptLocalAddins: (addins: AddinModel[]) => Promise<void>;
+  /** Makes Revit skip the addins, without removing their files */
+  disableLocalAddins: (addins: AddinModel[]) => Promise<void>;
+  /** Makes Revit load the addins again */
+  enableLocalAddins: (addins: AddinModel[]) => Promise<void>;
+  /** What the app has installed, one receipt per Revit version and scope */
+  getInstallReceipts: () => Promise<InstallReceiptModel[]>;
+  getRollbackReceipts: () => Promise<InstallReceiptModel[]>;
+  rollBackAddin: (installedXmlFile: string) => Promise<InstallReceiptModel>;
+  getBisectSessions: () => Promise<BisectSessionModel[]>;
+  /** Disables half of the managed addins of the Revit version to find the one causing a problem */
+  startBisectSession: (revitVersion: string) => Promise<BisectSessionModel>;
+  /** Narrows the suspects down, based on whether the problem showed up with the addins under test */
+  answerBisectStep: (
+    revitVersion: string,
+    problemReproduced: boolean
+  ) => Promise<BisectSessionModel>;
+  /** Forgets the session, and enables the addins again if it wasn't finished */
+  endBisectSession: (revitVersion: string) => Promise<void>;
+  /** Compares the local addins with their source in the registry, file by file */
+  verifyLocalAddins: (addins: AddinModel[]) => Promise<AddinVerificationModel[]>;
+  /** Restores the missing and modified files of the local addins from the registry */
+  repairLocalAddins: (addins: AddinModel[]) => Promise<AddinRepairModel[]>;
   exportAddin: (
     projectDir: string,
     addinFileInfo: SimplifiedAddinInfoModel,
@@ -43,10 +97,44 @@ interface TauriCommands {
   doesUserExist: (userEmail: string) => Promise<boolean>;
   changeUserStatsEmail: (newUserEmail: string) => Promise<void>;
   changeUserStatsName: (newUserName: string) => Promise<void>;
+  getStatsDbStatus: () => Promise<StatsDbStatusModel>;
+  /** Tries to reconnect to the stats database right away */
+  reconnectStatsDb: () => Promise<StatsDbStatusModel>;
+  /** Whether the addins listed are the offline snapshot of the registry, and how old it is */
+  getRegistryMirrorStatus: () => Promise<RegistryMirrorStatusModel>;
   /** Returns true if there are updates available */
   checkForUpdatesManual: () => Promise<boolean>;
   isRevitRunning: () => Promise<boolean>;
   getPendingUpdatesInfo: () => Promise<string | undefined>;
+  /** The updates, installs and uninstalls that wait for Revit to close, oldest first */
+  getPendingOperations: () => Promise<PendingOperationModel[]>;
+  /** Closes Revit, applies the pending operations and reopens Revit. Progress is sent with the "revit_restart_progress" event */
+  closeRevitAndUpdate: () => Promise<RevitRestartResultModel>;
+  /** What the last update cycle that did anything applied, deferred or failed. Also sent with the "update_cycle_report" event */
+  getLastUpdateCycleReport: () => Promise<UpdateCycleReportModel | undefined>;
+  /** A page of the update history, newest cycle first */
+  getUpdateHistory: (
+    offset: number,
+    limit: number
+  ) => Promise<UpdateHistoryPageModel>;
+  /** Writes the whole update history to a JSON file, to attach to support tickets */
+  exportUpdateHistory: (path: string) => Promise<void>;
+  /** The level of the messages that are logged, e.g. "info" */
+  getLogLevel: () => Promise<LogLevel>;
+  /** Changes the log level right away, without restarting the app */
+  setLogLevel: (level: LogLevel) => Promise<void>;
+  /** Writes the logs of the last week into a single file, to attach to support tickets */
+  exportRecentLogs: (path: string) => Promise<void>;
+  /** Writes a zip with the logs, local addins, config, registry diagnostics, pending operations, update history and Revit processes */
+  createSupportBundle: (path: string) => Promise<SupportBundleModel>;
+  /** Where the app is in updating itself. Changes are also sent with the "app_update_state_changed" event */
+  getAppUpdateState: () => Promise<AppUpdateStateModel>;
+  /** Looks for a newer version of the app on the configured channel. Undefined if the app is up to date */
+  checkForAppUpdate: () => Promise<AppUpdateInfoModel | undefined>;
+  /** Downloads the update found by the last check */
+  downloadAppUpdate: () => Promise<void>;
+  /** Installs the downloaded update and restarts the app, once no addin is being installed, updated or uninstalled */
+  applyAppUpdate: () => Promise<void>;
   registerUser: (
     userEmail: string,
     userName: string,
@@ -84,6 +172,14 @@ interface TauriCommands {
 }
 
 export default function getTauriCommands(): TauriCommands {
+  const getAppReadiness = async () => {
+    return await invoke<AppReadinessModel>("get_app_readiness");
+  };
+
+  const waitForAppReady = async () => {
+    return await invoke<AppReadinessModel>("wait_for_app_ready");
+  };
+
   const kvStoreSet = async (key: string, value: any) => {
     await invoke<void>("kv_store_set", {
       key,
@@ -137,6 +233,27 @@ export default function getTauriCommands(): TauriCommands {
     }
   };
 
+  const getRevitInstallations = async () => {
+    try {
+      return await invoke<RevitInstallationModel[]>("get_revit_installations");
+    } catch (err) {
+      console.error("Failed to get Revit installations:", err);
+      throw err;
+    }
+  };
+
+  // Returns the DLLs of the addin that Revit already ships itself
+  const getAddinDllConflicts = async (addin: AddinModel) => {
+    try {
+      return await invoke<DllConflictModel[]>("get_addin_dll_conflicts", {
+        addin,
+      });
+    } catch (err) {
+      console.error("Failed to get addin DLL conflicts:", err);
+      throw err;
+    }
+  };
+
   // Installs the addin for the given Revit versions locally
   const installAddins = async (installRequests: InstallAddinRequestModel[]) => {
     try {
@@ -170,13 +287,137 @@ export default function getTauriCommands(): TauriCommands {
     uninstallRequests: UninstallAddinRequestModel[]
   ) => {
     try {
-      return await invoke<void>("uninstall_addins", { uninstallRequests });
+      return await invoke<UninstallResultModel[]>("uninstall_addins", {
+        uninstallRequests,
+      });
     } catch (err) {
       console.warn("Failed to uninstall addin:", err);
       throw err;
     }
   };
 
+  const adoptLocalAddins = async (addins: AddinModel[]) => {
+    try {
+      return await invoke<void>("adopt_local_addins", { addins });
+    } catch (err) {
+      console.error("Failed to adopt addins:", err);
+      throw err;
+    }
+  };
+
+  const disableLocalAddins = async (addins: AddinModel[]) => {
+    try {
+      return await invoke<void>("disable_local_addins", { addins });
+    } catch (err) {
+      console.error("Failed to disable addins:", err);
+      throw err;
+    }
+  };
+
+  const enableLocalAddins = async (addins: AddinModel[]) => {
+    try {
+      return await invoke<void>("enable_local_addins", { addins });
+    } catch (err) {
+      console.error("Failed to enable addins:", err);
+      throw err;
+    }
+  };
+
+  const getInstallReceipts = async () => {
+    try {
+      return await invoke<InstallReceiptModel[]>("get_install_receipts");
+    } catch (err) {
+      console.error("Failed to get the install receipts:", err);
+      throw err;
+    }
+  };
+
+  const getRollbackReceipts = async () => {
+    try {
+      return await invoke<InstallReceiptModel[]>("get_rollback_receipts");
+    } catch (err) {
+      console.error("Failed to get the rollback receipts:", err);
+      throw err;
+    }
+  };
+
+  const rollBackAddin = async (installedXmlFile: string) => {
+    try {
+      return await invoke<InstallReceiptModel>("roll_back_addin", {
+        installedXmlFile,
+      });
+    } catch (err) {
+      console.error("Failed to roll back the addin:", err);
+      throw err;
+    }
+  };
+
+  const getBisectSessions = async () => {
+    try {
+      return await invoke<BisectSessionModel[]>("get_bisect_sessions");
+    } catch (err) {
+      console.error("Failed to get the bisect sessions:", err);
+      throw err;
+    }
+  };
+
+  const startBisectSession = async (revitVersion: string) => {
+    try {
+      return await invoke<BisectSessionModel>("start_bisect_session", {
+        revitVersion,
+      });
+    } catch (err) {
+      console.error("Failed to start the bisect session:", err);
+      throw err;
+    }
+  };
+
+  const answerBisectStep = async (
+    revitVersion: string,
+    problemReproduced: boolean
+  ) => {
+    try {
+      return await invoke<BisectSessionModel>("answer_bisect_step", {
+        revitVersion,
+        problemReproduced,
+      });
+    } catch (err) {
+      console.error("Failed to answer the bisect step:", err);
+      throw err;
+    }
+  };
+
+  const endBisectSession = async (revitVersion: string) => {
+    try {
+      return await invoke<void>("end_bisect_session", { revitVersion });
+    } catch (err) {
+      console.error("Failed to end the bisect session:", err);
+      throw err;
+    }
+  };
+
+  const verifyLocalAddins = async (addins: AddinModel[]) => {
+    try {
+      return await invoke<AddinVerificationModel[]>("verify_local_addins", {
+        addins,
+      });
+    } catch (err) {
+      console.error("Failed to verify addins:", err);
+      throw err;
+    }
+  };
+
+  const repairLocalAddins = async (addins: AddinModel[]) => {
+    try {
+      return await invoke<AddinRepairModel[]>("repair_local_addins", {
+        addins,
+      });
+    } catch (err) {
+      console.error("Failed to repair addins:", err);
+      throw err;
+    }
+  };
+
   const exportAddin = async (
     projectDir: string,
     addinFileInfo: SimplifiedAddinInfoModel,
@@ -229,6 +470,35 @@ export default function getTauriCommands(): TauriCommands {
     return await invoke<void>("change_user_stats_name", { newUserName });
   };
 
+  const getStatsDbStatus = async () => {
+    try {
+      return await invoke<StatsDbStatusModel>("get_stats_db_status");
+    } catch (err) {
+      console.error("Failed to get the stats database status:", err);
+      throw err;
+    }
+  };
+
+  const reconnectStatsDb = async () => {
+    try {
+      return await invoke<StatsDbStatusModel>("reconnect_stats_db");
+    } catch (err) {
+      console.error("Failed to reconnect to the stats database:", err);
+      throw err;
+    }
+  };
+
+  const getRegistryMirrorStatus = async () => {
+    try {
+      return await invoke<RegistryMirrorStatusModel>(
+        "get_registry_mirror_status"
+      );
+    } catch (err) {
+      console.error("Failed to get the registry mirror status:", err);
+      throw err;
+    }
+  };
+
   const checkForUpdatesManual = async () => {
     return await invoke<boolean>("check_for_updates_manual");
   };
@@ -241,6 +511,65 @@ export default function getTauriCommands(): TauriCommands {
     return await invoke<string | undefined>("get_pending_updates_info");
   };
 
+  const getPendingOperations = async () => {
+    return await invoke<PendingOperationModel[]>("get_pending_operations");
+  };
+
+  const closeRevitAndUpdate = async () => {
+    return await invoke<RevitRestartResultModel>("close_revit_and_update");
+  };
+
+  const getLastUpdateCycleReport = async () => {
+    return await invoke<UpdateCycleReportModel | undefined>(
+      "get_last_update_cycle_report"
+    );
+  };
+
+  const getUpdateHistory = async (offset: number, limit: number) => {
+    return await invoke<UpdateHistoryPageModel>("get_update_history", {
+      offset,
+      limit,
+    });
+  };
+
+  const exportUpdateHistory = async (path: string) => {
+    return await invoke<void>("export_update_history", { path });
+  };
+
+  const getLogLevel = async () => {
+    return await invoke<LogLevel>("get_log_level");
+  };
+
+  const setLogLevel = async (level: LogLevel) => {
+    return await invoke<void>("set_log_level", { level });
+  };
+
+  const exportRecentLogs = async (path: string) => {
+    return await invoke<void>("export_recent_logs", { path });
+  };
+
+  const createSupportBundle = async (path: string) => {
+    return await invoke<SupportBundleModel>("create_support_bundle", { path });
+  };
+
+  const getAppUpdateState = async () => {
+    return await invoke<AppUpdateStateModel>("get_app_update_state");
+  };
+
+  const checkForAppUpdate = async () => {
+    return await invoke<AppUpdateInfoModel | undefined>(
+      "check_for_app_update"
+    );
+  };
+
+  const downloadAppUpdate = async () => {
+    return await invoke<void>("download_app_update");
+  };
+
+  const applyAppUpdate = async () => {
+    return await invoke<void>("apply_app_update");
+  };
+
   const registerUser = async (
     userEmail: string,
     userName: string,
@@ -335,16 +664,32 @@ export default function getTauriCommands(): TauriCommands {
   };
 
   return {
+    getAppReadiness,
+    waitForAppReady,
     kvStoreSet,
     kvStoreGet,
     kvStoreSubscribeToKey,
     getAddins,
     getLocalAddins,
     getRevitVersions,
+    getRevitInstallations,
+    getAddinDllConflicts,
     installAddins,
     delistAddin,
     getCategories,
     uninstallAddins,
+    adoptLocalAddins,
+    disableLocalAddins,
+    enableLocalAddins,
+    getInstallReceipts,
+    getRollbackReceipts,
+    rollBackAddin,
+    getBisectSessions,
+    startBisectSession,
+    answerBisectStep,
+    endBisectSession,
+    verifyLocalAddins,
+    repairLocalAddins,
     exportAddin,
     getAddinFileInfo,
     getAllProjectDlls,
@@ -355,9 +700,25 @@ export default function getTauriCommands(): TauriCommands {
     getAllUserStats,
     changeUserStatsEmail,
     changeUserStatsName,
+    getStatsDbStatus,
+    reconnectStatsDb,
+    getRegistryMirrorStatus,
     checkForUpdatesManual,
     isRevitRunning,
     getPendingUpdatesInfo,
+    getPendingOperations,
+    closeRevitAndUpdate,
+    getLastUpdateCycleReport,
+    getUpdateHistory,
+    exportUpdateHistory,
+    getLogLevel,
+    setLogLevel,
+    exportRecentLogs,
+    createSupportBundle,
+    getAppUpdateState,
+    checkForAppUpdate,
+    downloadAppUpdate,
+    applyAppUpdate,
     registerUser,
     getUser,
     setAllowedAddinPathsForUser,
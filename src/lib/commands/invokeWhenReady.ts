@@ -0,0 +1,46 @@
+import { invoke, InvokeArgs } from "@tauri-apps/api/core";
+import {
+  AppReadinessModel,
+  NotReadyError,
+} from "../models/app-readiness.model";
+
+// These commands work before the backend services are ready
+const ALWAYS_AVAILABLE_COMMANDS = new Set([
+  "get_app_readiness",
+  "wait_for_app_ready",
+]);
+
+let appReady: Promise<AppReadinessModel> | undefined;
+
+/**
+ * Resolves once every backend service is available.
+ * Rejects with a `NotReadyError` if the app failed to initialize or is still initializing after the timeout
+ */
+export function waitForAppReady(): Promise<AppReadinessModel> {
+  if (!appReady) {
+    appReady = invoke<AppReadinessModel>("wait_for_app_ready").catch(
+      (err: NotReadyError) => {
+        // Wait again on the next call if this one only timed out
+        if (err.kind === "initializing") {
+          appReady = undefined;
+        }
+        throw err;
+      }
+    );
+  }
+  return appReady;
+}
+
+/**
+ * Same as `invoke`, but waits until the backend services are ready,
+ * since commands that are invoked earlier would fail with missing state
+ */
+export async function invokeWhenReady<T>(
+  cmd: string,
+  args?: InvokeArgs
+): Promise<T> {
+  if (!ALWAYS_AVAILABLE_COMMANDS.has(cmd)) {
+    await waitForAppReady();
+  }
+  return invoke<T>(cmd, args);
+}
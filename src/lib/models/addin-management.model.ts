@@ -0,0 +1,3 @@
+// "managed" was installed by the app, "unmanaged" is a copy of a registry addin that can be adopted,
+// "foreign" didn't come from the registry and is read-only
+export type AddinManagement = "managed" | "unmanaged" | "foreign";
@@ -9,4 +9,8 @@ export interface AddinPackageInfoModel {
   displayName: string;
   disciplinePackage: string | undefined;
   emoji: string | undefined;
+  // The oldest Revit version that the addin supports, if any
+  minRevitVersion: string | undefined;
+  // The newest Revit version that the addin supports, if any
+  maxRevitVersion: string | undefined;
 }
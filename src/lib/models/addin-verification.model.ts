@@ -0,0 +1,28 @@
+import { AddinModel } from "./addin.model";
+
+export type FileDiscrepancyKind = "missing" | "extra" | "modified";
+
+export interface FileDiscrepancyModel {
+  // Relative to the Revit version folder, e.g. "MyAddin/MyAddin.dll"
+  relativePath: string;
+  kind: FileDiscrepancyKind;
+}
+
+export interface AddinVerificationModel {
+  addin: AddinModel;
+  // The .addin file in the registry that the addin was installed from
+  registryXmlFile: string | undefined;
+  discrepancies: FileDiscrepancyModel[];
+  // The Assembly paths in the installed .addin file that don't point to a file
+  unresolvedAssemblies: string[];
+  error: string | undefined;
+  isHealthy: boolean;
+}
+
+export interface AddinRepairModel {
+  restoredFiles: string[];
+  // The state of the addin after the repair
+  verification: AddinVerificationModel;
+  // Set if the addin couldn't be repaired, e.g. its source isn't in the registry
+  error: string | undefined;
+}
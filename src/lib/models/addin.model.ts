@@ -1,4 +1,6 @@
 import { getFileNameFromPath, getParentDirectoryFromPath } from "../utils";
+import { InstallScope } from "./install-scope.model";
+import { AddinManagement } from "./addin-management.model";
 
 export interface AddinModel {
   // Full path to the .addin file
@@ -24,6 +26,12 @@ export interface AddinModel {
   revitVersion: string | null;
   // Whether the addin is installed locally
   isInstalledLocally: boolean;
+  // Whether the addin is installed for the current user or for the whole machine. Will only be present if the addin is installed locally
+  installScope?: InstallScope | null;
+  // Whether the app manages the addin. Will only be present if the addin is installed locally
+  management?: AddinManagement | null;
+  // Whether Revit skips the addin. Will only be true if the addin is installed locally
+  isDisabled?: boolean;
 }
 
 /**
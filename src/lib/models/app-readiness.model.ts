@@ -0,0 +1,12 @@
+// Where the backend service container is in its startup
+export type AppReadinessModel =
+  | { state: "initializing" }
+  | { state: "ready" }
+  // Everything works except the features that depend on the given reasons
+  | { state: "degraded"; reasons: string[] }
+  | { state: "failed"; error: string };
+
+// Returned by commands that can't run because the services are not ready
+export type NotReadyError =
+  | { kind: "initializing" }
+  | { kind: "failed"; error: string };
@@ -0,0 +1,32 @@
+// Which releases of the app are installed
+export type AppUpdateChannel = "stable" | "beta";
+
+// A newer version of the app
+export interface AppUpdateInfoModel {
+  version: string;
+  currentVersion: string;
+  // The release notes
+  notes?: string | null;
+  // When it was released, in RFC 3339 format
+  date?: string | null;
+  channel: AppUpdateChannel;
+}
+
+// Where the app is in updating itself, sent with the "app_update_state_changed" event
+export type AppUpdateStateModel =
+  | { stage: "idle" }
+  | { stage: "checking" }
+  | { stage: "upToDate" }
+  | { stage: "available"; update: AppUpdateInfoModel }
+  | {
+      stage: "downloading";
+      update: AppUpdateInfoModel;
+      downloaded: number;
+      // Missing if the server didn't say how big the update is
+      total?: number | null;
+    }
+  | { stage: "downloaded"; update: AppUpdateInfoModel }
+  // The app restarts once no addin is being installed, updated or uninstalled
+  | { stage: "waitingForInstalls"; update: AppUpdateInfoModel }
+  | { stage: "installing"; update: AppUpdateInfoModel }
+  | { stage: "failed"; error: string; update?: AppUpdateInfoModel | null };
@@ -0,0 +1,23 @@
+import { InstallScope } from "./install-scope.model";
+
+export interface BisectCandidateModel {
+  // Where the .addin file goes when the addin is enabled
+  xmlFile: string;
+  name: string;
+  installScope: InstallScope;
+}
+
+// A guided search for the addin that causes a problem in one Revit version
+export interface BisectSessionModel {
+  revitVersion: string;
+  // The managed addins that were enabled when the session started
+  candidates: BisectCandidateModel[];
+  // The .addin files of the candidates that may still cause the problem
+  suspects: string[];
+  // The .addin files of the suspects that are enabled for the current step
+  testing: string[];
+  stepsTaken: number;
+  // Set once a single suspect remains. The addins are enabled again by then
+  culprit?: BisectCandidateModel | null;
+  startedAt: string;
+}
@@ -5,4 +5,6 @@ export interface CreateAddinPackageRequestModel {
   displayName: string;
   disciplinePackage: string | null;
   emoji: string | null;
+  minRevitVersion: string | null;
+  maxRevitVersion: string | null;
 }
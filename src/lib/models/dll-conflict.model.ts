@@ -0,0 +1,6 @@
+// A DLL that an addin ships which Revit already ships itself
+export interface DllConflictModel {
+  dllName: string;
+  revitVersion: string;
+  revitInstallDir: string;
+}
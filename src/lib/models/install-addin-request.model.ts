@@ -1,6 +1,9 @@
 import { AddinModel } from "./addin.model";
+import { InstallScope } from "./install-scope.model";
 
 export interface InstallAddinRequestModel {
   addin: AddinModel;
   forRevitVersions: string[];
+  // Defaults to "user"
+  installScope?: InstallScope;
 }
\ No newline at end of file
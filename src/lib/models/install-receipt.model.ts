@@ -0,0 +1,30 @@
+import { InstallScope } from "./install-scope.model";
+
+// "user" installed it from the library, "autoUpdater" installed it because the user is allowed to have it
+export type InstallActor = "user" | "autoUpdater";
+
+export interface ReceiptFileModel {
+  // Relative to the Revit version folder, e.g. "MyAddin/MyAddin.dll"
+  relativePath: string;
+  sha256: string;
+}
+
+export interface InstallReceiptModel {
+  installedXmlFile: string;
+  installedDllFolder: string;
+  // The .addin file in the registry that the addin was installed from
+  sourceXmlFile: string;
+  sourceDllFolder: string;
+  addinId: string;
+  name: string;
+  vendor: string;
+  version: string;
+  revitVersion: string;
+  installScope: InstallScope;
+  files: ReceiptFileModel[];
+  installedBy: InstallActor;
+  installedAt: string;
+  updatedAt: string;
+  // The version the install was rolled back from. The updater doesn't install that version again
+  rolledBackFrom?: string | null;
+}
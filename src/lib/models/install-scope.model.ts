@@ -0,0 +1,2 @@
+// "user" installs into the current user's Addins folder, "machine" installs for every user of the machine
+export type InstallScope = "user" | "machine";
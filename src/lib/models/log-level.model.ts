@@ -0,0 +1,4 @@
+// How much the app writes to its log files, from least to most
+export const LOG_LEVELS = ["error", "warn", "info", "debug", "trace"] as const;
+
+export type LogLevel = (typeof LOG_LEVELS)[number];
@@ -0,0 +1,25 @@
+import { InstallScope } from "./install-scope.model";
+import { UninstallTargetModel } from "./uninstall-result.model";
+
+export type PendingOperationKind =
+  | { kind: "update"; registryDllFolder: string; localDllFolder: string }
+  | {
+      kind: "install";
+      registryDllFolder: string;
+      forRevitVersions: string[];
+      installScope: InstallScope;
+    }
+  | { kind: "uninstall"; target: UninstallTargetModel };
+
+// An update, install or uninstall that waits for Revit to close or for a retry. Kept across restarts
+export interface PendingOperationModel {
+  key: string;
+  addinName: string;
+  operation: PendingOperationKind;
+  createdAt: string;
+  // How many times applying it failed
+  attempts: number;
+  lastError?: string | null;
+  // When a failed operation is tried again
+  nextAttemptAt?: string | null;
+}
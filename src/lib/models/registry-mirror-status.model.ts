@@ -0,0 +1,9 @@
+export interface RegistryMirrorStatusModel {
+  // Whether the library is showing the offline snapshot because the registry can't be reached
+  isBrowsingSnapshot: boolean;
+  // When the snapshot was taken, in RFC 3339 format
+  snapshotTakenAt: string | undefined;
+  snapshotAgeSecs: number | undefined;
+  // The addins that can be installed or repaired while offline
+  mirroredAddinIds: string[];
+}
@@ -0,0 +1,9 @@
+export interface RevitInstallationModel {
+  // For example "Autodesk Revit 2025"
+  product: string;
+  version: string;
+  // Undefined if the installation was only inferred from an Addins folder
+  installDir: string | undefined;
+  userAddinsFolder: string | undefined;
+  machineAddinsFolder: string | undefined;
+}
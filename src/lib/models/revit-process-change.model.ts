@@ -0,0 +1,13 @@
+// Which Revit versions are running, e.g. "2025"
+export interface RunningRevitVersionsModel {
+  versions: string[];
+  // Set if the version of a running Revit couldn't be determined
+  hasUnknownVersion: boolean;
+}
+
+// Sent with the "revit_processes_changed" event when Revit starts or exits
+export interface RevitProcessChangeModel {
+  started: string[];
+  exited: string[];
+  running: RunningRevitVersionsModel;
+}
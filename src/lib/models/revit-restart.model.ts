@@ -0,0 +1,25 @@
+export type RevitRestartStage =
+  | "closing"
+  | "waitingForExit"
+  | "updating"
+  | "reopening"
+  | "done";
+
+// Sent with the "revit_restart_progress" event while Revit is closed for an update
+export interface RevitRestartProgressModel {
+  stage: RevitRestartStage;
+  message: string;
+}
+
+export interface RevitRestartResultModel {
+  // Whether Revit closed in time. If not, nothing was updated
+  revitClosed: boolean;
+  // The Revit processes that could not be asked to close, e.g. because they have no window.
+  // They keep running and the operations for their versions keep waiting
+  notClosedCount: number;
+  // The pending operations that still wait, e.g. because they failed
+  remainingOperations: number;
+  reopenedCount: number;
+  // What went wrong while updating or reopening Revit
+  error?: string;
+}
@@ -0,0 +1,10 @@
+export interface StatsDbStatusModel {
+  isOnline: boolean;
+  // The folder that holds the stats database
+  path: string;
+  // Failed connection attempts since the last successful one
+  failedAttempts: number;
+  lastError: string | undefined;
+  // When offline, the seconds until the next connection attempt
+  retryInSecs: number | undefined;
+}
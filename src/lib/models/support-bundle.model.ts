@@ -0,0 +1,11 @@
+// What was written to a support bundle zip
+export interface SupportBundleModel {
+  path: string;
+  createdAt: string;
+  appVersion: string;
+  os: string;
+  // The entries in the zip, e.g. "logs/app.log"
+  entries: string[];
+  // The parts that couldn't be collected. The rest of the bundle is still written
+  problems: string[];
+}
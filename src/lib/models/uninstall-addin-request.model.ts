@@ -1,6 +1,9 @@
 import { AddinModel } from "./addin.model";
+import { InstallScope } from "./install-scope.model";
 
 export interface UninstallAddinRequestModel {
   addin: AddinModel;
   forRevitVersions: string[];
+  // Defaults to the addin's own scope, or every scope if the addin's scope isn't known
+  installScope?: InstallScope;
 }
@@ -0,0 +1,22 @@
+import { InstallScope } from "./install-scope.model";
+
+// The files of one addin install, for one Revit version and scope
+export interface UninstallTargetModel {
+  addinName: string;
+  addinId: string;
+  revitVersion: string;
+  installScope: InstallScope;
+  xmlFile: string;
+  dllFolder: string;
+  // The files the app installed in the DLL folder, relative to it. Null if nothing recorded them
+  dllFiles?: string[] | null;
+}
+
+// "removed" left nothing behind, "pending" finishes once Revit closes, "failed" left the addin fully installed
+export type UninstallStatus = "removed" | "pending" | "failed";
+
+export interface UninstallResultModel {
+  target: UninstallTargetModel;
+  status: UninstallStatus;
+  error?: string | null;
+}
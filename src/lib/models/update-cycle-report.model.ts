@@ -0,0 +1,42 @@
+export type UpdateOperationType = "update" | "install" | "uninstall";
+
+export type OperationOutcome =
+  | { status: "succeeded" }
+  // Nothing was done, e.g. the addin was already up to date or a retry isn't due yet
+  | { status: "skipped"; reason: string }
+  // Waits for a Revit version to close
+  | { status: "deferred" }
+  // Kept as a pending operation and retried at retryAt
+  | { status: "failed"; cause: string; attempts: number; retryAt?: string | null };
+
+// Addins all report version "1.0.0" for now, so the modification time of the DLLs tells versions apart
+export interface AddinVersionModel {
+  version: string;
+  modifiedAt: string;
+}
+
+export interface OperationReportModel {
+  addinName: string;
+  operationType: UpdateOperationType;
+  outcome: OperationOutcome;
+  // The installed version before the operation. Unset for installs
+  versionBefore?: AddinVersionModel | null;
+  // The version the operation installs, whether or not it succeeded. Unset for uninstalls
+  versionAfter?: AddinVersionModel | null;
+}
+
+// Everything an update cycle did. Sent with the "update_cycle_report" event and kept in the update history
+export interface UpdateCycleReportModel {
+  id: string;
+  startedAt: string;
+  finishedAt?: string | null;
+  // Unset if the cycle only applied pending operations and didn't scan the registry
+  registryReachable?: boolean | null;
+  operations: OperationReportModel[];
+}
+
+// A page of the update history, newest cycle first
+export interface UpdateHistoryPageModel {
+  cycles: UpdateCycleReportModel[];
+  total: number;
+}
@@ -1,4 +1,4 @@
-export type UpdateNotificationType = "info" | "install" | "warning";
+export type UpdateNotificationType = "info" | "install" | "warning" | "error";
 
 export interface UpdateNotificationModel {
   title: string;
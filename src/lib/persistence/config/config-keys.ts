@@ -1,3 +1,7 @@
+import { InstallScope } from "@/lib/models/install-scope.model";
+import { LogLevel } from "@/lib/models/log-level.model";
+import { AppUpdateChannel } from "@/lib/models/app-update.model";
+
 /**
  * These items lazily exist in the local db's KV Store
  *
@@ -10,4 +14,16 @@ export interface ConfigKeys{
   userName: string;
   userDisciplines:string[];
   localAddinRegistryPath:string;
+  /** Whether new addins are installed for the current user or for the whole machine */
+  defaultInstallScope: InstallScope;
+  /** The folder that holds the shared stats database. Empty to use the default location */
+  statsDbPath: string;
+  /** The longest time between two full registry scans, in minutes. Scans also happen whenever the registry changes */
+  updateCheckIntervalMinutes: number;
+  /** The level of the messages written to the log files. Change it with the `setLogLevel` command, so it applies right away */
+  logLevel: LogLevel;
+  /** Which releases of the app itself are installed */
+  appUpdateChannel: AppUpdateChannel;
+  /** Where the app looks for its own updates. `{{channel}}` is replaced with the channel. Empty to use the channel's default */
+  appUpdateEndpoint: string;
 }
\ No newline at end of file